@@ -0,0 +1,145 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: job_detail.rs
+ * description: Job detail page component
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+use uuid::Uuid;
+
+use crate::application::job_service::{JobPolling, JobService};
+use crate::application::runner_service::RunnerService;
+use crate::components::atoms::typography::*;
+use crate::components::molecules::section::*;
+use crate::components::organisms::job_detail_view::*;
+use crate::components::organisms::job_log_viewer::*;
+use crate::components::organisms::residual_chart::*;
+use crate::components::templates::standard::*;
+use crate::domain::job_context::{JobInfo, JobStatus, ResourceType};
+
+/// Job Detail Page - the job is selected by the `:id` route parameter
+#[component]
+pub fn JobDetail() -> impl IntoView {
+    let params = use_params_map();
+    let job_id = move || {
+        params
+            .read()
+            .get("id")
+            .and_then(|id| Uuid::parse_str(&id).ok())
+    };
+
+    return view! {
+        <Standard>
+            <A href={"/simulate".to_string()} text_class={NORMAL_CLASS.to_string()}>"Back to jobs"</A>
+            {move || match job_id() {
+                Some(job_id) => view! { <JobDetailLoader job_id=job_id /> }.into_any(),
+                None => view! { <P>"Invalid job ID."</P> }.into_any(),
+            }}
+        </Standard>
+    };
+}
+
+#[component]
+fn JobDetailLoader(job_id: Uuid) -> impl IntoView {
+    JobService::poll_jobs(JobPolling::default());
+    let fetched_job = JobService::get_job(job_id);
+    let cached_job = JobService::cached_job(job_id);
+    let runners = RunnerService::get_runners(false);
+
+    // the polled job list keeps the job current, the direct fetch covers the first load.
+    let job = Memo::new(move |_| cached_job.get().or_else(|| fetched_job.get().flatten()));
+    let resources = JobService::get_job_resources(job.into());
+    let log_resource = Memo::new(move |_| {
+        resources
+            .get()
+            .flatten()?
+            .into_iter()
+            .find(|resource| resource.resource_type == ResourceType::Log)
+    });
+    let job_running = Signal::derive(move || {
+        job.with(|job| {
+            job.as_ref()
+                .is_some_and(|job| job.status == JobStatus::Running)
+        })
+    });
+
+    // a resubmit links to the new job, anything else changed this one.
+    let on_action = Callback::new(move |updated: JobInfo| {
+        if updated.id == job_id {
+            fetched_job.refetch();
+        }
+    });
+    let deleted = RwSignal::new(false);
+    let on_deleted = Callback::new(move |_| deleted.set(true));
+
+    let details = move || match job.get() {
+        Some(job) => {
+            let runner = job
+                .runner_id
+                .and_then(|id| runners.get().flatten()?.get(&id).cloned());
+            view! {
+                <JobDetailView
+                    job=job
+                    resources=resources.get().flatten()
+                    runner=runner
+                    on_action=on_action
+                    on_deleted=on_deleted
+                />
+            }
+            .into_any()
+        }
+        None => match fetched_job.get() {
+            Some(None) => view! { <P>"This job could not be found."</P> }.into_any(),
+            _ => view! { <div>"Loading job..."</div> }.into_any(),
+        },
+    };
+
+    // rendered apart from the details so the log is not refetched on every job update. The log is
+    // tailed once, for both the log viewer and residuals read from the log.
+    let live = move || {
+        let log_resource = log_resource.get();
+        let log_tail = log_resource
+            .clone()
+            .map(|resource| JobService::tail_resource(resource, job_running));
+        let log = log_resource.zip(log_tail).map(|(resource, tail)| {
+            view! {
+                <Section level=SectionLevel::H2 title="Log".to_string()>
+                    <JobLogViewer resource=resource tail=tail follow=job_running />
+                </Section>
+            }
+        });
+        view! {
+            <Section level=SectionLevel::H2 title="Residuals".to_string()>
+                <ResidualMonitor job=job.into() log_tail=log_tail />
+            </Section>
+            {log}
+        }
+    };
+
+    return view! {
+        <Show
+            when=move || !deleted.get()
+            fallback=|| view! { <P>"This job was deleted."</P> }
+        >
+            {details}
+            {live}
+        </Show>
+    };
+}
@@ -21,13 +21,182 @@
  */
 
 use leptos::prelude::*;
+use serde_json::Value;
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
 use uuid::Uuid;
 
-use crate::domain::job_context::JobInfo;
+use crate::common::browser_download::save_blob;
+use crate::common::log_tail::LogTail;
+use crate::common::obj::parse_obj;
+use crate::common::polling::poll;
+use crate::common::stl::parse_stl;
+use crate::common::vtk::parse_legacy_vtk;
+use crate::common::vtu::parse_vtu;
+use crate::domain::job_context::{
+    BulkOperation, JobAction, JobInfo, JobResource, JobStatus, ResourceType,
+};
+use crate::domain::mesh::{Mesh, MeshFormat};
+use crate::domain::residual_history::ResidualHistory;
 use crate::domain::user_context::UserContext;
 use crate::infrastructure::fyn_api_client::FynApiClient;
+use crate::infrastructure::fyn_api_error::FynApiError;
 
+// -------------------------------------------------------------------------------------------------
+// Errors
+// -------------------------------------------------------------------------------------------------
+
+/// Why a job operation failed. Backend failures keep their `FynApiError`, so callers can still
+/// tell e.g. a rejected submission from an expired session.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobServiceError {
+    /// Refused before reaching the backend, e.g. the job's status does not allow the operation.
+    Invalid(String),
+    /// A backend request failed, `context` says which step (e.g. "Failed to queue job x").
+    Api { context: String, error: FynApiError },
+    /// Some of a job's files could not be uploaded or deleted, listed by file name.
+    Files {
+        context: String,
+        failures: Vec<(String, FynApiError)>,
+    },
+}
+
+impl JobServiceError {
+    /// Wraps a backend error with the step that failed, for use with `map_err`.
+    fn api(context: String) -> impl FnOnce(FynApiError) -> Self {
+        move |error| JobServiceError::Api { context, error }
+    }
+
+    /// The backend error behind the failure, if it came from a single request.
+    pub fn api_error(&self) -> Option<&FynApiError> {
+        match self {
+            JobServiceError::Api { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for JobServiceError {
+    fn from(message: String) -> Self {
+        JobServiceError::Invalid(message)
+    }
+}
+
+impl fmt::Display for JobServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobServiceError::Invalid(message) => write!(f, "{}", message),
+            JobServiceError::Api { context, error } => write!(f, "{}: {}", context, error),
+            JobServiceError::Files { context, failures } => {
+                let failures = failures
+                    .iter()
+                    .map(|(file_name, error)| format!("{}: {}", file_name, error))
+                    .collect::<Vec<String>>();
+                write!(f, "{}: {}", context, failures.join("; "))
+            }
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Polling
+// -------------------------------------------------------------------------------------------------
+
+/// How often the job list is refreshed. While the tab is hidden the interval doubles on each poll,
+/// up to `max_interval`, and drops back to `interval` once the tab is visible again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JobPolling {
+    pub interval: Duration,
+    pub max_interval: Duration,
+}
+
+impl Default for JobPolling {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            max_interval: Duration::from_secs(120),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl JobPolling {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    fn next_interval(&self, current: Duration, hidden: bool) -> Duration {
+        match hidden {
+            true => (current * 2).clamp(self.interval, self.max_interval.max(self.interval)),
+            false => self.interval,
+        }
+    }
+}
+
+/// A file to attach to a new job as an input resource, e.g. a mesh or geometry. `progress` follows
+/// its upload as a fraction of the file sent.
+#[derive(Debug, Clone)]
+pub struct InputUpload {
+    pub file: web_sys::File,
+    pub description: RwSignal<String>,
+    pub progress: RwSignal<Option<f64>>,
+}
+
+/// How often a followed resource is checked for new content.
+const TAIL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A resource fetched incrementally by `JobService::tail_resource`. It is `Copy`, so one tail can
+/// feed several views of the same resource, e.g. a job's log and the residuals read from it.
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceTail {
+    pub resource_id: Uuid,
+    pub log: RwSignal<LogTail>,
+    pub error: RwSignal<Option<String>>,
+}
+
+fn is_document_hidden() -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .map(|document| document.hidden())
+        .unwrap_or(false)
+}
+
+/// Applies a freshly fetched job list to the cache in place: jobs that no longer exist are
+/// dropped, and only jobs that differ from their cached copy are replaced. Returns whether the
+/// cache changed.
+fn merge_job_list(cache: &mut HashMap<Uuid, JobInfo>, fetched: HashMap<Uuid, JobInfo>) -> bool {
+    let cached_len = cache.len();
+    cache.retain(|id, _| fetched.contains_key(id));
+    let mut changed = cache.len() != cached_len;
+
+    for (id, mut job) in fetched {
+        job.merge_status_history(cache.get(&id));
+        if cache.get(&id) != Some(&job) {
+            cache.insert(id, job);
+            changed = true;
+        }
+    }
+    changed
+}
+
+// -------------------------------------------------------------------------------------------------
+// Job Service
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Clone)]
 pub struct JobService {
     api_client: FynApiClient,
     user_sig: RwSignal<Option<UserContext>>,
@@ -55,7 +224,7 @@ impl JobService {
     }
 
     async fn fetch_and_update_job_list(&self) {
-        if self.user_sig.get().is_none() {
+        if self.user_sig.get_untracked().is_none() {
             return;
         }
 
@@ -64,15 +233,82 @@ impl JobService {
         match response {
             Ok(jobs) => {
                 let job_len = jobs.len();
+                self.user_sig.maybe_update(|user_opt| match user_opt {
+                    Some(user) => {
+                        let was_cached = user.jobs.is_some();
+                        let cache = user.jobs.get_or_insert_with(HashMap::new);
+                        merge_job_list(cache, jobs) || !was_cached
+                    }
+                    None => false,
+                });
+                leptos::logging::log!("Successfully updated jobs: {} found", job_len);
+            }
+            Err(error @ (FynApiError::Unauthorized(_) | FynApiError::Forbidden(_))) => {
+                leptos::logging::error!(
+                    "Not permitted to fetch jobs, check the session: {}",
+                    error
+                );
+            }
+            Err(error) => {
+                leptos::logging::error!("Failed fetch jobs: {}", error);
+            }
+        }
+    }
+
+    /// Tracks sign in/out only, so resources that write to the user context do not refetch
+    /// themselves.
+    fn signed_in(&self) -> Memo<bool> {
+        let user_sig = self.user_sig;
+        Memo::new(move |_| user_sig.with(|user| user.is_some()))
+    }
+
+    /// Writes a fetched job into the cached job list, returning it with its status history.
+    fn cache_job(&self, mut job: JobInfo) -> JobInfo {
+        // only refresh an existing list, a lone job must not pass for the cached list.
+        self.user_sig.maybe_update(|user_opt| {
+            match user_opt.as_mut().and_then(|user| user.jobs.as_mut()) {
+                Some(jobs) => {
+                    job.merge_status_history(jobs.get(&job.id));
+                    let changed = jobs.get(&job.id) != Some(&job);
+                    jobs.insert(job.id, job.clone());
+                    changed
+                }
+                None => {
+                    job.merge_status_history(None);
+                    false
+                }
+            }
+        });
+        job
+    }
+
+    /// Drops a deleted job from the cached list.
+    fn forget_job(&self, job_id: Uuid) {
+        self.user_sig.maybe_update(|user_opt| {
+            match user_opt.as_mut().and_then(|user| user.jobs.as_mut()) {
+                Some(jobs) => jobs.remove(&job_id).is_some(),
+                None => false,
+            }
+        });
+    }
+
+    async fn fetch_and_update_job(&self, job_id: Uuid) -> Option<JobInfo> {
+        self.user_sig.get_untracked()?;
+
+        match self.api_client.get_job(job_id).await {
+            Ok(job) => Some(self.cache_job(job)),
+            Err(FynApiError::NotFound(_)) => {
+                leptos::logging::error!("Job {} does not exist", job_id);
                 self.user_sig.update(|user_opt| {
-                    if let Some(user) = user_opt {
-                        user.jobs = Some(jobs);
+                    if let Some(jobs) = user_opt.as_mut().and_then(|user| user.jobs.as_mut()) {
+                        jobs.remove(&job_id);
                     }
                 });
-                leptos::logging::log!("Successfully updated runners: {} found", job_len);
+                None
             }
             Err(error) => {
-                leptos::logging::error!("Failed fetch runners: {}", error);
+                leptos::logging::error!("Failed fetch job {}: {}", job_id, error);
+                None
             }
         }
     }
@@ -99,4 +335,445 @@ impl JobService {
             }
         })
     }
+
+    /// A single job, always refreshed from the backend so the detail view shows the latest status.
+    pub fn get_job(job_id: Uuid) -> LocalResource<Option<JobInfo>> {
+        let signed_in = JobService::new().signed_in();
+        LocalResource::new(move || async move {
+            if !signed_in.get() {
+                leptos::logging::log!("No user context available");
+                return None;
+            }
+            JobService::new().fetch_and_update_job(job_id).await
+        })
+    }
+
+    /// The resources attached to a job, these are not cached as they change as the job runs.
+    /// They are refetched whenever the job's list of resources changes.
+    pub fn get_job_resources(
+        job: Signal<Option<JobInfo>>,
+    ) -> LocalResource<Option<Vec<JobResource>>> {
+        let signed_in = JobService::new().signed_in();
+        let resource_ids =
+            Memo::new(move |_| job.with(|job| job.as_ref().map(|job| job.resources.clone())));
+        LocalResource::new(move || async move {
+            if !signed_in.get() || resource_ids.get().is_none() {
+                return None;
+            }
+            let job = job.get_untracked()?;
+            match JobService::new().api_client.get_job_resources(&job).await {
+                Ok(resources) => Some(resources),
+                Err(error) => {
+                    leptos::logging::error!("Failed fetch resources for job {}: {}", job.id, error);
+                    None
+                }
+            }
+        })
+    }
+
+    /// Applies a user action to a job, returning the job it produced: the patched job, or for a
+    /// resubmit the newly queued clone.
+    pub async fn apply_action(
+        &self,
+        job: &JobInfo,
+        action: JobAction,
+    ) -> Result<JobInfo, JobServiceError> {
+        let updated = match action.target_status() {
+            Some(status) => {
+                let mut patched_job = job.clone();
+                patched_job.transition_to(status)?;
+                self.api_client
+                    .patch_job(&patched_job)
+                    .await
+                    .map_err(JobServiceError::api(format!(
+                        "Failed to {} job {}",
+                        action, job.name
+                    )))?
+            }
+            None => self.resubmit(job).await?,
+        };
+        leptos::logging::log!("{} applied to job {} ({})", action, job.name, job.id);
+        Ok(self.cache_job(updated))
+    }
+
+    /// Deletes a job and its resources. With `keep_results` its output and result files are
+    /// downloaded first, and nothing is deleted should one of them fail to download. The job is
+    /// kept if any of its resources could not be deleted, so it can be retried.
+    pub async fn delete_job(
+        &self,
+        job: &JobInfo,
+        keep_results: bool,
+    ) -> Result<(), JobServiceError> {
+        if !job.can_delete() {
+            return Err(JobServiceError::Invalid(format!(
+                "Job {} is still in progress ({}), cancel it first",
+                job.name, job.status
+            )));
+        }
+        let resources =
+            self.api_client
+                .get_job_resources(job)
+                .await
+                .map_err(JobServiceError::api(format!(
+                    "Failed to fetch resources of job {}",
+                    job.name
+                )))?;
+
+        if keep_results {
+            for resource in resources
+                .iter()
+                .filter(|resource| resource.resource_type.is_result())
+            {
+                let file_name = resource.file_name();
+                let blob = self
+                    .api_client
+                    .download_job_resource(resource)
+                    .await
+                    .map_err(JobServiceError::api(format!(
+                        "Job {} was not deleted, downloading {} failed",
+                        job.name, file_name
+                    )))?;
+                save_blob(&blob, &file_name).map_err(|e| {
+                    JobServiceError::Invalid(format!(
+                        "Job {} was not deleted, saving {} failed: {}",
+                        job.name, file_name, e
+                    ))
+                })?;
+            }
+        }
+
+        let mut failures = Vec::new();
+        for resource in &resources {
+            if let Err(e) = self.api_client.delete_job_resource(resource.id).await {
+                failures.push((resource.file_name(), e));
+            }
+        }
+        if !failures.is_empty() {
+            return Err(JobServiceError::Files {
+                context: format!(
+                    "Job {} was not deleted, deleting its files failed",
+                    job.name
+                ),
+                failures,
+            });
+        }
+
+        self.api_client
+            .delete_job(job.id)
+            .await
+            .map_err(JobServiceError::api(format!(
+                "Failed to delete job {}",
+                job.name
+            )))?;
+        self.forget_job(job.id);
+        leptos::logging::log!("Deleted job {} ({})", job.name, job.id);
+        Ok(())
+    }
+
+    /// Applies one operation of a bulk edit to a job that `BulkOperation::plan` accepted.
+    pub async fn apply_bulk_operation(
+        &self,
+        job: &JobInfo,
+        operation: BulkOperation,
+    ) -> Result<(), JobServiceError> {
+        match operation {
+            BulkOperation::Cancel => self.apply_action(job, JobAction::Cancel).await.map(|_| ()),
+            BulkOperation::Delete { keep_results } => self.delete_job(job, keep_results).await,
+            BulkOperation::Requeue if job.status.is_finished() => self
+                .apply_action(job, JobAction::Resubmit)
+                .await
+                .map(|_| ()),
+            BulkOperation::Requeue => {
+                let mut queued_job = job.clone();
+                queued_job.transition_to(JobStatus::Queued)?;
+                let updated =
+                    self.api_client
+                        .patch_job(&queued_job)
+                        .await
+                        .map_err(JobServiceError::api(format!(
+                            "Failed to queue job {}",
+                            job.name
+                        )))?;
+                self.cache_job(updated);
+                Ok(())
+            }
+            BulkOperation::Reprioritise(priority) => {
+                let mut patched_job = job.clone();
+                patched_job.priority = priority;
+                let updated =
+                    self.api_client
+                        .patch_job(&patched_job)
+                        .await
+                        .map_err(JobServiceError::api(format!(
+                            "Failed to set priority of job {}",
+                            job.name
+                        )))?;
+                self.cache_job(updated);
+                Ok(())
+            }
+        }
+    }
+
+    /// Submits a clone of a finished job, carrying over its config resource, and queues it. The
+    /// clone is deleted again should its config fail to copy, rather than left behind unqueued.
+    async fn resubmit(&self, job: &JobInfo) -> Result<JobInfo, JobServiceError> {
+        let new_job = job.resubmission()?;
+        let config = self
+            .api_client
+            .get_job_resources(job)
+            .await
+            .map_err(JobServiceError::api(format!(
+                "Failed to fetch resources of job {}",
+                job.name
+            )))?
+            .into_iter()
+            .find(|resource| resource.resource_type == ResourceType::Config);
+
+        let mut created_job =
+            self.api_client
+                .submit_new_job(&new_job)
+                .await
+                .map_err(JobServiceError::api(format!(
+                    "Failed to resubmit job {}",
+                    job.name
+                )))?;
+
+        if let Some(config) = config {
+            if let Err(error) = self
+                .api_client
+                .copy_job_resource(&config, created_job.id)
+                .await
+            {
+                if let Err(e) = self.api_client.delete_job(created_job.id).await {
+                    leptos::logging::error!(
+                        "Failed to delete incomplete resubmission {}: {}",
+                        created_job.id,
+                        e
+                    );
+                }
+                return Err(JobServiceError::Api {
+                    context: format!("Failed to copy the config file of job {}", job.name),
+                    error,
+                });
+            }
+        }
+
+        created_job.transition_to(JobStatus::Queued)?;
+        self.api_client
+            .patch_job(&created_job)
+            .await
+            .map_err(JobServiceError::api(format!(
+                "Failed to queue resubmitted job {}",
+                created_job.name
+            )))
+    }
+
+    /// Submits a new job with `config` uploaded as its config resource and `inputs` as input
+    /// resources. The job is queued only once every upload has succeeded.
+    pub async fn submit_with_config(
+        &self,
+        job: &JobInfo,
+        config: Option<&Value>,
+        inputs: &[InputUpload],
+    ) -> Result<JobInfo, JobServiceError> {
+        let mut created_job =
+            self.api_client
+                .submit_new_job(job)
+                .await
+                .map_err(JobServiceError::api(format!(
+                    "Failed to submit job {}",
+                    job.name
+                )))?;
+
+        if let Some(config) = config {
+            let config_file = FynApiClient::create_json_file(config, "config_file.json")?;
+            self.api_client
+                .upload_job_resource_file(
+                    created_job.id,
+                    config_file,
+                    "CFG",
+                    Some("Application configuration file"),
+                )
+                .await
+                .map_err(JobServiceError::api(format!(
+                    "Failed to upload config file of job {}",
+                    job.name
+                )))?;
+        }
+
+        let mut failures = Vec::new();
+        for input in inputs {
+            let progress = input.progress;
+            progress.set(Some(0.0));
+            let description = input.description.get_untracked();
+            let description = Some(description.as_str()).filter(|desc| !desc.is_empty());
+            let uploaded = self
+                .api_client
+                .upload_job_resource_file_with_progress(
+                    created_job.id,
+                    input.file.clone(),
+                    "IN", // INPUT resource type
+                    description,
+                    move |fraction| progress.set(Some(fraction)),
+                )
+                .await;
+            match uploaded {
+                Ok(()) => progress.set(Some(1.0)),
+                Err(e) => {
+                    progress.set(None);
+                    failures.push((input.file.name(), e));
+                }
+            }
+        }
+        if !failures.is_empty() {
+            return Err(JobServiceError::Files {
+                context: format!("Job {} was not queued, input uploads failed", job.name),
+                failures,
+            });
+        }
+
+        created_job.transition_to(JobStatus::Queued)?;
+        let queued_job =
+            self.api_client
+                .patch_job(&created_job)
+                .await
+                .map_err(JobServiceError::api(format!(
+                    "Failed to queue job {}",
+                    job.name
+                )))?;
+        Ok(self.cache_job(queued_job))
+    }
+
+    /// Keeps `UserContext.jobs` up to date by refetching the job list, so views reading the jobs
+    /// follow status transitions live. The list is refetched straight away when a hidden tab is
+    /// shown again. Polling stops when the calling owner is cleaned up.
+    pub fn poll_jobs(polling: JobPolling) {
+        let service = JobService::new();
+        let delay = Rc::new(Cell::new(polling.interval));
+        let poller = poll(polling.interval, {
+            let delay = delay.clone();
+            move || {
+                let service = service.clone();
+                let delay = delay.clone();
+                async move {
+                    service.fetch_and_update_job_list().await;
+                    delay.set(polling.next_interval(delay.get(), is_document_hidden()));
+                    Some(delay.get())
+                }
+            }
+        });
+
+        // a tab coming back into view is refreshed at once, not after its backed off interval
+        let listener = window_event_listener(leptos::ev::visibilitychange, move |_| {
+            if !is_document_hidden() {
+                delay.set(polling.interval);
+                poller.wake();
+            }
+        });
+        on_cleanup(move || listener.remove());
+    }
+
+    /// A job from the cached job list, kept current by `poll_jobs`.
+    pub fn cached_job(job_id: Uuid) -> Memo<Option<JobInfo>> {
+        let user_sig = JobService::new().user_sig;
+        Memo::new(move |_| {
+            user_sig.with(|user| user.as_ref()?.jobs.as_ref()?.get(&job_id).cloned())
+        })
+    }
+
+    /// Follows a resource as it grows, e.g. the log of a running job. The resource is fetched
+    /// once, then tailed in byte ranges while `follow` is true, with a final fetch once it turns
+    /// false to pick up the last of the output.
+    pub fn tail_resource(resource: JobResource, follow: Signal<bool>) -> ResourceTail {
+        let service = JobService::new();
+        let tail = ResourceTail {
+            resource_id: resource.id,
+            log: RwSignal::new(LogTail::new()),
+            error: RwSignal::new(None),
+        };
+        let fetch_next = Rc::new(Cell::new(true));
+
+        poll(Duration::ZERO, move || {
+            let service = service.clone();
+            let resource = resource.clone();
+            let fetch_next = fetch_next.clone();
+            async move {
+                let following = follow.get_untracked();
+                if fetch_next.replace(following) || following {
+                    let offset = tail.log.with_untracked(|log| log.offset());
+                    match service
+                        .api_client
+                        .fetch_job_resource_from(&resource, offset)
+                        .await
+                    {
+                        Ok(bytes) => {
+                            if !bytes.is_empty() {
+                                tail.log.update(|log| log.append(&bytes));
+                            }
+                            tail.error.set(None);
+                        }
+                        Err(error) => {
+                            leptos::logging::error!(
+                                "Failed to fetch resource {}: {}",
+                                resource.id,
+                                error
+                            );
+                            tail.error.set(Some(error.to_string()));
+                        }
+                    }
+                }
+                Some(TAIL_INTERVAL)
+            }
+        });
+        tail
+    }
+
+    /// Downloads and parses a mesh or result resource, the format is taken from its file name.
+    /// Refetched whenever the selected resource changes.
+    pub fn get_mesh(
+        resource: Signal<Option<JobResource>>,
+    ) -> LocalResource<Option<Result<Mesh, String>>> {
+        LocalResource::new(move || async move {
+            let resource = resource.get()?;
+            Some(JobService::new().fetch_mesh(&resource).await)
+        })
+    }
+
+    async fn fetch_mesh(&self, resource: &JobResource) -> Result<Mesh, String> {
+        let file_name = resource.file_name();
+        let format = MeshFormat::from_file_name(&file_name)
+            .ok_or_else(|| format!("{} is not a supported mesh file", file_name))?;
+        let bytes = self
+            .api_client
+            .fetch_job_resource_from(resource, 0)
+            .await
+            .map_err(|e| format!("Failed to download {}: {}", file_name, e))?;
+
+        match format {
+            MeshFormat::Stl => parse_stl(&bytes),
+            MeshFormat::Obj => parse_obj(&String::from_utf8_lossy(&bytes)),
+            MeshFormat::Vtk => parse_legacy_vtk(&bytes),
+            MeshFormat::Vtu => parse_vtu(&bytes),
+        }
+        .map_err(|e| format!("Failed to read {}: {}", file_name, e))
+    }
+
+    /// Reads residual or monitor columns from a tailed resource, feeding each new line into the
+    /// returned history.
+    pub fn tail_residuals(tail: ResourceTail) -> ReadSignal<ResidualHistory> {
+        let history = RwSignal::new(ResidualHistory::new());
+
+        Effect::new(move |_| {
+            tail.log.with(|log| {
+                let lines_read = history.with_untracked(|history| history.lines_read());
+                if log.lines().len() > lines_read {
+                    history.update(|history| {
+                        log.plain_lines(lines_read)
+                            .for_each(|line| history.push_line(&line))
+                    });
+                }
+            })
+        });
+        history.read_only()
+    }
 }
@@ -27,6 +27,7 @@ use uuid::Uuid;
 use crate::domain::runner_info::RunnerInfo;
 use crate::domain::user_context::UserContext;
 use crate::infrastructure::fyn_api_client::FynApiClient;
+use crate::infrastructure::fyn_api_error::FynApiError;
 
 pub struct RunnerService {
     api_client: FynApiClient,
@@ -72,6 +73,12 @@ impl RunnerService {
                 });
                 leptos::logging::log!("Successfully updated runners: {} found", runners_len);
             }
+            Err(error @ (FynApiError::Unauthorized(_) | FynApiError::Forbidden(_))) => {
+                leptos::logging::error!(
+                    "Not permitted to fetch runners, check the session: {}",
+                    error
+                );
+            }
             Err(error) => {
                 leptos::logging::error!("Failed fetch runners: {}", error);
             }
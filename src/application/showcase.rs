@@ -371,60 +371,84 @@ fn get_example_table_data() -> TableStruct {
             ],
             rows: vec![
                 vec![
-                    "Drag Coefficient".to_string(),
-                    "0.0234".to_string(),
-                    "-".to_string(),
-                    "1247".to_string(),
-                    "Converged".to_string(),
+                    "Drag Coefficient".into(),
+                    "0.0234".into(),
+                    "-".into(),
+                    "1247".into(),
+                    Cell::Badge {
+                        text: "Converged".to_string(),
+                        tone: AlertType::Success,
+                    },
                 ],
                 vec![
-                    "Lift Coefficient".to_string(),
-                    "1.2456".to_string(),
-                    "-".to_string(),
-                    "1247".to_string(),
-                    "Converged".to_string(),
+                    "Lift Coefficient".into(),
+                    "1.2456".into(),
+                    "-".into(),
+                    "1247".into(),
+                    Cell::Badge {
+                        text: "Converged".to_string(),
+                        tone: AlertType::Success,
+                    },
                 ],
                 vec![
-                    "Pressure Drop".to_string(),
-                    "1245.67".to_string(),
-                    "Pa".to_string(),
-                    "892".to_string(),
-                    "Converged".to_string(),
+                    "Pressure Drop".into(),
+                    "1245.67".into(),
+                    "Pa".into(),
+                    "892".into(),
+                    Cell::Badge {
+                        text: "Converged".to_string(),
+                        tone: AlertType::Success,
+                    },
                 ],
                 vec![
-                    "Reynolds Number".to_string(),
-                    "2300000.0".to_string(),
-                    "-".to_string(),
-                    "0".to_string(),
-                    "Input".to_string(),
+                    "Reynolds Number".into(),
+                    "2300000.0".into(),
+                    "-".into(),
+                    "0".into(),
+                    Cell::Badge {
+                        text: "Input".to_string(),
+                        tone: AlertType::Info,
+                    },
                 ],
                 vec![
-                    "Angle of Attack".to_string(),
-                    "15.5".to_string(),
-                    "degrees".to_string(),
-                    "0".to_string(),
-                    "Input".to_string(),
+                    "Angle of Attack".into(),
+                    "15.5".into(),
+                    "degrees".into(),
+                    "0".into(),
+                    Cell::Badge {
+                        text: "Input".to_string(),
+                        tone: AlertType::Info,
+                    },
                 ],
                 vec![
-                    "Mach Number".to_string(),
-                    "0.3".to_string(),
-                    "-".to_string(),
-                    "0".to_string(),
-                    "Input".to_string(),
+                    "Mach Number".into(),
+                    "0.3".into(),
+                    "-".into(),
+                    "0".into(),
+                    Cell::Badge {
+                        text: "Input".to_string(),
+                        tone: AlertType::Info,
+                    },
                 ],
                 vec![
-                    "Turbulence Intensity".to_string(),
-                    "0.05".to_string(),
-                    "%".to_string(),
-                    "1450".to_string(),
-                    "Converged".to_string(),
+                    "Turbulence Intensity".into(),
+                    "0.05".into(),
+                    "%".into(),
+                    "1450".into(),
+                    Cell::Badge {
+                        text: "Converged".to_string(),
+                        tone: AlertType::Success,
+                    },
                 ],
                 vec![
-                    "Wall Y+".to_string(),
-                    "1.2".to_string(),
-                    "-".to_string(),
-                    "2100".to_string(),
-                    "Converged".to_string(),
+                    "Wall Y+".into(),
+                    "1.2".into(),
+                    "-".into(),
+                    "2100".into(),
+                    Cell::Badge {
+                        text: "Converged".to_string(),
+                        tone: AlertType::Success,
+                    },
                 ],
             ],
         },
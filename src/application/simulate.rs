@@ -24,16 +24,23 @@ use leptos::prelude::*;
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::application::job_service::JobService;
+use crate::application::job_service::{JobPolling, JobService};
 use crate::application::runner_service::RunnerService;
 use crate::common::size::*;
+use crate::components::atoms::alert::*;
 use crate::components::atoms::button::*;
+use crate::components::atoms::input::SelectText;
+use crate::components::atoms::typography::*;
 use crate::components::molecules::button_bar::*;
 use crate::components::molecules::table::*;
 use crate::components::organisms::job_config_form::*;
 use crate::components::organisms::job_manager_view::*;
+use crate::components::organisms::mesh_viewer::*;
 use crate::components::organisms::navigation::*;
-use crate::domain::runner_info::RunnerInfo;
+use crate::components::organisms::residual_chart::*;
+use crate::domain::job_context::{JobInfo, JobResource, JobStatus};
+use crate::domain::mesh::MeshFormat;
+use crate::domain::runner_info::{RunnerInfo, RunnerState};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SimulateView {
@@ -42,12 +49,20 @@ pub enum SimulateView {
     JobManagerView,
 }
 
+/// What the right-hand panel of the form layout shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViewerMode {
+    Model,
+    Residuals,
+}
+
 /// Simulate Page - Left toolbar template with dynamic content area
 #[component]
 pub fn Simulate() -> impl IntoView {
     let (current_view, set_current_view) = signal(SimulateView::FormAndViewer);
     let runners_resource = RunnerService::get_runners(false);
     let jobs = JobService::get_jobs(false);
+    JobService::poll_jobs(JobPolling::default());
 
     view! {
         <Navigation/>
@@ -70,7 +85,10 @@ pub fn Simulate() -> impl IntoView {
                             <RunnerView runners=runners_resource.get().flatten() />
                         }.into_any(),
                         SimulateView::JobManagerView => view! {
-                           <JobManagerView jobs=jobs.get().flatten() runners=runners_resource.get().flatten() />
+                           <JobManagerView
+                               jobs=Signal::derive(move || jobs.get().flatten())
+                               runners=Signal::derive(move || runners_resource.get().flatten())
+                           />
                         }.into_any(),
                     }
                 }}
@@ -79,6 +97,20 @@ pub fn Simulate() -> impl IntoView {
     }
 }
 
+/// A badge for a runner's state, green when it can take jobs.
+fn runner_state_cell(state: &RunnerState) -> Cell {
+    let tone = match state {
+        RunnerState::Idle => AlertType::Success,
+        RunnerState::Busy => AlertType::Info,
+        RunnerState::Offline => AlertType::Error,
+        RunnerState::Unregistered | RunnerState::Unknown => AlertType::Warning,
+    };
+    Cell::Badge {
+        text: format!("{:?}", state),
+        tone,
+    }
+}
+
 #[component]
 fn RunnerView(runners: Option<HashMap<Uuid, RunnerInfo>>) -> impl IntoView {
     view! {
@@ -87,15 +119,13 @@ fn RunnerView(runners: Option<HashMap<Uuid, RunnerInfo>>) -> impl IntoView {
                 Some(runner_map) => {
                             let rows = runner_map.iter().map(|(_, runner)| {
                                 vec![
-                                    runner.name.clone(),
-                                    format!("{:?}", runner.state),
-                                    runner.last_contact
-                                        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
-                                        .unwrap_or_else(|| "Never".to_string()),
-                                    runner.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                                    runner.id.to_string(),
+                                    runner.name.clone().into(),
+                                    runner_state_cell(&runner.state),
+                                    Cell::Timestamp(runner.last_contact),
+                                    Cell::Timestamp(Some(runner.created_at)),
+                                    Cell::Uuid(runner.id),
                                 ]
-                            }).collect::<Vec<Vec<String>>>();
+                            }).collect::<Vec<Vec<Cell>>>();
 
                             view! {
                                 <Table table={TableStruct {
@@ -125,7 +155,7 @@ fn RunnerView(runners: Option<HashMap<Uuid, RunnerInfo>>) -> impl IntoView {
                                         ],
                                         rows
                                     }
-                                }}/>
+                                }} interactive=true />
                             }.into_any()
                 },
                 None => view! { <div>"Loading runners..."</div> }.into_any()
@@ -134,7 +164,7 @@ fn RunnerView(runners: Option<HashMap<Uuid, RunnerInfo>>) -> impl IntoView {
     }
 }
 
-/// Form and 3D Viewer Layout Component with working resizable splitter
+/// Form and Viewer Layout Component with working resizable splitter
 #[component]
 fn FormAndViewerLayout(runners: Option<HashMap<Uuid, RunnerInfo>>) -> impl IntoView {
     let (splitter_x, set_splitter_x) = signal(400.0);
@@ -170,8 +200,8 @@ fn FormAndViewerLayout(runners: Option<HashMap<Uuid, RunnerInfo>>) -> impl IntoV
                 <div class="absolute top-1/2 left-1/2 transform -translate-x-1/2 -translate-y-1/2 w-1 h-8 bg-white rounded shadow"></div>
             </div>
 
-            // 3D Viewer section - starts after the splitter
-            <PlaceholderRender splitter_x = {splitter_x} />
+            // Viewer section - starts after the splitter
+            <ViewerPanel splitter_x = {splitter_x} />
 
             // Global mouse handlers for smooth dragging
             {move || if is_dragging.get() {
@@ -197,26 +227,181 @@ fn FormAndViewerLayout(runners: Option<HashMap<Uuid, RunnerInfo>>) -> impl IntoV
     }
 }
 
+/// Right-hand panel, switching between the 3D viewer and residual plots of a job
 #[component]
-fn PlaceholderRender(splitter_x: ReadSignal<f64>) -> impl IntoView {
+fn ViewerPanel(splitter_x: ReadSignal<f64>) -> impl IntoView {
+    let (mode, set_mode) = signal(ViewerMode::Model);
+
     return view! {
         <div
-            class="absolute top-0 h-full bg-surface-100 dark:bg-surface-900"
+            class="absolute top-0 h-full flex flex-col bg-surface-100 dark:bg-surface-900"
             style:left=move || format!("{}px", splitter_x.get() + 8.0)
             style:right="0"
         >
-            <div class="flex items-center justify-center h-full">
-                <div class="text-center">
-                    <div class="text-6xl mb-4">"🎲"</div>
-                    <h3 class="text-xl font-semibold text-surface-900 dark:text-surface-100">"3D Viewer"</h3>
-                    <p class="text-surface-600 dark:text-surface-400 mt-2">
-                        "Splitter at: " {move || format!("{:.0}px", splitter_x.get())}
-                    </p>
-                    <p class="text-xs text-surface-500 dark:text-surface-400 mt-1">
-                        "Drag the vertical line to resize"
-                    </p>
-                </div>
+            <div class="p-2">
+                <ButtonBar items = vec![
+                    view! {<GroupButton button_data=ButtonData::new().size(Size::Sm).text("3D").on_click(move || set_mode.set(ViewerMode::Model))/>},
+                    view! {<GroupButton button_data=ButtonData::new().size(Size::Sm).text("Residuals").on_click(move || set_mode.set(ViewerMode::Residuals))/>},
+                ] />
+            </div>
+            <div class="flex-1 overflow-auto">
+                {move || match mode.get() {
+                    ViewerMode::Model => view! { <ModelPanel /> }.into_any(),
+                    ViewerMode::Residuals => view! { <ResidualPanel /> }.into_any(),
+                }}
             </div>
         </div>
     };
 }
+
+/// Residuals of a chosen job, defaulting to one that is running
+#[component]
+fn ResidualPanel() -> impl IntoView {
+    let jobs = JobService::get_jobs(false);
+    let selected_id = RwSignal::new(String::new());
+
+    let job = Memo::new(move |_| {
+        let jobs = jobs.get().flatten()?;
+        match Uuid::parse_str(&selected_id.get()) {
+            Ok(id) => jobs.get(&id).cloned(),
+            Err(_) => default_residual_job(jobs.values()),
+        }
+    });
+
+    let job_select = move || {
+        view! {
+            <SelectText
+                id="residual_job".to_string()
+                key="residual_job".to_string()
+                options=job_options(jobs.get().flatten())
+                signal=selected_id
+                placeholder=Some("Running job".to_string())
+            />
+        }
+    };
+
+    return view! {
+        <div class="p-4 flex flex-col gap-4">
+            {job_select}
+            {move || match job.with(|job| job.is_some()) {
+                true => view! { <ResidualMonitor job=job.into() /> }.into_any(),
+                false => view! { <P>"Select a job to plot its residuals."</P> }.into_any(),
+            }}
+        </div>
+    };
+}
+
+/// The first running job in the order the job list shows them, if any is running.
+fn default_residual_job<'a>(jobs: impl Iterator<Item = &'a JobInfo>) -> Option<JobInfo> {
+    jobs.filter(|job| job.status == JobStatus::Running)
+        .min_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)))
+        .cloned()
+}
+
+/// Meshes and results attached to a chosen job, shown in the 3D viewer
+#[component]
+fn ModelPanel() -> impl IntoView {
+    let jobs = JobService::get_jobs(false);
+    let selected_job = RwSignal::new(String::new());
+    let selected_file = RwSignal::new(String::new());
+    let load_error = RwSignal::new(None::<String>);
+
+    let job = Memo::new(move |_| {
+        let id = Uuid::parse_str(&selected_job.get()).ok()?;
+        jobs.get().flatten()?.get(&id).cloned()
+    });
+    let resources = JobService::get_job_resources(job.into());
+    let mesh_files = Memo::new(move |_| {
+        resources
+            .get()
+            .flatten()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|resource| MeshFormat::from_file_name(&resource.file_name()).is_some())
+            .collect::<Vec<JobResource>>()
+    });
+    // the first mesh of the job until another is picked.
+    let mesh_file = Memo::new(move |_| {
+        mesh_files.with(|files| {
+            selected_file.with(|selected| {
+                files
+                    .iter()
+                    .find(|resource| resource.id.to_string() == *selected)
+                    .or(files.first())
+                    .cloned()
+            })
+        })
+    });
+
+    let loaded = JobService::get_mesh(mesh_file.into());
+    let mesh = Memo::new(move |_| loaded.with(|loaded| loaded.clone().flatten()?.ok()));
+    Effect::new(move |_| {
+        load_error.set(loaded.with(|loaded| loaded.clone().flatten()?.err()));
+    });
+
+    let job_select = move || {
+        view! {
+            <SelectText
+                id="model_job".to_string()
+                key="model_job".to_string()
+                options=job_options(jobs.get().flatten())
+                signal=selected_job
+                placeholder=Some("Select a job".to_string())
+            />
+        }
+    };
+
+    let file_select = move || {
+        let options = mesh_files
+            .get()
+            .into_iter()
+            .map(|resource| {
+                let file_name = resource.file_name();
+                let format = MeshFormat::from_file_name(&file_name)
+                    .map(|format| format.to_string())
+                    .unwrap_or_default();
+                (
+                    resource.id.to_string(),
+                    format!("{} ({})", file_name, format),
+                )
+            })
+            .collect::<Vec<(String, String)>>();
+
+        match options.is_empty() {
+            true => view! { <P>"No mesh or result files attached to this job."</P> }.into_any(),
+            false => view! {
+                <SelectText
+                    id="model_file".to_string()
+                    key="model_file".to_string()
+                    options=options
+                    signal=selected_file
+                />
+            }
+            .into_any(),
+        }
+    };
+
+    return view! {
+        <div class="h-full flex flex-col">
+            <div class="p-2 flex flex-col gap-2">
+                {job_select}
+                {move || job.with(|job| job.is_some()).then(file_select)}
+                <ErrorAlert message=load_error.read_only() size=Size::Sm />
+            </div>
+            <div class="flex-1 min-h-0">
+                <MeshViewer mesh=mesh.into() />
+            </div>
+        </div>
+    };
+}
+
+/// Select options for a job list, labelled with name and status.
+fn job_options(jobs: Option<HashMap<Uuid, JobInfo>>) -> Vec<(String, String)> {
+    let mut options = jobs
+        .unwrap_or_default()
+        .into_values()
+        .map(|job| (job.id.to_string(), format!("{} ({})", job.name, job.status)))
+        .collect::<Vec<(String, String)>>();
+    options.sort_by(|a, b| a.1.cmp(&b.1));
+    options
+}
@@ -0,0 +1,226 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: ansi.rs
+ * description: Parse ANSI escape sequences in terminal output into styled text spans
+ * ------------------------------------------------------------------------------------------------
+ */
+
+// -------------------------------------------------------------------------------------------------
+//  Styles
+// -------------------------------------------------------------------------------------------------
+
+/// A terminal colour, `Palette` holds the 16 standard colours (8-15 being the bright variants).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnsiColour {
+    Palette(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl AnsiColour {
+    /// Resolves an index into the xterm 256 colour table.
+    fn from_256(index: u8) -> Self {
+        const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        match index {
+            0..=15 => AnsiColour::Palette(index),
+            16..=231 => {
+                let cube = index - 16;
+                AnsiColour::Rgb(
+                    CUBE_LEVELS[(cube / 36) as usize],
+                    CUBE_LEVELS[((cube / 6) % 6) as usize],
+                    CUBE_LEVELS[(cube % 6) as usize],
+                )
+            }
+            _ => {
+                let grey = 8 + 10 * (index - 232);
+                AnsiColour::Rgb(grey, grey, grey)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct AnsiStyle {
+    pub foreground: Option<AnsiColour>,
+    pub background: Option<AnsiColour>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnsiSpan {
+    pub text: String,
+    pub style: AnsiStyle,
+}
+
+// -------------------------------------------------------------------------------------------------
+//  Parser
+// -------------------------------------------------------------------------------------------------
+
+/// Splits terminal output into styled spans. Only SGR (colour and font) sequences are applied,
+/// other escape sequences such as cursor movement are dropped.
+#[derive(Debug, Clone, Default)]
+pub struct AnsiParser {
+    style: AnsiStyle,
+}
+
+impl AnsiParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a single line, the style carries over to the following lines as in a terminal. A
+    /// carriage return followed by more text overwrites the line, as progress bars do.
+    pub fn parse_line(&mut self, line: &str) -> Vec<AnsiSpan> {
+        let mut spans: Vec<AnsiSpan> = Vec::new();
+        let mut overwrite = false;
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => match chars.next() {
+                    // CSI: parameters up to a final byte in '@'..='~'
+                    Some('[') => {
+                        let mut params = String::new();
+                        for c in chars.by_ref() {
+                            if ('@'..='~').contains(&c) {
+                                if c == 'm' {
+                                    self.apply_sgr(&params);
+                                }
+                                break;
+                            }
+                            params.push(c);
+                        }
+                    }
+                    // OSC: terminated by BEL or ESC '\'
+                    Some(']') => {
+                        while let Some(c) = chars.next() {
+                            if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                                break;
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                '\r' => overwrite = true,
+                c if c.is_control() && c != '\t' => {}
+                c => {
+                    if overwrite {
+                        spans.clear();
+                        overwrite = false;
+                    }
+                    match spans.last_mut() {
+                        Some(span) if span.style == self.style => span.text.push(c),
+                        _ => spans.push(AnsiSpan {
+                            text: c.to_string(),
+                            style: self.style,
+                        }),
+                    }
+                }
+            }
+        }
+        spans
+    }
+
+    fn apply_sgr(&mut self, params: &str) {
+        // an omitted parameter means 0 (e.g. `ESC[m`), codes that cannot be read are skipped
+        let codes = params
+            .split(';')
+            .filter_map(|code| match code {
+                "" => Some(0),
+                code => code.parse::<u8>().ok(),
+            })
+            .collect::<Vec<u8>>();
+
+        let mut i = 0;
+        while i < codes.len() {
+            match codes[i] {
+                0 => self.style = AnsiStyle::default(),
+                1 => self.style.bold = true,
+                2 => self.style.dim = true,
+                3 => self.style.italic = true,
+                4 => self.style.underline = true,
+                22 => {
+                    self.style.bold = false;
+                    self.style.dim = false;
+                }
+                23 => self.style.italic = false,
+                24 => self.style.underline = false,
+                code @ 30..=37 => self.style.foreground = Some(AnsiColour::Palette(code - 30)),
+                39 => self.style.foreground = None,
+                code @ 40..=47 => self.style.background = Some(AnsiColour::Palette(code - 40)),
+                49 => self.style.background = None,
+                code @ 90..=97 => self.style.foreground = Some(AnsiColour::Palette(code - 82)),
+                code @ 100..=107 => self.style.background = Some(AnsiColour::Palette(code - 92)),
+                code @ (38 | 48) => {
+                    let (colour, consumed) = extended_colour(&codes[i + 1..]);
+                    match (code, colour) {
+                        (38, Some(colour)) => self.style.foreground = Some(colour),
+                        (48, Some(colour)) => self.style.background = Some(colour),
+                        _ => {}
+                    }
+                    i += consumed;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Reads a `5;n` or `2;r;g;b` colour following a 38/48 code, returning the number of codes used.
+fn extended_colour(codes: &[u8]) -> (Option<AnsiColour>, usize) {
+    match codes {
+        [5, index, ..] => (Some(AnsiColour::from_256(*index)), 2),
+        [2, r, g, b, ..] => (Some(AnsiColour::Rgb(*r, *g, *b)), 4),
+        _ => (None, codes.len()),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+//  Tests
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styles(line: &str) -> Vec<(String, AnsiStyle)> {
+        AnsiParser::new()
+            .parse_line(line)
+            .into_iter()
+            .map(|span| (span.text, span.style))
+            .collect()
+    }
+
+    #[test]
+    fn empty_parameters_reset_the_style() {
+        let spans = styles("\x1b[1mbold\x1b[m plain");
+        assert!(spans[0].1.bold);
+        assert_eq!(spans[1], (" plain".to_string(), AnsiStyle::default()));
+    }
+
+    #[test]
+    fn unreadable_codes_are_skipped() {
+        let spans = styles("\x1b[1mbold\x1b[4:3;300;2mstill bold");
+        assert!(spans[1].1.bold);
+        assert!(spans[1].1.dim);
+        assert!(!spans[1].1.underline);
+    }
+}
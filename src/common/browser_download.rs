@@ -0,0 +1,58 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: browser_download.rs
+ * description: Save in-memory data to the user's machine through the browser
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::prelude::set_timeout;
+use std::time::Duration;
+use wasm_bindgen::JsCast;
+
+/// How long an object URL outlives the click that starts its download.
+const REVOKE_DELAY: Duration = Duration::from_secs(10);
+
+/// Prompts the browser to save a blob under the given file name, via a temporary object URL.
+pub fn save_blob(blob: &web_sys::Blob, file_name: &str) -> Result<(), String> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("No document available")?;
+
+    let url = web_sys::Url::create_object_url_with_blob(blob)
+        .map_err(|e| format!("Failed to create object URL: {:?}", e))?;
+
+    let anchor = document
+        .create_element("a")
+        .map_err(|e| format!("Failed to create link: {:?}", e))?
+        .dyn_into::<web_sys::HtmlAnchorElement>()
+        .map_err(|_| "Failed to cast to HtmlAnchorElement".to_string())?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    // the download starts after the click returns, revoking the URL now could cancel it
+    set_timeout(
+        move || {
+            if let Err(e) = web_sys::Url::revoke_object_url(&url) {
+                leptos::logging::error!("Failed to revoke object URL: {:?}", e);
+            }
+        },
+        REVOKE_DELAY,
+    );
+    Ok(())
+}
@@ -0,0 +1,32 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: clipboard.rs
+ * description: Copying text to the system clipboard
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use wasm_bindgen_futures::JsFuture;
+
+/// Puts `text` on the clipboard. Browsers only allow this in response to a user gesture.
+pub async fn write_text(text: &str) -> Result<(), String> {
+    let window = web_sys::window().ok_or("No window available")?;
+    JsFuture::from(window.navigator().clipboard().write_text(text))
+        .await
+        .map_err(|e| format!("Failed to copy to the clipboard: {:?}", e))?;
+    Ok(())
+}
@@ -0,0 +1,128 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: colour_map.rs
+ * description: Colour maps for shading meshes by a scalar field
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColourMap {
+    Viridis,
+    CoolWarm,
+    Jet,
+    Greyscale,
+}
+
+impl ColourMap {
+    pub const ALL: [ColourMap; 4] = [
+        ColourMap::Viridis,
+        ColourMap::CoolWarm,
+        ColourMap::Jet,
+        ColourMap::Greyscale,
+    ];
+
+    /// Evenly spaced control colours, linearly interpolated between.
+    fn stops(&self) -> &'static [[f32; 3]] {
+        match self {
+            ColourMap::Viridis => &[
+                [0.267, 0.005, 0.329],
+                [0.283, 0.141, 0.458],
+                [0.254, 0.265, 0.530],
+                [0.207, 0.372, 0.553],
+                [0.164, 0.471, 0.558],
+                [0.128, 0.567, 0.551],
+                [0.135, 0.659, 0.518],
+                [0.267, 0.749, 0.441],
+                [0.478, 0.821, 0.320],
+                [0.741, 0.873, 0.150],
+                [0.993, 0.906, 0.144],
+            ],
+            ColourMap::CoolWarm => &[
+                [0.230, 0.299, 0.754],
+                [0.552, 0.690, 0.996],
+                [0.865, 0.865, 0.865],
+                [0.958, 0.604, 0.482],
+                [0.706, 0.016, 0.150],
+            ],
+            ColourMap::Jet => &[
+                [0.0, 0.0, 0.5],
+                [0.0, 0.0, 1.0],
+                [0.0, 1.0, 1.0],
+                [1.0, 1.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [0.5, 0.0, 0.0],
+            ],
+            ColourMap::Greyscale => &[[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]],
+        }
+    }
+
+    /// The colour at `t` between zero and one, values outside are clamped.
+    pub fn sample(&self, t: f32) -> [f32; 3] {
+        let stops = self.stops();
+        let position = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+        let lower = (position.floor() as usize).min(stops.len() - 2);
+        let fraction = position - lower as f32;
+        [0, 1, 2].map(|channel| {
+            stops[lower][channel] + fraction * (stops[lower + 1][channel] - stops[lower][channel])
+        })
+    }
+
+    /// Colours for each value, with `range` mapped onto the full map. Non-finite values are grey.
+    pub fn colours(&self, values: &[f32], (min, max): (f32, f32)) -> Vec<[f32; 3]> {
+        let span = if max > min { max - min } else { 1.0 };
+        values
+            .iter()
+            .map(|value| match value.is_finite() {
+                true => self.sample((value - min) / span),
+                false => [0.5; 3],
+            })
+            .collect()
+    }
+
+    /// A CSS gradient running from the bottom of the map to the top, for legends.
+    pub fn css_gradient(&self) -> String {
+        let stops = self.stops();
+        let colours = stops
+            .iter()
+            .enumerate()
+            .map(|(index, [r, g, b])| {
+                format!(
+                    "rgb({:.0}, {:.0}, {:.0}) {:.1}%",
+                    r * 255.0,
+                    g * 255.0,
+                    b * 255.0,
+                    100.0 * index as f32 / (stops.len() - 1) as f32
+                )
+            })
+            .collect::<Vec<String>>();
+        format!("linear-gradient(to top, {})", colours.join(", "))
+    }
+}
+
+impl fmt::Display for ColourMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColourMap::Viridis => write!(f, "Viridis"),
+            ColourMap::CoolWarm => write!(f, "Cool to warm"),
+            ColourMap::Jet => write!(f, "Jet"),
+            ColourMap::Greyscale => write!(f, "Greyscale"),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: json_schema.rs
+ * description: Helpers for walking JSON Schema documents
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use serde_json::Value;
+
+/// Limit on chained `$ref`s, guards against a definition that refers to itself.
+const MAX_REF_DEPTH: usize = 32;
+
+/// Follows a local `$ref` (e.g. `#/$defs/mesh`) against the root document. Keywords written next to
+/// the `$ref` take precedence over those of the referenced schema. References that cannot be
+/// resolved are dropped and the remaining keywords returned as-is.
+pub fn resolve(schema: &Value, root: &Value) -> Value {
+    let mut resolved = schema.clone();
+    for _ in 0..MAX_REF_DEPTH {
+        let Some(object) = resolved.as_object_mut() else {
+            return resolved;
+        };
+        let Some(reference) = object.remove("$ref") else {
+            return resolved;
+        };
+        let target = reference
+            .as_str()
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| root.pointer(pointer));
+
+        if let Some(Value::Object(target)) = target {
+            let mut merged = target.clone();
+            for (key, value) in object.iter() {
+                merged.insert(key.clone(), value.clone());
+            }
+            resolved = Value::Object(merged);
+        }
+    }
+    resolved
+}
+
+/// The schema's type keyword. For a list of types (e.g. `["number", "null"]`) the first non-null
+/// entry is used, and a missing type is inferred from `properties` or `items`.
+pub fn schema_type(schema: &Value) -> Option<&str> {
+    match &schema["type"] {
+        Value::String(name) => Some(name.as_str()),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(|name| name.as_str())
+            .find(|name| *name != "null"),
+        _ if schema["properties"].is_object() => Some("object"),
+        _ if schema.get("items").is_some() => Some("array"),
+        _ => None,
+    }
+}
+
+/// Display title of a schema, falling back to its property key.
+pub fn title<'a>(schema: &'a Value, key: &'a str) -> &'a str {
+    schema["title"].as_str().unwrap_or(key)
+}
@@ -0,0 +1,115 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: log_tail.rs
+ * description: Incrementally built, ANSI styled view of a growing log file
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use crate::common::ansi::{AnsiParser, AnsiSpan};
+
+/// Lines of a log file that is fetched in byte ranges as it grows. Bytes after the last newline
+/// are held back until the line is complete, so multi-byte characters are never split.
+#[derive(Debug, Clone, Default)]
+pub struct LogTail {
+    offset: u64,
+    pending: Vec<u8>,
+    parser: AnsiParser,
+    lines: Vec<Vec<AnsiSpan>>,
+}
+
+impl LogTail {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of bytes received so far, i.e. where the next range request starts.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn append(&mut self, bytes: &[u8]) {
+        self.offset += bytes.len() as u64;
+        self.pending.extend_from_slice(bytes);
+
+        while let Some(end) = self.pending.iter().position(|byte| *byte == b'\n') {
+            let line = self.pending.drain(..=end).collect::<Vec<u8>>();
+            let text = String::from_utf8_lossy(&line[..end]);
+            let spans = self
+                .parser
+                .parse_line(text.strip_suffix('\r').unwrap_or(&text));
+            self.lines.push(spans);
+        }
+    }
+
+    pub fn lines(&self) -> &[Vec<AnsiSpan>] {
+        &self.lines
+    }
+
+    /// The incomplete last line, if the log does not end with a newline.
+    pub fn partial_line(&self) -> Option<Vec<AnsiSpan>> {
+        match self.pending.is_empty() {
+            true => None,
+            false => Some(
+                self.parser
+                    .clone()
+                    .parse_line(&String::from_utf8_lossy(&self.pending)),
+            ),
+        }
+    }
+
+    /// The complete lines from `start` onwards as plain text, without styling.
+    pub fn plain_lines(&self, start: usize) -> impl Iterator<Item = String> + '_ {
+        self.lines
+            .iter()
+            .skip(start)
+            .map(|line| line.iter().map(|span| span.text.as_str()).collect())
+    }
+
+    /// Number of lines containing `query`, ignoring ASCII case.
+    pub fn count_matching_lines(&self, query: &str) -> usize {
+        if query.is_empty() {
+            return 0;
+        }
+        self.plain_lines(0)
+            .filter(|text| split_matches(text, query).iter().any(|(_, hit)| *hit))
+            .count()
+    }
+}
+
+/// Splits `text` into runs that do and do not match `query`, ignoring ASCII case.
+pub fn split_matches<'a>(text: &'a str, query: &str) -> Vec<(&'a str, bool)> {
+    if query.is_empty() {
+        return vec![(text, false)];
+    }
+
+    let haystack = text.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for (index, _) in haystack.match_indices(&needle) {
+        if index > start {
+            parts.push((&text[start..index], false));
+        }
+        parts.push((&text[index..index + needle.len()], true));
+        start = index + needle.len();
+    }
+    if start < text.len() {
+        parts.push((&text[start..], false));
+    }
+    parts
+}
@@ -21,4 +21,20 @@
  */
 
 pub mod size;
+pub mod ansi;
 pub mod base64_utils;
+pub mod browser_download;
+pub mod browser_file;
+pub mod clipboard;
+pub mod colour_map;
+pub mod json_schema;
+pub mod local_storage;
+pub mod log_tail;
+pub mod obj;
+pub mod orbit_camera;
+pub mod polling;
+pub mod schema_validator;
+pub mod stl;
+pub mod vtk;
+pub mod vtu;
+pub mod webgl_renderer;
@@ -0,0 +1,135 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: obj.rs
+ * description: Parse Wavefront OBJ geometry into triangle meshes
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use crate::domain::mesh::Mesh;
+
+/// Reads the vertices (`v`) and faces (`f`) of an OBJ file, all groups and objects together.
+/// Faces may use any of the `v`, `v/vt`, `v//vn` or `v/vt/vn` forms and negative (relative)
+/// indices; polygons are split into fans. Texture coordinates, normals, materials and lines are
+/// ignored.
+pub fn parse_obj(text: &str) -> Result<Mesh, String> {
+    let mut mesh = Mesh::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let line_error = |what: &str| format!("{} on line {}", what, number + 1);
+        let line = line.split('#').next().unwrap_or_default();
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("v") => {
+                let coordinates = tokens
+                    .take(3)
+                    .map(|token| token.parse::<f32>())
+                    .collect::<Result<Vec<f32>, _>>()
+                    .ok()
+                    .filter(|coordinates| {
+                        coordinates.len() == 3 && coordinates.iter().all(|c| c.is_finite())
+                    })
+                    .ok_or_else(|| line_error("Invalid vertex"))?;
+                mesh.positions
+                    .push([coordinates[0], coordinates[1], coordinates[2]]);
+            }
+            Some("f") => {
+                let polygon = tokens
+                    .map(|token| resolve_index(token, mesh.positions.len()))
+                    .collect::<Option<Vec<u32>>>()
+                    .ok_or_else(|| line_error("Invalid face index"))?;
+                if polygon.len() < 3 {
+                    return Err(line_error("Face with fewer than three vertices"));
+                }
+                for i in 1..polygon.len() - 1 {
+                    mesh.triangles
+                        .push([polygon[0], polygon[i], polygon[i + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match mesh.is_empty() {
+        true => Err("OBJ file contains no faces".to_string()),
+        false => Ok(mesh),
+    }
+}
+
+/// Converts a face vertex such as `7/2/5` or `-1//3` to a zero based position index, given the
+/// number of vertices read so far.
+fn resolve_index(token: &str, vertex_count: usize) -> Option<u32> {
+    let index = token.split('/').next()?.parse::<i64>().ok()?;
+    let resolved = match index {
+        0 => return None,
+        index if index > 0 => index - 1,
+        index => vertex_count as i64 + index,
+    };
+    match (0..vertex_count as i64).contains(&resolved) {
+        true => Some(resolved as u32),
+        false => None,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Tests
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n";
+
+    #[test]
+    fn splits_polygons_into_fans() {
+        let mesh = parse_obj(&format!("{}f 1 2 3 4\n", SQUARE)).unwrap();
+        assert_eq!(mesh.triangles, vec![[0, 1, 2], [0, 2, 3]]);
+    }
+
+    #[test]
+    fn reads_texture_and_normal_indices() {
+        let text = format!(
+            "{}vt 0 0\nvn 0 0 1\nf 1/1/1 2/1/1 3/1/1\nf 1//1 3//1 4//1\n",
+            SQUARE
+        );
+        let mesh = parse_obj(&text).unwrap();
+        assert_eq!(mesh.triangles, vec![[0, 1, 2], [0, 2, 3]]);
+    }
+
+    #[test]
+    fn resolves_negative_indices_against_the_vertices_so_far() {
+        let text = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf -3 -2 -1\nv 1 1 0\nf -3/1 -1 -2\n";
+        let mesh = parse_obj(text).unwrap();
+        assert_eq!(mesh.triangles, vec![[0, 1, 2], [1, 3, 2]]);
+    }
+
+    #[test]
+    fn reports_the_line_of_an_invalid_index() {
+        assert!(parse_obj("v 0 0 0\nf 1 2 3\n")
+            .unwrap_err()
+            .contains("line 2"));
+        assert!(parse_obj(&format!("{}f 0 1 2\n", SQUARE)).is_err());
+        assert!(parse_obj(&format!("{}f -5 1 2\n", SQUARE)).is_err());
+    }
+
+    #[test]
+    fn rejects_files_without_faces() {
+        assert!(parse_obj(SQUARE).is_err());
+    }
+}
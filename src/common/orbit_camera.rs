@@ -0,0 +1,252 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: orbit_camera.rs
+ * description: Orbit camera and the 4x4 matrix maths used by the 3D viewer
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use std::f32::consts::FRAC_PI_2;
+
+use crate::domain::mesh::BoundingBox;
+
+/// Column major, as WebGL expects.
+pub type Matrix4 = [f32; 16];
+
+const MIN_DISTANCE: f32 = 1e-6;
+const PITCH_LIMIT: f32 = FRAC_PI_2 - 0.01;
+
+// -------------------------------------------------------------------------------------------------
+// Orbit Camera
+// -------------------------------------------------------------------------------------------------
+
+/// A camera circling a target point, with +y up. Yaw and pitch are in radians, a zero yaw looks
+/// down the -z axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitCamera {
+    pub target: [f32; 3],
+    pub distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub fov_y: f32,
+    /// Size of the scene, bounds the clipping planes.
+    pub scene_radius: f32,
+}
+
+impl Default for OrbitCamera {
+    fn default() -> Self {
+        Self {
+            target: [0.0; 3],
+            distance: 3.0,
+            yaw: 0.6,
+            pitch: 0.4,
+            fov_y: 45f32.to_radians(),
+            scene_radius: 1.0,
+        }
+    }
+}
+
+impl OrbitCamera {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Centres the box and backs off until it fits the view, keeping the viewing direction.
+    pub fn fit(mut self, bounds: &BoundingBox) -> Self {
+        let radius = bounds.radius().max(MIN_DISTANCE);
+        self.target = bounds.center();
+        self.scene_radius = radius;
+        self.distance = 1.1 * radius / (0.5 * self.fov_y).sin();
+        self
+    }
+
+    /// Rotates about the target, the angles are in radians.
+    pub fn orbit(mut self, delta_yaw: f32, delta_pitch: f32) -> Self {
+        self.yaw += delta_yaw;
+        self.pitch = (self.pitch + delta_pitch).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+        self
+    }
+
+    /// Slides the target across the view, the offsets are fractions of the view height so the
+    /// scene follows the pointer.
+    pub fn pan(mut self, dx: f32, dy: f32) -> Self {
+        let view_height = 2.0 * self.distance * (0.5 * self.fov_y).tan();
+        let (right, up) = (self.right(), self.up());
+        self.target = [0, 1, 2]
+            .map(|axis| self.target[axis] - (dx * right[axis] - dy * up[axis]) * view_height);
+        self
+    }
+
+    /// Scales the distance to the target, factors below one move closer.
+    pub fn zoom(mut self, factor: f32) -> Self {
+        self.distance = (self.distance * factor).max(MIN_DISTANCE);
+        self
+    }
+
+    pub fn eye(&self) -> [f32; 3] {
+        let offset = [
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.cos(),
+        ];
+        [0, 1, 2].map(|axis| self.target[axis] + self.distance * offset[axis])
+    }
+
+    pub fn view_matrix(&self) -> Matrix4 {
+        look_at(self.eye(), self.target, [0.0, 1.0, 0.0])
+    }
+
+    /// The view matrix without its translation, used to orient the axes triad.
+    pub fn rotation_matrix(&self) -> Matrix4 {
+        let mut rotation = self.view_matrix();
+        rotation[12..15].fill(0.0);
+        rotation
+    }
+
+    pub fn projection_matrix(&self, aspect: f32) -> Matrix4 {
+        let far = self.distance + 2.0 * self.scene_radius;
+        let near = (self.distance - 2.0 * self.scene_radius).max(far * 1e-4);
+        perspective(self.fov_y, aspect, near, far)
+    }
+
+    fn right(&self) -> [f32; 3] {
+        [self.yaw.cos(), 0.0, -self.yaw.sin()]
+    }
+
+    fn up(&self) -> [f32; 3] {
+        [
+            -self.pitch.sin() * self.yaw.sin(),
+            self.pitch.cos(),
+            -self.pitch.sin() * self.yaw.cos(),
+        ]
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Matrices
+// -------------------------------------------------------------------------------------------------
+
+#[rustfmt::skip]
+pub fn perspective(fov_y: f32, aspect: f32, near: f32, far: f32) -> Matrix4 {
+    let f = 1.0 / (0.5 * fov_y).tan();
+    let range = 1.0 / (near - far);
+    [
+        f / aspect, 0.0, 0.0, 0.0,
+        0.0, f, 0.0, 0.0,
+        0.0, 0.0, (near + far) * range, -1.0,
+        0.0, 0.0, 2.0 * near * far * range, 0.0,
+    ]
+}
+
+/// Orthographic projection of the box `[-extent, extent]` on every axis.
+#[rustfmt::skip]
+pub fn orthographic(extent: f32) -> Matrix4 {
+    let s = 1.0 / extent;
+    [
+        s, 0.0, 0.0, 0.0,
+        0.0, s, 0.0, 0.0,
+        0.0, 0.0, -s, 0.0,
+        0.0, 0.0, 0.0, 1.0,
+    ]
+}
+
+#[rustfmt::skip]
+pub fn look_at(eye: [f32; 3], target: [f32; 3], up: [f32; 3]) -> Matrix4 {
+    let forward = normalise([0, 1, 2].map(|axis| target[axis] - eye[axis]));
+    let side = normalise(cross(forward, up));
+    let up = cross(side, forward);
+    [
+        side[0], up[0], -forward[0], 0.0,
+        side[1], up[1], -forward[1], 0.0,
+        side[2], up[2], -forward[2], 0.0,
+        -dot(side, eye), -dot(up, eye), dot(forward, eye), 1.0,
+    ]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalise(a: [f32; 3]) -> [f32; 3] {
+    let len = dot(a, a).sqrt().max(f32::EPSILON);
+    a.map(|value| value / len)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Tests
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Maps a world position into view space.
+    fn to_view(camera: &OrbitCamera, position: [f32; 3]) -> [f32; 3] {
+        let view = camera.view_matrix();
+        [0, 1, 2].map(|row| {
+            (0..3)
+                .map(|column| view[4 * column + row] * position[column])
+                .sum::<f32>()
+                + view[12 + row]
+        })
+    }
+
+    #[test]
+    fn fit_centres_the_box_and_keeps_it_in_view() {
+        let bounds = BoundingBox {
+            min: [0.0; 3],
+            max: [2.0; 3],
+        };
+        let camera = OrbitCamera::new().orbit(0.7, 0.3).fit(&bounds);
+        assert_eq!(camera.target, [1.0; 3]);
+
+        let target = to_view(&camera, camera.target);
+        assert!(target[0].abs() < 1e-4 && target[1].abs() < 1e-4);
+        assert!((target[2] + camera.distance).abs() < 1e-4);
+
+        // The enclosing sphere lies within the vertical field of view.
+        let half_angle = (bounds.radius() / camera.distance).asin();
+        assert!(half_angle < 0.5 * camera.fov_y);
+    }
+
+    #[test]
+    fn pan_follows_the_pointer() {
+        let bounds = BoundingBox {
+            min: [0.0; 3],
+            max: [2.0; 3],
+        };
+        let camera = OrbitCamera::new().fit(&bounds);
+        let right = to_view(&camera, camera.pan(0.1, 0.0).target);
+        assert!(right[0] < 0.0 && right[1].abs() < 1e-4);
+        let down = to_view(&camera, camera.pan(0.0, 0.1).target);
+        assert!(down[1] > 0.0 && down[0].abs() < 1e-4);
+    }
+
+    #[test]
+    fn orbit_stops_short_of_the_poles() {
+        let camera = OrbitCamera::new().orbit(0.0, 10.0);
+        assert!(camera.pitch < FRAC_PI_2);
+    }
+}
@@ -0,0 +1,100 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: polling.rs
+ * description: Repeatedly run an async task on a timer while its reactive owner is alive
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::{prelude::*, reactive::spawn_local};
+use std::cell::Cell;
+use std::future::Future;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Handle to a loop started by [`poll`].
+#[derive(Clone)]
+pub struct PollHandle {
+    wake: Rc<dyn Fn()>,
+}
+
+impl PollHandle {
+    /// Runs the next tick now rather than after the pending delay, the loop then carries on from
+    /// the delay that tick returns.
+    pub fn wake(&self) {
+        (self.wake)();
+    }
+}
+
+/// Runs `tick` after `delay`, then again after the delay each tick returns, until a tick returns
+/// `None` or the reactive owner that started the loop is cleaned up.
+pub fn poll<F, Fut>(delay: Duration, tick: F) -> PollHandle
+where
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = Option<Duration>> + 'static,
+{
+    let stopped = Arc::new(AtomicBool::new(false));
+    on_cleanup({
+        let stopped = stopped.clone();
+        move || stopped.store(true, Ordering::Relaxed)
+    });
+    let tick = Rc::new(tick);
+    let generation = Rc::new(Cell::new(0));
+    schedule(delay, tick.clone(), stopped.clone(), generation.clone());
+
+    PollHandle {
+        wake: Rc::new(move || {
+            // timers and ticks of the previous generation no longer schedule the next tick
+            generation.set(generation.get() + 1);
+            schedule(
+                Duration::ZERO,
+                tick.clone(),
+                stopped.clone(),
+                generation.clone(),
+            );
+        }),
+    }
+}
+
+fn schedule<F, Fut>(
+    delay: Duration,
+    tick: Rc<F>,
+    stopped: Arc<AtomicBool>,
+    generation: Rc<Cell<u64>>,
+) where
+    F: Fn() -> Fut + 'static,
+    Fut: Future<Output = Option<Duration>> + 'static,
+{
+    let scheduled = generation.get();
+    set_timeout(
+        move || {
+            if stopped.load(Ordering::Relaxed) || generation.get() != scheduled {
+                return;
+            }
+            spawn_local(async move {
+                if let Some(next_delay) = tick().await {
+                    if !stopped.load(Ordering::Relaxed) && generation.get() == scheduled {
+                        schedule(next_delay, tick, stopped, generation);
+                    }
+                }
+            });
+        },
+        delay,
+    );
+}
@@ -0,0 +1,539 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: schema_validator.rs
+ * description: Validation of JSON values against an application's JSON Schema
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use chrono::{DateTime, NaiveDate, NaiveTime};
+use indexmap::IndexMap;
+use regex::Regex;
+use serde_json::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use uuid::Uuid;
+
+use crate::common::json_schema;
+
+/// Messages keyed by the dotted path of the offending value (e.g. `mesh.cells`, `probes.0`), the
+/// root object itself is keyed by an empty path.
+pub type ValidationErrors = IndexMap<String, Vec<String>>;
+
+/// Checks `instance` against `schema`, collecting every failure rather than stopping at the first.
+/// `$ref`s are resolved against `root`. Unknown formats are treated as annotations and accepted.
+pub fn validate(schema: &Value, root: &Value, instance: &Value) -> ValidationErrors {
+    let mut errors = ValidationErrors::new();
+    validate_value("", schema, root, instance, &mut errors);
+    errors
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    match path {
+        "" => key.to_string(),
+        _ => format!("{}.{}", path, key),
+    }
+}
+
+/// Adds a message for `path` unless it is already there, e.g. a required field sent as `null` fails
+/// both the `required` and the `type` check.
+fn push_error(errors: &mut ValidationErrors, path: &str, message: String) {
+    let messages = errors.entry(path.to_string()).or_default();
+    if !messages.contains(&message) {
+        messages.push(message);
+    }
+}
+
+fn validate_value(
+    path: &str,
+    schema: &Value,
+    root: &Value,
+    instance: &Value,
+    errors: &mut ValidationErrors,
+) {
+    let schema = json_schema::resolve(schema, root);
+
+    if let Some(constant) = schema.get("const") {
+        if constant != instance {
+            push_error(errors, path, format!("Must be {}", constant));
+            return;
+        }
+    }
+
+    if let Some(options) = schema["enum"].as_array() {
+        if !options.contains(instance) {
+            let listed = options
+                .iter()
+                .map(|option| match option {
+                    Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                })
+                .collect::<Vec<String>>();
+            push_error(
+                errors,
+                path,
+                format!("Must be one of: {}", listed.join(", ")),
+            );
+            return;
+        }
+    }
+
+    if let Some(expected) = json_schema::schema_type(&schema) {
+        if !matches_type(expected, instance) {
+            let message = match instance {
+                Value::Null => "This field is required".to_string(),
+                _ => format!("Must be {}", type_name(expected)),
+            };
+            push_error(errors, path, message);
+            return;
+        }
+    }
+
+    validate_conditions(path, &schema, root, instance, errors);
+
+    match instance {
+        Value::Number(number) => validate_number(path, &schema, number.as_f64(), errors),
+        Value::String(text) => validate_string(path, &schema, text, errors),
+        Value::Array(items) => validate_array(path, &schema, root, items, errors),
+        Value::Object(object) => {
+            for key in schema["required"].as_array().into_iter().flatten() {
+                if let Some(key) = key.as_str() {
+                    if object.get(key).is_none_or(Value::is_null) {
+                        push_error(
+                            errors,
+                            &child_path(path, key),
+                            "This field is required".to_string(),
+                        );
+                    }
+                }
+            }
+            for (key, dependents) in schema["dependentRequired"]
+                .as_object()
+                .into_iter()
+                .flatten()
+            {
+                if object.get(key).is_none_or(Value::is_null) {
+                    continue;
+                }
+                for dependent in dependents.as_array().into_iter().flatten() {
+                    if let Some(dependent) = dependent.as_str() {
+                        if object.get(dependent).is_none_or(Value::is_null) {
+                            push_error(
+                                errors,
+                                &child_path(path, dependent),
+                                format!("This field is required when {} is set", key),
+                            );
+                        }
+                    }
+                }
+            }
+            for (key, dependent) in schema["dependentSchemas"].as_object().into_iter().flatten() {
+                if object.get(key).is_some_and(|value| !value.is_null()) {
+                    validate_value(path, dependent, root, instance, errors);
+                }
+            }
+            if let Some(properties) = schema["properties"].as_object() {
+                for (key, value) in object {
+                    if let Some(property) = properties.get(key) {
+                        validate_value(&child_path(path, key), property, root, value, errors);
+                    }
+                }
+            }
+        }
+        Value::Bool(_) | Value::Null => {}
+    }
+}
+
+/// Applies `oneOf`/`anyOf` and `if`/`then`/`else`. When no alternative matches, the errors of the
+/// closest one are reported so they land on the fields the user can fix.
+fn validate_conditions(
+    path: &str,
+    schema: &Value,
+    root: &Value,
+    instance: &Value,
+    errors: &mut ValidationErrors,
+) {
+    for keyword in ["oneOf", "anyOf"] {
+        let Some(options) = schema[keyword].as_array() else {
+            continue;
+        };
+        let results = options
+            .iter()
+            .map(|option| {
+                let mut option_errors = ValidationErrors::new();
+                validate_value(path, option, root, instance, &mut option_errors);
+                option_errors
+            })
+            .collect::<Vec<ValidationErrors>>();
+
+        let matched = results.iter().filter(|result| result.is_empty()).count();
+        if matched == 0 {
+            if let Some(closest) = results.into_iter().min_by_key(|result| result.len()) {
+                for (key, messages) in closest {
+                    errors.entry(key).or_default().extend(messages);
+                }
+            }
+        } else if matched > 1 && keyword == "oneOf" {
+            push_error(errors, path, "Must match exactly one option".to_string());
+        }
+    }
+
+    if let Some(condition) = schema.get("if") {
+        let branch = match validate(condition, root, instance).is_empty() {
+            true => &schema["then"],
+            false => &schema["else"],
+        };
+        if !branch.is_null() {
+            validate_value(path, branch, root, instance, errors);
+        }
+    }
+}
+
+fn matches_type(expected: &str, instance: &Value) -> bool {
+    match expected {
+        "string" => instance.is_string(),
+        "integer" => {
+            instance.is_i64()
+                || instance.is_u64()
+                || instance.as_f64().is_some_and(|value| value.fract() == 0.0)
+        }
+        "number" => instance.is_number(),
+        "boolean" => instance.is_boolean(),
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "null" => instance.is_null(),
+        _ => true,
+    }
+}
+
+fn type_name(expected: &str) -> &str {
+    match expected {
+        "string" => "text",
+        "integer" => "a whole number",
+        "number" => "a number",
+        "boolean" => "true or false",
+        "object" => "an object",
+        "array" => "a list",
+        other => other,
+    }
+}
+
+fn validate_number(path: &str, schema: &Value, value: Option<f64>, errors: &mut ValidationErrors) {
+    let Some(value) = value else {
+        return;
+    };
+
+    // draft 4 spells exclusive bounds as booleans modifying minimum and maximum
+    let exclusive_min = schema["exclusiveMinimum"].as_bool().unwrap_or(false);
+    let exclusive_max = schema["exclusiveMaximum"].as_bool().unwrap_or(false);
+
+    if let Some(minimum) = schema["minimum"].as_f64() {
+        if exclusive_min && value <= minimum {
+            push_error(errors, path, format!("Must be greater than {}", minimum));
+        } else if value < minimum {
+            push_error(errors, path, format!("Must be at least {}", minimum));
+        }
+    }
+    if let Some(maximum) = schema["maximum"].as_f64() {
+        if exclusive_max && value >= maximum {
+            push_error(errors, path, format!("Must be less than {}", maximum));
+        } else if value > maximum {
+            push_error(errors, path, format!("Must be at most {}", maximum));
+        }
+    }
+    if let Some(minimum) = schema["exclusiveMinimum"].as_f64() {
+        if value <= minimum {
+            push_error(errors, path, format!("Must be greater than {}", minimum));
+        }
+    }
+    if let Some(maximum) = schema["exclusiveMaximum"].as_f64() {
+        if value >= maximum {
+            push_error(errors, path, format!("Must be less than {}", maximum));
+        }
+    }
+    if let Some(divisor) = schema["multipleOf"]
+        .as_f64()
+        .filter(|divisor| *divisor > 0.0)
+    {
+        let quotient = value / divisor;
+        if (quotient - quotient.round()).abs() > 1e-9 * quotient.abs().max(1.0) {
+            push_error(errors, path, format!("Must be a multiple of {}", divisor));
+        }
+    }
+}
+
+fn validate_string(path: &str, schema: &Value, text: &str, errors: &mut ValidationErrors) {
+    let length = text.chars().count() as u64;
+
+    if let Some(min_length) = schema["minLength"].as_u64() {
+        if length < min_length {
+            push_error(
+                errors,
+                path,
+                format!("Must be at least {} characters", min_length),
+            );
+        }
+    }
+    if let Some(max_length) = schema["maxLength"].as_u64() {
+        if length > max_length {
+            push_error(
+                errors,
+                path,
+                format!("Must be at most {} characters", max_length),
+            );
+        }
+    }
+    if let Some(pattern) = schema["pattern"].as_str() {
+        // an invalid pattern is a fault in the schema, not in the user's input
+        if let Ok(regex) = Regex::new(pattern) {
+            if !regex.is_match(text) {
+                push_error(errors, path, format!("Must match the pattern {}", pattern));
+            }
+        }
+    }
+    if let Some(format) = schema["format"].as_str() {
+        if let Some(false) = matches_format(format, text) {
+            push_error(
+                errors,
+                path,
+                format!("Must be a valid {}", format_name(format)),
+            );
+        }
+    }
+}
+
+/// Whether `text` is in the given format, `None` for formats that are not checked.
+fn matches_format(format: &str, text: &str) -> Option<bool> {
+    let valid = match format {
+        "email" => text.split_once('@').is_some_and(|(local, domain)| {
+            !local.is_empty() && !domain.starts_with('.') && domain.contains('.')
+        }),
+        "uri" => text.split_once(':').is_some_and(|(scheme, rest)| {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+                && !rest.is_empty()
+        }),
+        "date" => NaiveDate::parse_from_str(text, "%Y-%m-%d").is_ok(),
+        "date-time" => DateTime::parse_from_rfc3339(text).is_ok(),
+        "time" => NaiveTime::parse_from_str(text, "%H:%M:%S%.f").is_ok(),
+        "uuid" => Uuid::parse_str(text).is_ok(),
+        "ipv4" => text.parse::<Ipv4Addr>().is_ok(),
+        "ipv6" => text.parse::<Ipv6Addr>().is_ok(),
+        _ => return None,
+    };
+    Some(valid)
+}
+
+fn format_name(format: &str) -> &str {
+    match format {
+        "email" => "email address",
+        "uri" => "URI",
+        "date" => "date (YYYY-MM-DD)",
+        "date-time" => "date and time (RFC 3339)",
+        "time" => "time (HH:MM:SS)",
+        "uuid" => "UUID",
+        "ipv4" => "IPv4 address",
+        "ipv6" => "IPv6 address",
+        other => other,
+    }
+}
+
+fn validate_array(
+    path: &str,
+    schema: &Value,
+    root: &Value,
+    items: &[Value],
+    errors: &mut ValidationErrors,
+) {
+    if let Some(min_items) = schema["minItems"].as_u64() {
+        if (items.len() as u64) < min_items {
+            push_error(
+                errors,
+                path,
+                format!("Must have at least {} items", min_items),
+            );
+        }
+    }
+    if let Some(max_items) = schema["maxItems"].as_u64() {
+        if (items.len() as u64) > max_items {
+            push_error(
+                errors,
+                path,
+                format!("Must have at most {} items", max_items),
+            );
+        }
+    }
+    if schema["items"].is_object() {
+        for (index, item) in items.iter().enumerate() {
+            validate_value(
+                &child_path(path, &index.to_string()),
+                &schema["items"],
+                root,
+                item,
+                errors,
+            );
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Tests
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn check(schema: &Value, instance: Value) -> ValidationErrors {
+        validate(schema, schema, &instance)
+    }
+
+    fn messages(errors: &ValidationErrors, path: &str) -> Vec<String> {
+        errors.get(path).cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn reports_missing_and_null_required_fields() {
+        let schema = json!({
+            "type": "object",
+            "required": ["name", "steps"],
+            "properties": {
+                "name": { "type": "string" },
+                "steps": { "type": "integer" }
+            }
+        });
+        let errors = check(&schema, json!({ "steps": null }));
+        assert_eq!(messages(&errors, "name"), vec!["This field is required"]);
+        assert_eq!(messages(&errors, "steps"), vec!["This field is required"]);
+        assert!(check(&schema, json!({ "name": "a", "steps": 1 })).is_empty());
+    }
+
+    #[test]
+    fn checks_inclusive_bounds() {
+        let schema = json!({ "type": "number", "minimum": 0, "maximum": 1 });
+        assert!(check(&schema, json!(0)).is_empty());
+        assert!(check(&schema, json!(1)).is_empty());
+        assert_eq!(
+            messages(&check(&schema, json!(-0.5)), ""),
+            vec!["Must be at least 0"]
+        );
+        assert_eq!(
+            messages(&check(&schema, json!(2)), ""),
+            vec!["Must be at most 1"]
+        );
+    }
+
+    #[test]
+    fn checks_exclusive_bounds() {
+        let schema = json!({ "type": "number", "exclusiveMinimum": 0, "exclusiveMaximum": 1 });
+        assert!(check(&schema, json!(0.5)).is_empty());
+        assert_eq!(
+            messages(&check(&schema, json!(0)), ""),
+            vec!["Must be greater than 0"]
+        );
+        assert_eq!(
+            messages(&check(&schema, json!(1)), ""),
+            vec!["Must be less than 1"]
+        );
+    }
+
+    #[test]
+    fn checks_draft_4_exclusive_bounds() {
+        let schema = json!({
+            "type": "number",
+            "minimum": 0,
+            "exclusiveMinimum": true,
+            "maximum": 1,
+            "exclusiveMaximum": true
+        });
+        assert!(check(&schema, json!(0.5)).is_empty());
+        assert_eq!(
+            messages(&check(&schema, json!(0)), ""),
+            vec!["Must be greater than 0"]
+        );
+        assert_eq!(
+            messages(&check(&schema, json!(1)), ""),
+            vec!["Must be less than 1"]
+        );
+    }
+
+    #[test]
+    fn reports_type_mismatches() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "steps": { "type": "integer" },
+                "name": { "type": "string" },
+                "probes": { "type": "array" }
+            }
+        });
+        let errors = check(&schema, json!({ "steps": 1.5, "name": 3, "probes": {} }));
+        assert_eq!(messages(&errors, "steps"), vec!["Must be a whole number"]);
+        assert_eq!(messages(&errors, "name"), vec!["Must be text"]);
+        assert_eq!(messages(&errors, "probes"), vec!["Must be a list"]);
+        assert!(check(&schema, json!({ "steps": 2.0 })).is_empty());
+    }
+
+    #[test]
+    fn keys_nested_errors_by_their_form_field_path() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "mesh": { "$ref": "#/$defs/mesh" },
+                "probes": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["x"],
+                        "properties": { "x": { "type": "number" } }
+                    }
+                }
+            },
+            "$defs": {
+                "mesh": {
+                    "type": "object",
+                    "required": ["cells"],
+                    "properties": {
+                        "cells": { "type": "integer", "minimum": 1 },
+                        "refinement": {
+                            "type": "object",
+                            "properties": { "levels": { "type": "integer", "maximum": 5 } }
+                        }
+                    }
+                }
+            }
+        });
+        let errors = check(
+            &schema,
+            json!({
+                "mesh": { "cells": 0, "refinement": { "levels": 9 } },
+                "probes": [{ "x": 1 }, {}]
+            }),
+        );
+        assert_eq!(
+            errors.keys().collect::<Vec<_>>(),
+            vec!["mesh.cells", "mesh.refinement.levels", "probes.1.x"]
+        );
+        assert_eq!(messages(&errors, "mesh.cells"), vec!["Must be at least 1"]);
+        assert_eq!(
+            messages(&errors, "probes.1.x"),
+            vec!["This field is required"]
+        );
+    }
+}
@@ -0,0 +1,224 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: stl.rs
+ * description: Parse ASCII and binary STL files into triangle meshes
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use crate::domain::mesh::Mesh;
+
+const HEADER_LEN: usize = 80;
+const TRIANGLE_LEN: usize = 50; // normal, three vertices and an attribute word
+
+/// Reads an STL file, binary or ASCII. Facet normals are ignored, the viewer derives its own from
+/// the winding.
+///
+/// Binary files may also start with `solid`, so the format is decided by whether the size
+/// matches the triangle count in the binary header.
+pub fn parse_stl(bytes: &[u8]) -> Result<Mesh, String> {
+    match binary_triangle_count(bytes) {
+        Some(count) if binary_len(count) == Some(bytes.len()) => parse_binary(bytes, count),
+        _ if is_ascii_stl(bytes) => parse_ascii(&String::from_utf8_lossy(bytes)),
+        Some(count) => Err(format!(
+            "Binary STL declares {} triangles but holds {} bytes",
+            count,
+            bytes.len()
+        )),
+        None => Err("File is too short to be an STL".to_string()),
+    }
+}
+
+fn binary_triangle_count(bytes: &[u8]) -> Option<usize> {
+    let count = bytes.get(HEADER_LEN..HEADER_LEN + 4)?;
+    Some(u32::from_le_bytes(count.try_into().ok()?) as usize)
+}
+
+/// Size of a binary file holding `count` triangles, `None` if it does not fit in memory anyway.
+fn binary_len(count: usize) -> Option<usize> {
+    count.checked_mul(TRIANGLE_LEN)?.checked_add(HEADER_LEN + 4)
+}
+
+fn is_ascii_stl(bytes: &[u8]) -> bool {
+    let start = bytes.iter().position(|b| !b.is_ascii_whitespace());
+    start.is_some_and(|start| bytes[start..].starts_with(b"solid"))
+}
+
+fn parse_binary(bytes: &[u8], count: usize) -> Result<Mesh, String> {
+    let mut mesh = Mesh::new();
+    mesh.positions.reserve(3 * count);
+    mesh.triangles.reserve(count);
+
+    for record in bytes[HEADER_LEN + 4..].chunks_exact(TRIANGLE_LEN) {
+        let first = mesh.positions.len() as u32;
+        for vertex in record[12..48].chunks_exact(12) {
+            let coordinate = |i: usize| {
+                f32::from_le_bytes([
+                    vertex[4 * i],
+                    vertex[4 * i + 1],
+                    vertex[4 * i + 2],
+                    vertex[4 * i + 3],
+                ])
+            };
+            mesh.positions
+                .push([coordinate(0), coordinate(1), coordinate(2)]);
+        }
+        mesh.triangles.push([first, first + 1, first + 2]);
+    }
+    validate(&mesh)?;
+    Ok(mesh)
+}
+
+/// Reads `facet ... outer loop / vertex x y z ... endloop / endfacet` blocks. Loops with more
+/// than three vertices, which some writers emit for planar polygons, are split into a fan.
+fn parse_ascii(text: &str) -> Result<Mesh, String> {
+    let mut mesh = Mesh::new();
+    let mut polygon: Vec<u32> = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("vertex") => {
+                let coordinates = tokens
+                    .map(|token| token.parse::<f32>())
+                    .collect::<Result<Vec<f32>, _>>()
+                    .ok()
+                    .filter(|coordinates| coordinates.len() == 3)
+                    .ok_or_else(|| format!("Invalid vertex on line {}", number + 1))?;
+                polygon.push(mesh.positions.len() as u32);
+                mesh.positions
+                    .push([coordinates[0], coordinates[1], coordinates[2]]);
+            }
+            Some("endloop") => {
+                if polygon.len() < 3 {
+                    return Err(format!(
+                        "Facet ending on line {} has fewer than three vertices",
+                        number + 1
+                    ));
+                }
+                for i in 1..polygon.len() - 1 {
+                    mesh.triangles
+                        .push([polygon[0], polygon[i], polygon[i + 1]]);
+                }
+                polygon.clear();
+            }
+            _ => {}
+        }
+    }
+
+    validate(&mesh)?;
+    Ok(mesh)
+}
+
+fn validate(mesh: &Mesh) -> Result<(), String> {
+    if mesh.is_empty() {
+        return Err("STL file contains no facets".to_string());
+    }
+    match mesh
+        .positions
+        .iter()
+        .flatten()
+        .all(|value| value.is_finite())
+    {
+        true => Ok(()),
+        false => Err("STL file contains non-finite coordinates".to_string()),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Tests
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASCII_TRIANGLE: &str = "solid t
+ facet normal 0 0 1
+  outer loop
+   vertex 0 0 0
+   vertex 1 0 0
+   vertex 0 1 0
+  endloop
+ endfacet
+endsolid t
+";
+
+    fn binary_stl(header: &[u8], triangles: &[[[f32; 3]; 3]]) -> Vec<u8> {
+        let mut bytes = header.to_vec();
+        bytes.resize(HEADER_LEN, 0);
+        bytes.extend((triangles.len() as u32).to_le_bytes());
+        for triangle in triangles {
+            bytes.extend([0u8; 12]);
+            for coordinate in triangle.iter().flatten() {
+                bytes.extend(coordinate.to_le_bytes());
+            }
+            bytes.extend([0u8; 2]);
+        }
+        bytes
+    }
+
+    #[test]
+    fn reads_ascii_files() {
+        let mesh = parse_stl(ASCII_TRIANGLE.as_bytes()).unwrap();
+        assert_eq!(mesh.positions[1], [1.0, 0.0, 0.0]);
+        assert_eq!(mesh.triangles, vec![[0, 1, 2]]);
+    }
+
+    #[test]
+    fn reads_binary_files() {
+        let triangles = [
+            [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            [[0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        ];
+        let mesh = parse_stl(&binary_stl(b"exported", &triangles)).unwrap();
+        assert_eq!(mesh.positions.len(), 6);
+        assert_eq!(mesh.positions[3], [0.0, 0.0, 1.0]);
+        assert_eq!(mesh.triangles, vec![[0, 1, 2], [3, 4, 5]]);
+    }
+
+    #[test]
+    fn reads_binary_files_whose_header_starts_with_solid() {
+        let triangles = [[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]];
+        let mesh = parse_stl(&binary_stl(b"solid exported by a CAD tool", &triangles)).unwrap();
+        assert_eq!(mesh.triangles, vec![[0, 1, 2]]);
+    }
+
+    #[test]
+    fn rejects_truncated_binary_files() {
+        let triangles = [[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]; 2];
+        let mut bytes = binary_stl(b"exported", &triangles);
+        bytes.pop();
+        assert!(parse_stl(&bytes).unwrap_err().contains("declares 2"));
+    }
+
+    #[test]
+    fn rejects_binary_files_declaring_too_many_triangles() {
+        let mut bytes = binary_stl(b"exported", &[]);
+        bytes[HEADER_LEN..].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(parse_stl(&bytes)
+            .unwrap_err()
+            .contains("declares 4294967295"));
+        assert_eq!(binary_len(usize::MAX), None);
+    }
+
+    #[test]
+    fn rejects_malformed_ascii_files() {
+        assert!(parse_stl(b"solid x\n vertex 1 2\n").is_err());
+        assert!(parse_stl(b"solid x\nendsolid").is_err());
+    }
+}
@@ -0,0 +1,768 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: vtk.rs
+ * description: Parse legacy VTK files and extract the surface of VTK grids
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use std::collections::HashMap;
+
+use crate::domain::mesh::{FieldLocation, Mesh, MeshField};
+
+// -------------------------------------------------------------------------------------------------
+// VTK Grid
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VtkCell {
+    pub cell_type: u8,
+    pub points: Vec<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VtkArray {
+    pub name: String,
+    pub components: usize,
+    pub values: Vec<f32>,
+}
+
+/// Points, cells and their data arrays as read from a VTK file, before the surface is extracted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VtkGrid {
+    pub points: Vec<[f32; 3]>,
+    pub cells: Vec<VtkCell>,
+    pub point_data: Vec<VtkArray>,
+    pub cell_data: Vec<VtkArray>,
+}
+
+impl VtkGrid {
+    /// The viewable surface: 2D cells as they are, plus the faces of 3D cells that are not shared
+    /// with a neighbour. Points, lines and polyhedra are skipped. Cell data is copied to each
+    /// triangle cut from the cell.
+    pub fn into_surface(self) -> Result<Mesh, String> {
+        self.check()?;
+
+        let mut polygons: Vec<(usize, Vec<u32>)> = Vec::new();
+        let mut solid_faces: HashMap<Vec<u32>, Option<(usize, Vec<u32>)>> = HashMap::new();
+        for (cell_index, cell) in self.cells.iter().enumerate() {
+            for face in cell_faces(cell) {
+                match is_solid(cell.cell_type) {
+                    false => polygons.push((cell_index, face)),
+                    true => {
+                        let mut key = face.clone();
+                        key.sort_unstable();
+                        // a face met twice is interior, `None` marks it as shared.
+                        solid_faces
+                            .entry(key)
+                            .and_modify(|entry| *entry = None)
+                            .or_insert(Some((cell_index, face)));
+                    }
+                }
+            }
+        }
+        let mut boundary = solid_faces.into_values().flatten().collect::<Vec<_>>();
+        boundary.sort_unstable();
+        polygons.extend(boundary);
+
+        let mut triangles = Vec::new();
+        let mut triangle_cells = Vec::new();
+        for (cell_index, polygon) in &polygons {
+            for i in 1..polygon.len().saturating_sub(1) {
+                triangles.push([polygon[0], polygon[i], polygon[i + 1]]);
+                triangle_cells.push(*cell_index);
+            }
+        }
+        if triangles.is_empty() {
+            return Err("VTK file contains no surface or volume cells".to_string());
+        }
+
+        let point_fields = self.point_data.into_iter().map(|array| MeshField {
+            name: array.name,
+            location: FieldLocation::Point,
+            components: array.components,
+            values: array.values,
+        });
+        let cell_fields = self.cell_data.into_iter().map(|array| MeshField {
+            name: array.name,
+            location: FieldLocation::Cell,
+            components: array.components,
+            values: triangle_cells
+                .iter()
+                .flat_map(|cell| {
+                    array.values[cell * array.components..(cell + 1) * array.components]
+                        .iter()
+                        .copied()
+                })
+                .collect(),
+        });
+
+        Ok(Mesh {
+            positions: self.points,
+            triangles,
+            fields: point_fields.chain(cell_fields).collect(),
+        })
+    }
+
+    fn check(&self) -> Result<(), String> {
+        let point_count = self.points.len();
+        if let Some(cell) = self.cells.iter().find(|cell| {
+            cell.points
+                .iter()
+                .any(|point| *point as usize >= point_count)
+        }) {
+            return Err(format!(
+                "A cell of type {} refers to a point that does not exist",
+                cell.cell_type
+            ));
+        }
+
+        let expected = [
+            (&self.point_data, point_count, "point"),
+            (&self.cell_data, self.cells.len(), "cell"),
+        ];
+        for (arrays, count, location) in expected {
+            if let Some(array) = arrays.iter().find(|array| {
+                count.checked_mul(array.components) != Some(array.values.len())
+                    || array.components == 0
+            }) {
+                return Err(format!(
+                    "The {} array {} holds {} values, expected {} tuples",
+                    location,
+                    array.name,
+                    array.values.len(),
+                    count
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_solid(cell_type: u8) -> bool {
+    matches!(cell_type, 10..=14 | 24..=27)
+}
+
+/// The polygons bounding a cell, by VTK cell type. Quadratic cells are drawn by their corners.
+fn cell_faces(cell: &VtkCell) -> Vec<Vec<u32>> {
+    let p = &cell.points;
+    let faces = |corners: &[&[usize]]| -> Vec<Vec<u32>> {
+        match corners
+            .iter()
+            .flat_map(|face| face.iter())
+            .all(|i| *i < p.len())
+        {
+            true => corners
+                .iter()
+                .map(|face| face.iter().map(|i| p[*i]).collect())
+                .collect(),
+            false => Vec::new(),
+        }
+    };
+
+    match cell.cell_type {
+        // triangle, polygon, quadratic triangle
+        5 | 22 => faces(&[&[0, 1, 2]]),
+        7 if p.len() >= 3 => vec![p.clone()],
+        // triangle strip, alternating the winding
+        6 => (2..p.len())
+            .map(|i| match i % 2 {
+                0 => vec![p[i - 2], p[i - 1], p[i]],
+                _ => vec![p[i - 1], p[i - 2], p[i]],
+            })
+            .collect(),
+        // pixel
+        8 => faces(&[&[0, 1, 3, 2]]),
+        // quad, quadratic quad
+        9 | 23 => faces(&[&[0, 1, 2, 3]]),
+        // tetrahedron
+        10 | 24 => faces(&[&[0, 1, 3], &[1, 2, 3], &[2, 0, 3], &[0, 2, 1]]),
+        // voxel
+        11 => faces(&[
+            &[0, 4, 6, 2],
+            &[1, 3, 7, 5],
+            &[0, 1, 5, 4],
+            &[2, 6, 7, 3],
+            &[0, 2, 3, 1],
+            &[4, 5, 7, 6],
+        ]),
+        // hexahedron
+        12 | 25 => faces(&[
+            &[0, 4, 7, 3],
+            &[1, 2, 6, 5],
+            &[0, 1, 5, 4],
+            &[3, 7, 6, 2],
+            &[0, 3, 2, 1],
+            &[4, 5, 6, 7],
+        ]),
+        // wedge
+        13 | 26 => faces(&[
+            &[0, 1, 2],
+            &[3, 5, 4],
+            &[0, 3, 4, 1],
+            &[1, 4, 5, 2],
+            &[2, 5, 3, 0],
+        ]),
+        // pyramid
+        14 | 27 => faces(&[
+            &[0, 3, 2, 1],
+            &[0, 1, 4],
+            &[1, 2, 4],
+            &[2, 3, 4],
+            &[3, 0, 4],
+        ]),
+        _ => Vec::new(),
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Legacy Format
+// -------------------------------------------------------------------------------------------------
+
+/// Reads a legacy `.vtk` file holding an `UNSTRUCTURED_GRID` or `POLYDATA` dataset, ASCII or
+/// binary, in the layout of both the classic (up to 4.2) and the 5.1 cell arrays.
+pub fn parse_legacy_vtk(bytes: &[u8]) -> Result<Mesh, String> {
+    let mut reader = LegacyReader::new(bytes);
+
+    let version_line = reader.line();
+    if !version_line.starts_with("# vtk DataFile") {
+        return Err("Not a legacy VTK file".to_string());
+    }
+    reader.line(); // title
+    reader.binary = match reader.line().trim().to_ascii_uppercase().as_str() {
+        "ASCII" => false,
+        "BINARY" => true,
+        other => return Err(format!("Unknown VTK encoding {}", other)),
+    };
+
+    let mut grid = VtkGrid::default();
+    let mut poly_cells: [Vec<VtkCell>; 4] = Default::default(); // vertices, lines, polygons, strips
+    let mut cell_types: Option<Vec<u8>> = None;
+    let mut data_location: Option<FieldLocation> = None;
+
+    while let Some(keyword) = reader.token() {
+        match keyword.to_ascii_uppercase().as_str() {
+            "DATASET" => {
+                let dataset = reader.expect_token("dataset type")?;
+                if !matches!(dataset.as_str(), "UNSTRUCTURED_GRID" | "POLYDATA") {
+                    return Err(format!("VTK {} datasets are not supported", dataset));
+                }
+            }
+            "POINTS" => {
+                let count = reader.count("point count")?;
+                let data_type = reader.expect_token("point data type")?;
+                let values = reader.values(value_count(count, 3)?, &data_type)?;
+                grid.points = values
+                    .chunks_exact(3)
+                    .map(|xyz| [xyz[0] as f32, xyz[1] as f32, xyz[2] as f32])
+                    .collect();
+            }
+            "CELLS" => {
+                grid.cells = reader.cells()?;
+            }
+            "CELL_TYPES" => {
+                let count = reader.count("cell type count")?;
+                let values = reader.values(count, "int")?;
+                cell_types = Some(values.into_iter().map(|value| value as u8).collect());
+            }
+            section @ ("VERTICES" | "LINES" | "POLYGONS" | "TRIANGLE_STRIPS") => {
+                let (slot, cell_type) = match section {
+                    "VERTICES" => (0, 2),
+                    "LINES" => (1, 4),
+                    "POLYGONS" => (2, 7),
+                    _ => (3, 6),
+                };
+                poly_cells[slot] = reader
+                    .cells()?
+                    .into_iter()
+                    .map(|cell| VtkCell {
+                        cell_type,
+                        points: cell.points,
+                    })
+                    .collect();
+            }
+            "POINT_DATA" => {
+                reader.count("point data count")?;
+                data_location = Some(FieldLocation::Point);
+            }
+            "CELL_DATA" => {
+                reader.count("cell data count")?;
+                data_location = Some(FieldLocation::Cell);
+            }
+            // dataset wide field data, such as the solution time, has nothing to colour by.
+            "FIELD" if data_location.is_none() => {
+                reader.attribute("FIELD", 0)?;
+            }
+            "SCALARS"
+            | "VECTORS"
+            | "NORMALS"
+            | "TENSORS"
+            | "TEXTURE_COORDINATES"
+            | "FIELD"
+            | "COLOR_SCALARS"
+            | "LOOKUP_TABLE" => {
+                let keyword = keyword.to_ascii_uppercase();
+                match data_location {
+                    Some(FieldLocation::Point) => {
+                        let arrays = reader.attribute(&keyword, grid.points.len())?;
+                        grid.point_data.extend(arrays);
+                    }
+                    Some(FieldLocation::Cell) => {
+                        let count = grid.cells.len().max(poly_cells.iter().map(Vec::len).sum());
+                        let arrays = reader.attribute(&keyword, count)?;
+                        grid.cell_data.extend(arrays);
+                    }
+                    None => {
+                        return Err(format!("{} found before POINT_DATA or CELL_DATA", keyword))
+                    }
+                }
+            }
+            "METADATA" => reader.skip_metadata(),
+            _ => return Err(format!("Unexpected VTK keyword {}", keyword)),
+        }
+    }
+
+    // polydata cells are numbered vertices first, then lines, polygons and strips.
+    if grid.cells.is_empty() {
+        grid.cells = poly_cells.into_iter().flatten().collect();
+    } else {
+        let cell_types = cell_types.ok_or("VTK file has CELLS but no CELL_TYPES")?;
+        if cell_types.len() != grid.cells.len() {
+            return Err("VTK file has a different number of CELLS and CELL_TYPES".to_string());
+        }
+        for (cell, cell_type) in grid.cells.iter_mut().zip(cell_types) {
+            cell.cell_type = cell_type;
+        }
+    }
+    grid.into_surface()
+}
+
+/// Reads whitespace separated tokens, switching to big endian binary for data blocks in binary
+/// files.
+struct LegacyReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    binary: bool,
+}
+
+impl<'a> LegacyReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            binary: false,
+        }
+    }
+
+    fn line(&mut self) -> String {
+        let rest = &self.bytes[self.position..];
+        let end = rest.iter().position(|b| *b == b'\n').unwrap_or(rest.len());
+        self.position += (end + 1).min(rest.len());
+        String::from_utf8_lossy(&rest[..end])
+            .trim_end_matches('\r')
+            .to_string()
+    }
+
+    fn token(&mut self) -> Option<String> {
+        while self.bytes.get(self.position)?.is_ascii_whitespace() {
+            self.position += 1;
+        }
+        let start = self.position;
+        while self
+            .bytes
+            .get(self.position)
+            .is_some_and(|b| !b.is_ascii_whitespace())
+        {
+            self.position += 1;
+        }
+        Some(String::from_utf8_lossy(&self.bytes[start..self.position]).to_string())
+    }
+
+    fn expect_token(&mut self, what: &str) -> Result<String, String> {
+        self.token()
+            .ok_or_else(|| format!("VTK file ended while reading the {}", what))
+    }
+
+    fn count(&mut self, what: &str) -> Result<usize, String> {
+        let token = self.expect_token(what)?;
+        token
+            .parse::<usize>()
+            .map_err(|_| format!("Invalid {} {} in VTK file", what, token))
+    }
+
+    /// Reads `count` numbers of a VTK data type, as text or as binary starting on the next line.
+    fn values(&mut self, count: usize, data_type: &str) -> Result<Vec<f64>, String> {
+        if !self.binary {
+            return (0..count)
+                .map(|_| {
+                    let token = self.expect_token("data values")?;
+                    token
+                        .parse::<f64>()
+                        .map_err(|_| format!("Invalid value {} in VTK file", token))
+                })
+                .collect();
+        }
+
+        let size = binary_size(data_type)?;
+        self.line(); // the rest of the keyword line
+        let data = count
+            .checked_mul(size)
+            .and_then(|len| self.position.checked_add(len))
+            .and_then(|end| self.bytes.get(self.position..end))
+            .ok_or("VTK file ended inside a binary data block")?;
+        self.position += data.len();
+        Ok(data
+            .chunks_exact(size)
+            .map(|value| decode_big_endian(value, data_type))
+            .collect())
+    }
+
+    /// Reads a cell block, positioned after its keyword: counts and point lists in the classic
+    /// layout, or `OFFSETS` and `CONNECTIVITY` arrays in the 5.1 layout.
+    fn cells(&mut self) -> Result<Vec<VtkCell>, String> {
+        let first = self.count("cell count")?;
+        let size = self.count("cell list size")?;
+
+        let saved = self.position;
+        if self.token().as_deref() == Some("OFFSETS") {
+            let offsets_type = self.expect_token("offsets type")?;
+            let offsets = self.values(first, &offsets_type)?;
+            if self.token().as_deref() != Some("CONNECTIVITY") {
+                return Err("VTK file has OFFSETS without CONNECTIVITY".to_string());
+            }
+            let connectivity_type = self.expect_token("connectivity type")?;
+            let connectivity = self.values(size, &connectivity_type)?;
+            return offsets
+                .windows(2)
+                .map(|range| {
+                    let points = connectivity
+                        .get(range[0] as usize..range[1] as usize)
+                        .ok_or("VTK cell offsets run past the connectivity")?;
+                    Ok(VtkCell {
+                        cell_type: 0,
+                        points: points.iter().map(|point| *point as u32).collect(),
+                    })
+                })
+                .collect();
+        }
+        self.position = saved;
+
+        let values = self.values(size, "int")?;
+        // every cell takes at least its point count, a larger cell count is caught below
+        let mut cells = Vec::with_capacity(first.min(values.len()));
+        let mut rest = values.as_slice();
+        for _ in 0..first {
+            let (count, tail) = rest.split_first().ok_or("VTK cell list is too short")?;
+            let count = *count as usize;
+            let points = tail.get(..count).ok_or("VTK cell list is too short")?;
+            cells.push(VtkCell {
+                cell_type: 0,
+                points: points.iter().map(|point| *point as u32).collect(),
+            });
+            rest = &tail[count..];
+        }
+        Ok(cells)
+    }
+
+    /// Reads a data attribute positioned after its keyword, for `count` points or cells. Returns
+    /// the arrays it holds: none for lookup tables, possibly several for a `FIELD`.
+    fn attribute(&mut self, keyword: &str, count: usize) -> Result<Vec<VtkArray>, String> {
+        let array = |name: String, components: usize, values: Vec<f64>| VtkArray {
+            name: name.replace("%20", " "),
+            components,
+            values: values.into_iter().map(|value| value as f32).collect(),
+        };
+
+        match keyword {
+            "SCALARS" => {
+                let name = self.expect_token("scalar name")?;
+                let data_type = self.expect_token("scalar type")?;
+                // the component count is optional, as is the lookup table in newer files.
+                let saved = self.position;
+                let components = match self.token().and_then(|token| token.parse::<usize>().ok()) {
+                    Some(components) => components,
+                    None => {
+                        self.position = saved;
+                        1
+                    }
+                };
+                let saved = self.position;
+                match self.token().as_deref() {
+                    Some("LOOKUP_TABLE") => {
+                        self.expect_token("lookup table name")?;
+                    }
+                    _ => self.position = saved,
+                }
+                let values = self.values(value_count(count, components)?, &data_type)?;
+                Ok(vec![array(name, components, values)])
+            }
+            "VECTORS" | "NORMALS" | "TENSORS" => {
+                let name = self.expect_token("array name")?;
+                let data_type = self.expect_token("array type")?;
+                let components = if keyword == "TENSORS" { 9 } else { 3 };
+                let values = self.values(value_count(count, components)?, &data_type)?;
+                Ok(vec![array(name, components, values)])
+            }
+            "TEXTURE_COORDINATES" => {
+                let name = self.expect_token("array name")?;
+                let components = self.count("texture dimension")?;
+                let data_type = self.expect_token("array type")?;
+                let values = self.values(value_count(count, components)?, &data_type)?;
+                Ok(vec![array(name, components, values)])
+            }
+            "COLOR_SCALARS" => {
+                let name = self.expect_token("array name")?;
+                let components = self.count("colour components")?;
+                let data_type = if self.binary {
+                    "unsigned_char"
+                } else {
+                    "float"
+                };
+                let values = self.values(value_count(count, components)?, data_type)?;
+                Ok(vec![array(name, components, values)])
+            }
+            "LOOKUP_TABLE" => {
+                self.expect_token("lookup table name")?;
+                let size = self.count("lookup table size")?;
+                let data_type = if self.binary {
+                    "unsigned_char"
+                } else {
+                    "float"
+                };
+                self.values(value_count(size, 4)?, data_type)?;
+                Ok(Vec::new())
+            }
+            _ => {
+                self.expect_token("field name")?;
+                let array_count = self.count("field array count")?;
+                let mut arrays = Vec::with_capacity(array_count.min(self.remaining()));
+                for _ in 0..array_count {
+                    let name = self.expect_token("array name")?;
+                    let components = self.count("array components")?;
+                    let tuples = self.count("array tuples")?;
+                    let data_type = self.expect_token("array type")?;
+                    let values = self.values(value_count(tuples, components)?, &data_type)?;
+                    if tuples == count {
+                        arrays.push(array(name, components, values));
+                    }
+                }
+                Ok(arrays)
+            }
+        }
+    }
+
+    /// Bytes left to read, a bound on what the rest of the file can hold.
+    fn remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    /// Skips a `METADATA` block, which ends at the first blank line.
+    fn skip_metadata(&mut self) {
+        self.line();
+        while self.position < self.bytes.len() && !self.line().trim().is_empty() {}
+    }
+}
+
+/// Number of values in a block of `tuples` tuples, checked as both counts come from the file.
+fn value_count(tuples: usize, components: usize) -> Result<usize, String> {
+    tuples.checked_mul(components).ok_or_else(|| {
+        format!(
+            "VTK data block of {} x {} values is too large",
+            tuples, components
+        )
+    })
+}
+
+fn binary_size(data_type: &str) -> Result<usize, String> {
+    match data_type {
+        "unsigned_char" | "char" => Ok(1),
+        "unsigned_short" | "short" => Ok(2),
+        "unsigned_int" | "int" | "float" => Ok(4),
+        "unsigned_long" | "long" | "double" | "vtktypeint64" | "vtktypeuint64" => Ok(8),
+        other => Err(format!("Unsupported VTK data type {}", other)),
+    }
+}
+
+fn decode_big_endian(bytes: &[u8], data_type: &str) -> f64 {
+    let mut buffer = [0u8; 8];
+    buffer[8 - bytes.len()..].copy_from_slice(bytes);
+    let raw = u64::from_be_bytes(buffer);
+    match data_type {
+        "char" => raw as u8 as i8 as f64,
+        "short" => raw as u16 as i16 as f64,
+        "int" => raw as u32 as i32 as f64,
+        "long" | "vtktypeint64" => raw as i64 as f64,
+        "float" => f32::from_bits(raw as u32) as f64,
+        "double" => f64::from_bits(raw),
+        _ => raw as f64,
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Tests
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::mesh::FieldLocation;
+
+    /// Two hexahedra sharing a face, so ten boundary quads remain.
+    const TWO_HEXES: &str = "# vtk DataFile Version 3.0
+two hexes
+ASCII
+DATASET UNSTRUCTURED_GRID
+FIELD FieldData 1
+TIME 1 1 double
+0.5
+POINTS 12 float
+0 0 0 1 0 0 1 1 0 0 1 0
+0 0 1 1 0 1 1 1 1 0 1 1
+2 0 0 2 1 0 2 0 1 2 1 1
+CELLS 2 18
+8 0 1 2 3 4 5 6 7
+8 1 8 9 2 5 10 11 6
+CELL_TYPES 2
+12
+12
+CELL_DATA 2
+SCALARS p float 1
+LOOKUP_TABLE default
+1 2
+POINT_DATA 12
+VECTORS U double
+1 0 0 1 0 0 1 0 0 1 0 0 1 0 0 1 0 0 1 0 0 1 0 0 3 4 0 3 4 0 3 4 0 3 4 0
+";
+
+    fn big_endian<T: Copy, const N: usize>(values: &[T], to_bytes: fn(T) -> [u8; N]) -> Vec<u8> {
+        values.iter().flat_map(|value| to_bytes(*value)).collect()
+    }
+
+    #[test]
+    fn reads_ascii_unstructured_grid_with_point_and_cell_data() {
+        let mesh = parse_legacy_vtk(TWO_HEXES.as_bytes()).unwrap();
+        assert_eq!(mesh.positions.len(), 12);
+        assert_eq!(mesh.triangles.len(), 20);
+        assert_eq!(mesh.fields.len(), 2);
+
+        let velocity = mesh.fields.iter().find(|field| field.name == "U").unwrap();
+        assert_eq!(velocity.location, FieldLocation::Point);
+        assert_eq!(velocity.components, 3);
+        assert_eq!(velocity.range(), Some((1.0, 5.0)));
+
+        let pressure = mesh.fields.iter().find(|field| field.name == "p").unwrap();
+        assert_eq!(pressure.location, FieldLocation::Cell);
+        assert_eq!(pressure.values.len(), 20);
+        assert_eq!(
+            pressure
+                .values
+                .iter()
+                .filter(|value| **value == 1.0)
+                .count(),
+            10
+        );
+    }
+
+    #[test]
+    fn reads_binary_cells_as_big_endian() {
+        let mut file =
+            b"# vtk DataFile Version 3.0\nt\nBINARY\nDATASET UNSTRUCTURED_GRID\nPOINTS 3 double\n"
+                .to_vec();
+        file.extend(big_endian(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            f64::to_be_bytes,
+        ));
+        file.extend(b"\nCELLS 1 4\n");
+        file.extend(big_endian(&[3, 0, 1, 2], i32::to_be_bytes));
+        file.extend(b"\nCELL_TYPES 1\n");
+        file.extend(5i32.to_be_bytes());
+        file.extend(b"\nPOINT_DATA 3\nSCALARS t float\nLOOKUP_TABLE default\n");
+        file.extend(big_endian(&[1.5, 2.5, 3.5], f32::to_be_bytes));
+        file.extend(b"\n");
+
+        let mesh = parse_legacy_vtk(&file).unwrap();
+        assert_eq!(mesh.positions[1], [1.0, 0.0, 0.0]);
+        assert_eq!(mesh.triangles, vec![[0, 1, 2]]);
+        assert_eq!(mesh.fields[0].name, "t");
+        assert_eq!(mesh.fields[0].location, FieldLocation::Point);
+        assert_eq!(mesh.fields[0].values, vec![1.5, 2.5, 3.5]);
+    }
+
+    #[test]
+    fn reads_binary_offsets_and_connectivity() {
+        let mut file =
+            b"# vtk DataFile Version 5.1\nt\nBINARY\nDATASET POLYDATA\nPOINTS 4 float\n".to_vec();
+        file.extend(big_endian(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            f32::to_be_bytes,
+        ));
+        file.extend(b"\nPOLYGONS 2 4\nOFFSETS vtktypeint64\n");
+        file.extend(big_endian(&[0, 4], i64::to_be_bytes));
+        file.extend(b"\nCONNECTIVITY vtktypeint64\n");
+        file.extend(big_endian(&[0, 1, 2, 3], i64::to_be_bytes));
+        file.extend(b"\nCELL_DATA 1\nSCALARS id int\n");
+        file.extend(7i32.to_be_bytes());
+        file.extend(b"\nMETADATA\nINFORMATION 0\n\n");
+
+        let mesh = parse_legacy_vtk(&file).unwrap();
+        assert_eq!(mesh.triangles, vec![[0, 1, 2], [0, 2, 3]]);
+        assert_eq!(mesh.fields[0].location, FieldLocation::Cell);
+        assert_eq!(mesh.fields[0].values, vec![7.0, 7.0]);
+    }
+
+    #[test]
+    fn reads_ascii_offsets_and_connectivity() {
+        let file = "# vtk DataFile Version 5.1
+t
+ASCII
+DATASET UNSTRUCTURED_GRID
+POINTS 4 float
+0 0 0 1 0 0 0 1 0 0 0 1
+CELLS 3 6
+OFFSETS vtktypeint64
+0 3 6
+CONNECTIVITY vtktypeint64
+0 1 2 0 1 3
+CELL_TYPES 2
+5
+5
+";
+        let mesh = parse_legacy_vtk(file.as_bytes()).unwrap();
+        assert_eq!(mesh.triangles, vec![[0, 1, 2], [0, 1, 3]]);
+    }
+
+    #[test]
+    fn rejects_files_without_a_header() {
+        assert!(parse_legacy_vtk(b"hello").is_err());
+    }
+
+    #[test]
+    fn rejects_binary_counts_larger_than_the_file() {
+        let header = "# vtk DataFile Version 3.0\nt\nBINARY\nDATASET UNSTRUCTURED_GRID\n";
+        let huge = usize::MAX / 2;
+        for block in [
+            format!("POINTS {} float\n", huge),
+            format!("CELLS {} {}\n", huge, huge),
+            format!("CELLS {} 1\n", huge),
+            format!("POINTS 0 float\nPOINT_DATA {}\nVECTORS U double\n", huge),
+            format!("POINTS 0 float\nFIELD data {}\nU 3 {} double\n", huge, huge),
+        ] {
+            let file = format!("{}{}", header, block);
+            assert!(parse_legacy_vtk(file.as_bytes()).is_err(), "{}", block);
+        }
+    }
+}
@@ -0,0 +1,696 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: vtu.rs
+ * description: Parse VTK XML unstructured grid (.vtu) files
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use base64::Engine;
+use std::collections::HashMap;
+
+use crate::common::vtk::{VtkArray, VtkCell, VtkGrid};
+use crate::domain::mesh::Mesh;
+
+/// Reads an XML `UnstructuredGrid` file, joining all its pieces. Data arrays may be `ascii`,
+/// inline base64 `binary` or `appended` (raw or base64), but not compressed.
+pub fn parse_vtu(bytes: &[u8]) -> Result<Mesh, String> {
+    // raw appended data is binary, only the XML before it is text.
+    let (xml_bytes, appended, appended_base64) = split_appended_data(bytes);
+    let xml = String::from_utf8_lossy(xml_bytes);
+
+    let mut grid = VtkGrid::default();
+    let mut file = FileInfo::default();
+    let mut piece = Piece::default();
+    let mut section = Vec::<String>::new();
+
+    for element in XmlElements::new(&xml) {
+        match element {
+            XmlElement::Open {
+                name,
+                attributes,
+                text,
+                closed,
+            } => {
+                match name {
+                    "VTKFile" => {
+                        file = FileInfo::from_attributes(&attributes)?;
+                        file.appended_base64 = appended_base64;
+                    }
+                    "Piece" => piece = Piece::default(),
+                    "DataArray" => {
+                        let parent = section.last().map(String::as_str).unwrap_or_default();
+                        let array = read_data_array(&attributes, text, &file, appended)?;
+                        piece.add(parent, array);
+                    }
+                    _ => {}
+                }
+                if !closed {
+                    section.push(name.to_string());
+                }
+                if closed && name == "Piece" {
+                    piece.append_to(&mut grid)?;
+                }
+            }
+            XmlElement::Close(name) => {
+                section.pop();
+                if name == "Piece" {
+                    piece.append_to(&mut grid)?;
+                    piece = Piece::default();
+                }
+            }
+        }
+    }
+
+    if file.grid_type != "UnstructuredGrid" {
+        return Err(format!(
+            "VTK XML {} files are not supported",
+            file.grid_type
+        ));
+    }
+    grid.into_surface()
+}
+
+/// Splits the file at the data after `<AppendedData ... >_`, returning the XML before it, the
+/// data, and whether the data is base64 rather than raw.
+fn split_appended_data(bytes: &[u8]) -> (&[u8], &[u8], bool) {
+    let marker = b"<AppendedData";
+    let Some(start) = bytes
+        .windows(marker.len())
+        .position(|window| window == marker)
+    else {
+        return (bytes, &[], false);
+    };
+    let Some(tag_end) = bytes[start..].iter().position(|b| *b == b'>') else {
+        return (bytes, &[], false);
+    };
+    let tag = String::from_utf8_lossy(&bytes[start + marker.len()..start + tag_end]);
+    let base64 = parse_attributes(&tag).get("encoding").map(String::as_str) == Some("base64");
+
+    let after_tag = start + tag_end + 1;
+    let data_start = bytes[after_tag..]
+        .iter()
+        .position(|b| *b == b'_')
+        .map_or(bytes.len(), |underscore| after_tag + underscore + 1);
+    (&bytes[..start], &bytes[data_start..], base64)
+}
+
+// -------------------------------------------------------------------------------------------------
+// Data Arrays
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct FileInfo {
+    grid_type: String,
+    big_endian: bool,
+    header_size: usize,
+    appended_base64: bool,
+}
+
+impl FileInfo {
+    fn from_attributes(attributes: &HashMap<&str, String>) -> Result<Self, String> {
+        if attributes.contains_key("compressor") {
+            return Err("Compressed VTU files are not supported".to_string());
+        }
+        Ok(Self {
+            grid_type: attributes.get("type").cloned().unwrap_or_default(),
+            big_endian: attributes.get("byte_order").map(String::as_str) == Some("BigEndian"),
+            header_size: match attributes.get("header_type").map(String::as_str) {
+                Some("UInt64") => 8,
+                _ => 4,
+            },
+            appended_base64: false,
+        })
+    }
+}
+
+struct DataArray {
+    name: String,
+    components: usize,
+    values: Vec<f64>,
+}
+
+fn read_data_array(
+    attributes: &HashMap<&str, String>,
+    text: &str,
+    file: &FileInfo,
+    appended: &[u8],
+) -> Result<DataArray, String> {
+    let name = attributes.get("Name").cloned().unwrap_or_default();
+    let data_type = attributes
+        .get("type")
+        .map(String::as_str)
+        .unwrap_or("Float32");
+    let components = attributes
+        .get("NumberOfComponents")
+        .and_then(|components| components.parse::<usize>().ok())
+        .unwrap_or(1);
+    let size = value_size(data_type)?;
+
+    let values = match attributes.get("format").map(String::as_str) {
+        Some("ascii") => text
+            .split_whitespace()
+            .map(|token| {
+                token
+                    .parse::<f64>()
+                    .map_err(|_| format!("Invalid value {} in array {}", token, name))
+            })
+            .collect::<Result<Vec<f64>, String>>()?,
+        Some("binary") => {
+            let encoded = text.split_whitespace().collect::<String>();
+            let data = decode_base64_block(&encoded, file.header_size)?;
+            let payload = strip_header(&data, file)?;
+            decode_values(payload, data_type, size, file.big_endian)
+        }
+        Some("appended") => {
+            let offset = attributes
+                .get("offset")
+                .and_then(|offset| offset.trim().parse::<usize>().ok())
+                .ok_or_else(|| format!("Appended array {} has no offset", name))?;
+            let rest = appended
+                .get(offset..)
+                .ok_or_else(|| format!("Appended array {} is past the end of the file", name))?;
+            match file.appended_base64 {
+                true => {
+                    let payload = decode_appended_base64(rest, file)?;
+                    decode_values(&payload, data_type, size, file.big_endian)
+                }
+                false => {
+                    let payload = strip_header(rest, file)?;
+                    decode_values(payload, data_type, size, file.big_endian)
+                }
+            }
+        }
+        other => {
+            return Err(format!(
+                "Array {} has unsupported format {}",
+                name,
+                other.unwrap_or("none")
+            ))
+        }
+    };
+
+    Ok(DataArray {
+        name,
+        components,
+        values,
+    })
+}
+
+/// Base64 data is written either as one stream holding header and values, or as the header and
+/// the values encoded separately; the separate form pads the header block.
+fn decode_base64_block(encoded: &str, header_size: usize) -> Result<Vec<u8>, String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let header_chars = header_size.div_ceil(3) * 4;
+    let separate = encoded
+        .get(..header_chars)
+        .is_some_and(|header| header.ends_with('=') && encoded.len() > header_chars);
+
+    match separate {
+        true => {
+            let mut data = engine
+                .decode(&encoded[..header_chars])
+                .map_err(|e| format!("Failed to decode base64 header: {:?}", e))?;
+            data.extend(
+                engine
+                    .decode(&encoded[header_chars..])
+                    .map_err(|e| format!("Failed to decode base64 data: {:?}", e))?,
+            );
+            Ok(data)
+        }
+        false => engine
+            .decode(encoded)
+            .map_err(|e| format!("Failed to decode base64 data: {:?}", e)),
+    }
+}
+
+/// Appended base64 arrays follow each other without a break, so each one is cut from the data
+/// by the length in its header. The header and the values are encoded either separately, when the
+/// header block decodes to just the header, or as one stream. Returns just the values.
+fn decode_appended_base64(encoded: &[u8], file: &FileInfo) -> Result<Vec<u8>, String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let header_chars = file.header_size.div_ceil(3) * 4;
+    let header_block = encoded
+        .get(..header_chars)
+        .ok_or("Appended data ends inside a header")?;
+    let header = engine
+        .decode(header_block)
+        .map_err(|e| format!("Failed to decode base64 header: {:?}", e))?;
+    let length = decode_integer(
+        header
+            .get(..file.header_size)
+            .ok_or("Appended data ends inside a header")?,
+        file.big_endian,
+    ) as usize;
+
+    match header.len() == file.header_size {
+        true => {
+            let data_chars = length.div_ceil(3) * 4;
+            let data = encoded
+                .get(header_chars..header_chars + data_chars)
+                .ok_or("Appended data ends inside an array")?;
+            engine
+                .decode(data)
+                .map_err(|e| format!("Failed to decode base64 data: {:?}", e))
+        }
+        false => {
+            let block_chars = (file.header_size + length).div_ceil(3) * 4;
+            let block = encoded
+                .get(..block_chars)
+                .ok_or("Appended data ends inside an array")?;
+            let data = engine
+                .decode(block)
+                .map_err(|e| format!("Failed to decode base64 data: {:?}", e))?;
+            Ok(strip_header(&data, file)?.to_vec())
+        }
+    }
+}
+
+/// Drops the byte count written before each binary block, returning just the values.
+fn strip_header<'a>(data: &'a [u8], file: &FileInfo) -> Result<&'a [u8], String> {
+    let header = data
+        .get(..file.header_size)
+        .ok_or("Binary block is shorter than its header")?;
+    let length = decode_integer(header, file.big_endian) as usize;
+    data.get(file.header_size..file.header_size + length)
+        .ok_or_else(|| "Binary block is shorter than its header claims".to_string())
+}
+
+fn value_size(data_type: &str) -> Result<usize, String> {
+    match data_type {
+        "Int8" | "UInt8" => Ok(1),
+        "Int16" | "UInt16" => Ok(2),
+        "Int32" | "UInt32" | "Float32" => Ok(4),
+        "Int64" | "UInt64" | "Float64" => Ok(8),
+        other => Err(format!("Unsupported VTK data type {}", other)),
+    }
+}
+
+fn decode_integer(bytes: &[u8], big_endian: bool) -> u64 {
+    let mut buffer = [0u8; 8];
+    match big_endian {
+        true => buffer[8 - bytes.len()..].copy_from_slice(bytes),
+        false => buffer[..bytes.len()].copy_from_slice(bytes),
+    }
+    match big_endian {
+        true => u64::from_be_bytes(buffer),
+        false => u64::from_le_bytes(buffer),
+    }
+}
+
+fn decode_values(data: &[u8], data_type: &str, size: usize, big_endian: bool) -> Vec<f64> {
+    data.chunks_exact(size)
+        .map(|value| {
+            let raw = decode_integer(value, big_endian);
+            match data_type {
+                "Int8" => raw as u8 as i8 as f64,
+                "Int16" => raw as u16 as i16 as f64,
+                "Int32" => raw as u32 as i32 as f64,
+                "Int64" => raw as i64 as f64,
+                "Float32" => f32::from_bits(raw as u32) as f64,
+                "Float64" => f64::from_bits(raw),
+                _ => raw as f64,
+            }
+        })
+        .collect()
+}
+
+// -------------------------------------------------------------------------------------------------
+// Pieces
+// -------------------------------------------------------------------------------------------------
+
+/// The arrays of one `Piece`, numbered from zero until appended to the grid.
+#[derive(Default)]
+struct Piece {
+    points: Option<DataArray>,
+    connectivity: Vec<f64>,
+    offsets: Vec<f64>,
+    types: Vec<f64>,
+    point_data: Vec<DataArray>,
+    cell_data: Vec<DataArray>,
+}
+
+impl Piece {
+    fn add(&mut self, section: &str, array: DataArray) {
+        match (section, array.name.as_str()) {
+            ("Points", _) => self.points = Some(array),
+            ("Cells", "connectivity") => self.connectivity = array.values,
+            ("Cells", "offsets") => self.offsets = array.values,
+            ("Cells", "types") => self.types = array.values,
+            ("PointData", _) => self.point_data.push(array),
+            ("CellData", _) => self.cell_data.push(array),
+            _ => {}
+        }
+    }
+
+    fn append_to(&mut self, grid: &mut VtkGrid) -> Result<(), String> {
+        let Some(points) = self.points.take() else {
+            return Ok(());
+        };
+        if points.components != 3 {
+            return Err("VTU points must have three components".to_string());
+        }
+        if self.offsets.len() != self.types.len() {
+            return Err("VTU cell offsets and types differ in length".to_string());
+        }
+
+        let first_point = grid.points.len() as u32;
+        let first_cell = grid.cells.len();
+        grid.points.extend(
+            points
+                .values
+                .chunks_exact(3)
+                .map(|xyz| [xyz[0] as f32, xyz[1] as f32, xyz[2] as f32]),
+        );
+
+        let mut start = 0;
+        for (end, cell_type) in self.offsets.iter().zip(&self.types) {
+            let end = *end as usize;
+            let points = self
+                .connectivity
+                .get(start..end)
+                .ok_or("VTU cell offsets run past the connectivity")?;
+            grid.cells.push(VtkCell {
+                cell_type: *cell_type as u8,
+                points: points
+                    .iter()
+                    .map(|point| first_point + *point as u32)
+                    .collect(),
+            });
+            start = end;
+        }
+
+        merge_arrays(
+            &mut grid.point_data,
+            self.point_data.drain(..),
+            first_point as usize,
+        );
+        merge_arrays(&mut grid.cell_data, self.cell_data.drain(..), first_cell);
+        Ok(())
+    }
+}
+
+/// Appends a piece's arrays to the grid's arrays of the same name. Arrays missing from earlier
+/// pieces are dropped, as they cannot cover the whole grid.
+fn merge_arrays(
+    arrays: &mut Vec<VtkArray>,
+    piece_arrays: impl Iterator<Item = DataArray>,
+    tuples_before: usize,
+) {
+    let piece_arrays = piece_arrays.collect::<Vec<DataArray>>();
+    if tuples_before == 0 {
+        arrays.extend(piece_arrays.into_iter().map(|array| VtkArray {
+            name: array.name,
+            components: array.components,
+            values: array.values.into_iter().map(|value| value as f32).collect(),
+        }));
+        return;
+    }
+    arrays.retain_mut(|existing| {
+        match piece_arrays
+            .iter()
+            .find(|array| array.name == existing.name && array.components == existing.components)
+        {
+            Some(array) => {
+                existing
+                    .values
+                    .extend(array.values.iter().map(|value| *value as f32));
+                true
+            }
+            None => false,
+        }
+    });
+}
+
+// -------------------------------------------------------------------------------------------------
+// XML Scanning
+// -------------------------------------------------------------------------------------------------
+
+/// Just enough XML for VTK files: elements, their attributes and the text straight after an
+/// opening tag. Comments, declarations and processing instructions are skipped.
+enum XmlElement<'a> {
+    Open {
+        name: &'a str,
+        attributes: HashMap<&'a str, String>,
+        text: &'a str,
+        closed: bool,
+    },
+    Close(&'a str),
+}
+
+struct XmlElements<'a> {
+    xml: &'a str,
+    position: usize,
+}
+
+impl<'a> XmlElements<'a> {
+    fn new(xml: &'a str) -> Self {
+        Self { xml, position: 0 }
+    }
+}
+
+impl<'a> Iterator for XmlElements<'a> {
+    type Item = XmlElement<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let start = self.position + self.xml[self.position..].find('<')?;
+            let rest = &self.xml[start..];
+
+            if rest.starts_with("<!--") {
+                self.position = start + rest.find("-->").map_or(rest.len(), |end| end + 3);
+                continue;
+            }
+            let end = start + rest.find('>')?;
+            self.position = end + 1;
+            let tag = &self.xml[start + 1..end];
+
+            if tag.starts_with('?') || tag.starts_with('!') {
+                continue;
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                return Some(XmlElement::Close(name.trim()));
+            }
+
+            let closed = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            let text_end = self.xml[self.position..]
+                .find('<')
+                .map_or(self.xml.len(), |offset| self.position + offset);
+
+            return Some(XmlElement::Open {
+                name: &tag[..name_end],
+                attributes: parse_attributes(&tag[name_end..]),
+                text: match closed {
+                    true => "",
+                    false => &self.xml[self.position..text_end],
+                },
+                closed,
+            });
+        }
+    }
+}
+
+fn parse_attributes(text: &str) -> HashMap<&str, String> {
+    let mut attributes = HashMap::new();
+    let mut rest = text;
+    while let Some(equals) = rest.find('=') {
+        let name = rest[..equals].trim();
+        let value_text = rest[equals + 1..].trim_start();
+        let Some(quote) = value_text
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+        else {
+            break;
+        };
+        let Some(value_end) = value_text[1..].find(quote) else {
+            break;
+        };
+        attributes.insert(name, unescape(&value_text[1..value_end + 1]));
+        rest = &value_text[value_end + 2..];
+    }
+    attributes
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// -------------------------------------------------------------------------------------------------
+// Tests
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::mesh::FieldLocation;
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Format {
+        Ascii,
+        Binary,
+        AppendedRaw,
+        AppendedBase64Separate,
+        AppendedBase64Joint,
+    }
+
+    /// The arrays of a single triangle with a point and a cell field, in file order.
+    const ARRAYS: [(&str, &str, usize, &[f64]); 6] = [
+        ("pressure", "Float32", 1, &[1.0, 2.0, 3.0]),
+        ("id", "Float64", 1, &[7.0]),
+        (
+            "Points",
+            "Float32",
+            3,
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+        ),
+        ("connectivity", "Int32", 1, &[0.0, 1.0, 2.0]),
+        ("offsets", "Int32", 1, &[3.0]),
+        ("types", "UInt8", 1, &[5.0]),
+    ];
+
+    fn encode(data_type: &str, values: &[f64]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| match data_type {
+                "Float32" => (*value as f32).to_le_bytes().to_vec(),
+                "Float64" => value.to_le_bytes().to_vec(),
+                "Int32" => (*value as i32).to_le_bytes().to_vec(),
+                _ => vec![*value as u8],
+            })
+            .collect()
+    }
+
+    fn vtu_file(format: Format) -> Vec<u8> {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let mut elements = Vec::new();
+        let mut appended = Vec::<u8>::new();
+        for (name, data_type, components, values) in ARRAYS {
+            let data = encode(data_type, values);
+            let header = (data.len() as u32).to_le_bytes();
+            let joint = [&header[..], &data].concat();
+            let offset = appended.len();
+            let (format_attributes, content) = match format {
+                Format::Ascii => (
+                    "format=\"ascii\"".to_string(),
+                    values
+                        .iter()
+                        .map(f64::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                Format::Binary => ("format=\"binary\"".to_string(), engine.encode(&joint)),
+                _ => {
+                    match format {
+                        Format::AppendedRaw => appended.extend(&joint),
+                        Format::AppendedBase64Separate => {
+                            appended.extend(engine.encode(header).bytes());
+                            appended.extend(engine.encode(&data).bytes());
+                        }
+                        _ => appended.extend(engine.encode(&joint).bytes()),
+                    }
+                    (
+                        format!("format=\"appended\" offset=\"{}\"", offset),
+                        String::new(),
+                    )
+                }
+            };
+            elements.push(format!(
+                "<DataArray type=\"{}\" Name=\"{}\" NumberOfComponents=\"{}\" {}>{}</DataArray>",
+                data_type, name, components, format_attributes, content
+            ));
+        }
+
+        let mut file = format!(
+            "<?xml version=\"1.0\"?>\n\
+             <VTKFile type=\"UnstructuredGrid\" version=\"1.0\" byte_order=\"LittleEndian\" header_type=\"UInt32\">\n\
+             <UnstructuredGrid>\n\
+             <Piece NumberOfPoints=\"3\" NumberOfCells=\"1\">\n\
+             <PointData Scalars=\"pressure\">{}</PointData>\n\
+             <CellData>{}</CellData>\n\
+             <Points>{}</Points>\n\
+             <Cells>{}{}{}</Cells>\n\
+             </Piece>\n\
+             </UnstructuredGrid>\n",
+            elements[0], elements[1], elements[2], elements[3], elements[4], elements[5]
+        )
+        .into_bytes();
+        if !appended.is_empty() {
+            let encoding = match format {
+                Format::AppendedRaw => "raw",
+                _ => "base64",
+            };
+            file.extend(format!("<AppendedData encoding=\"{}\">\n_", encoding).bytes());
+            file.extend(appended);
+            file.extend(b"\n</AppendedData>\n");
+        }
+        file.extend(b"</VTKFile>\n");
+        file
+    }
+
+    fn assert_triangle(mesh: Mesh) {
+        assert_eq!(
+            mesh.positions,
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+        );
+        assert_eq!(mesh.triangles, vec![[0, 1, 2]]);
+        assert_eq!(mesh.fields.len(), 2);
+        assert_eq!(mesh.fields[0].name, "pressure");
+        assert_eq!(mesh.fields[0].location, FieldLocation::Point);
+        assert_eq!(mesh.fields[0].values, vec![1.0, 2.0, 3.0]);
+        assert_eq!(mesh.fields[1].name, "id");
+        assert_eq!(mesh.fields[1].location, FieldLocation::Cell);
+        assert_eq!(mesh.fields[1].values, vec![7.0]);
+    }
+
+    #[test]
+    fn reads_ascii_arrays() {
+        assert_triangle(parse_vtu(&vtu_file(Format::Ascii)).unwrap());
+    }
+
+    #[test]
+    fn reads_inline_binary_arrays() {
+        assert_triangle(parse_vtu(&vtu_file(Format::Binary)).unwrap());
+    }
+
+    #[test]
+    fn reads_raw_appended_arrays() {
+        assert_triangle(parse_vtu(&vtu_file(Format::AppendedRaw)).unwrap());
+    }
+
+    #[test]
+    fn reads_base64_appended_arrays_encoded_separately() {
+        assert_triangle(parse_vtu(&vtu_file(Format::AppendedBase64Separate)).unwrap());
+    }
+
+    #[test]
+    fn reads_base64_appended_arrays_encoded_jointly() {
+        assert_triangle(parse_vtu(&vtu_file(Format::AppendedBase64Joint)).unwrap());
+    }
+
+    #[test]
+    fn rejects_compressed_files() {
+        let file = b"<VTKFile type=\"UnstructuredGrid\" compressor=\"vtkZLibDataCompressor\">";
+        assert!(parse_vtu(file).is_err());
+    }
+}
@@ -0,0 +1,474 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: webgl_renderer.rs
+ * description: WebGL2 drawing of surface meshes, wireframes and an axes triad
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use wasm_bindgen::JsCast;
+use web_sys::{
+    HtmlCanvasElement, WebGl2RenderingContext as GL, WebGlBuffer, WebGlProgram, WebGlShader,
+    WebGlVertexArrayObject,
+};
+
+use crate::common::orbit_camera::{orthographic, Matrix4, OrbitCamera};
+use crate::domain::mesh::Mesh;
+
+const POSITION_LOCATION: u32 = 0;
+const NORMAL_LOCATION: u32 = 1;
+const COLOUR_LOCATION: u32 = 2;
+
+/// Side of the square the axes triad is drawn in, in CSS pixels.
+const TRIAD_SIZE: f64 = 96.0;
+
+const BACKGROUND: [f32; 3] = [0.94, 0.95, 0.96];
+const SURFACE_COLOUR: [f32; 3] = [0.55, 0.7, 0.8];
+const SHADED_EDGE_COLOUR: [f32; 3] = [0.15, 0.2, 0.25];
+const WIREFRAME_COLOUR: [f32; 3] = [0.1, 0.3, 0.45];
+
+const SURFACE_VERTEX_SHADER: &str = r#"#version 300 es
+layout(location = 0) in vec3 a_position;
+layout(location = 1) in vec3 a_normal;
+layout(location = 2) in vec3 a_colour;
+uniform mat4 u_view;
+uniform mat4 u_projection;
+out vec3 v_normal;
+out vec3 v_colour;
+void main() {
+    v_normal = mat3(u_view) * a_normal;
+    v_colour = a_colour;
+    gl_Position = u_projection * u_view * vec4(a_position, 1.0);
+}
+"#;
+
+// a headlight at the camera, lighting both sides so open surfaces are not black from behind.
+const SURFACE_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+in vec3 v_normal;
+in vec3 v_colour;
+out vec4 colour;
+void main() {
+    float diffuse = abs(normalize(v_normal).z);
+    colour = vec4(v_colour * (0.3 + 0.7 * diffuse), 1.0);
+}
+"#;
+
+const LINE_VERTEX_SHADER: &str = r#"#version 300 es
+layout(location = 0) in vec3 a_position;
+layout(location = 2) in vec3 a_colour;
+uniform mat4 u_view;
+uniform mat4 u_projection;
+out vec3 v_colour;
+void main() {
+    v_colour = a_colour;
+    gl_Position = u_projection * u_view * vec4(a_position, 1.0);
+}
+"#;
+
+const LINE_FRAGMENT_SHADER: &str = r#"#version 300 es
+precision highp float;
+in vec3 v_colour;
+out vec4 colour;
+void main() {
+    colour = vec4(v_colour, 1.0);
+}
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderMode {
+    Shaded,
+    Wireframe,
+}
+
+/// A mesh uploaded to the GPU, one vertex array for the surface and one for its edges.
+struct MeshBuffers {
+    surface: WebGlVertexArrayObject,
+    edges: WebGlVertexArrayObject,
+    triangle_index_count: i32,
+    edge_index_count: i32,
+    buffers: Vec<WebGlBuffer>,
+}
+
+/// The surface unrolled to three vertices per triangle, each carrying its own colour so cell
+/// fields stay constant across a cell.
+struct ColouredSurface {
+    vertex_array: WebGlVertexArrayObject,
+    vertex_count: i32,
+    buffers: Vec<WebGlBuffer>,
+}
+
+pub struct MeshRenderer {
+    gl: GL,
+    canvas: HtmlCanvasElement,
+    surface_program: WebGlProgram,
+    line_program: WebGlProgram,
+    triad: WebGlVertexArrayObject,
+    mesh: Option<MeshBuffers>,
+    coloured: Option<ColouredSurface>,
+}
+
+impl MeshRenderer {
+    pub fn new(canvas: HtmlCanvasElement) -> Result<Self, String> {
+        let gl = canvas
+            .get_context("webgl2")
+            .map_err(|e| format!("Failed to get WebGL2 context: {:?}", e))?
+            .ok_or("WebGL2 is not supported by this browser")?
+            .dyn_into::<GL>()
+            .map_err(|_| "Failed to cast to WebGl2RenderingContext".to_string())?;
+
+        let surface_program = link_program(&gl, SURFACE_VERTEX_SHADER, SURFACE_FRAGMENT_SHADER)?;
+        let line_program = link_program(&gl, LINE_VERTEX_SHADER, LINE_FRAGMENT_SHADER)?;
+        let triad = create_triad(&gl)?;
+
+        Ok(Self {
+            gl,
+            canvas,
+            surface_program,
+            line_program,
+            triad,
+            mesh: None,
+            coloured: None,
+        })
+    }
+
+    /// Uploads a mesh, replacing the previous one. `None` clears the view.
+    pub fn set_mesh(&mut self, mesh: Option<&Mesh>) -> Result<(), String> {
+        self.set_corner_colours(None, &[])?;
+        if let Some(old) = self.mesh.take() {
+            self.gl.delete_vertex_array(Some(&old.surface));
+            self.gl.delete_vertex_array(Some(&old.edges));
+            old.buffers
+                .iter()
+                .for_each(|buffer| self.gl.delete_buffer(Some(buffer)));
+        }
+        let Some(mesh) = mesh else {
+            return Ok(());
+        };
+
+        let gl = &self.gl;
+        let positions = mesh
+            .positions
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<f32>>();
+        let normals = mesh
+            .vertex_normals()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<f32>>();
+        let triangle_indices = mesh
+            .triangles
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<u32>>();
+        let edge_indices = mesh.edges().into_iter().flatten().collect::<Vec<u32>>();
+
+        let surface = create_vertex_array(gl)?;
+        let position_buffer = upload_floats(gl, &positions)?;
+        bind_attribute(gl, POSITION_LOCATION, &position_buffer);
+        let normal_buffer = upload_floats(gl, &normals)?;
+        bind_attribute(gl, NORMAL_LOCATION, &normal_buffer);
+        let triangle_buffer = upload_indices(gl, &triangle_indices)?;
+
+        let edges = create_vertex_array(gl)?;
+        bind_attribute(gl, POSITION_LOCATION, &position_buffer);
+        let edge_buffer = upload_indices(gl, &edge_indices)?;
+        gl.bind_vertex_array(None);
+
+        self.mesh = Some(MeshBuffers {
+            surface,
+            edges,
+            triangle_index_count: triangle_indices.len() as i32,
+            edge_index_count: edge_indices.len() as i32,
+            buffers: vec![position_buffer, normal_buffer, triangle_buffer, edge_buffer],
+        });
+        Ok(())
+    }
+
+    /// Colours the surface with a colour per triangle corner, in the order of
+    /// `Mesh::corner_scalars`. Without a mesh the plain surface colour is restored.
+    pub fn set_corner_colours(
+        &mut self,
+        mesh: Option<&Mesh>,
+        colours: &[[f32; 3]],
+    ) -> Result<(), String> {
+        if let Some(old) = self.coloured.take() {
+            self.gl.delete_vertex_array(Some(&old.vertex_array));
+            old.buffers
+                .iter()
+                .for_each(|buffer| self.gl.delete_buffer(Some(buffer)));
+        }
+        let Some(mesh) = mesh else {
+            return Ok(());
+        };
+        if colours.len() != 3 * mesh.triangles.len() {
+            return Err("Expected one colour per triangle corner".to_string());
+        }
+
+        let gl = &self.gl;
+        let vertex_normals = mesh.vertex_normals();
+        let corners = mesh.triangles.iter().flatten().map(|index| *index as usize);
+        let positions = corners
+            .clone()
+            .flat_map(|index| mesh.positions[index])
+            .collect::<Vec<f32>>();
+        let normals = corners
+            .flat_map(|index| vertex_normals[index])
+            .collect::<Vec<f32>>();
+        let colours = colours.iter().flatten().copied().collect::<Vec<f32>>();
+
+        let vertex_array = create_vertex_array(gl)?;
+        let position_buffer = upload_floats(gl, &positions)?;
+        bind_attribute(gl, POSITION_LOCATION, &position_buffer);
+        let normal_buffer = upload_floats(gl, &normals)?;
+        bind_attribute(gl, NORMAL_LOCATION, &normal_buffer);
+        let colour_buffer = upload_floats(gl, &colours)?;
+        bind_attribute(gl, COLOUR_LOCATION, &colour_buffer);
+        gl.bind_vertex_array(None);
+
+        self.coloured = Some(ColouredSurface {
+            vertex_array,
+            vertex_count: (positions.len() / 3) as i32,
+            buffers: vec![position_buffer, normal_buffer, colour_buffer],
+        });
+        Ok(())
+    }
+
+    pub fn draw(&self, camera: &OrbitCamera, mode: RenderMode) {
+        let gl = &self.gl;
+        let (width, height) = self.resize_canvas();
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        gl.viewport(0, 0, width, height);
+        gl.clear_color(BACKGROUND[0], BACKGROUND[1], BACKGROUND[2], 1.0);
+        gl.enable(GL::DEPTH_TEST);
+        gl.clear(GL::COLOR_BUFFER_BIT | GL::DEPTH_BUFFER_BIT);
+
+        if let Some(mesh) = &self.mesh {
+            let view = camera.view_matrix();
+            let projection = camera.projection_matrix(width as f32 / height as f32);
+
+            if mode == RenderMode::Shaded {
+                // push the faces back so the edges drawn over them are not hidden.
+                gl.enable(GL::POLYGON_OFFSET_FILL);
+                gl.polygon_offset(1.0, 1.0);
+                gl.use_program(Some(&self.surface_program));
+                self.set_matrices(&self.surface_program, &view, &projection);
+                match &self.coloured {
+                    Some(coloured) => {
+                        gl.bind_vertex_array(Some(&coloured.vertex_array));
+                        gl.draw_arrays(GL::TRIANGLES, 0, coloured.vertex_count);
+                    }
+                    None => {
+                        gl.bind_vertex_array(Some(&mesh.surface));
+                        gl.vertex_attrib3f(
+                            COLOUR_LOCATION,
+                            SURFACE_COLOUR[0],
+                            SURFACE_COLOUR[1],
+                            SURFACE_COLOUR[2],
+                        );
+                        gl.draw_elements_with_i32(
+                            GL::TRIANGLES,
+                            mesh.triangle_index_count,
+                            GL::UNSIGNED_INT,
+                            0,
+                        );
+                    }
+                }
+                gl.disable(GL::POLYGON_OFFSET_FILL);
+            }
+
+            let edge_colour = match mode {
+                RenderMode::Shaded => SHADED_EDGE_COLOUR,
+                RenderMode::Wireframe => WIREFRAME_COLOUR,
+            };
+            gl.use_program(Some(&self.line_program));
+            self.set_matrices(&self.line_program, &view, &projection);
+            gl.bind_vertex_array(Some(&mesh.edges));
+            gl.vertex_attrib3f(
+                COLOUR_LOCATION,
+                edge_colour[0],
+                edge_colour[1],
+                edge_colour[2],
+            );
+            gl.draw_elements_with_i32(GL::LINES, mesh.edge_index_count, GL::UNSIGNED_INT, 0);
+        }
+
+        self.draw_triad(camera);
+        gl.bind_vertex_array(None);
+    }
+
+    /// Draws the x (red), y (green) and z (blue) axes in the bottom left corner, turning with the
+    /// camera.
+    fn draw_triad(&self, camera: &OrbitCamera) {
+        let gl = &self.gl;
+        let size = (TRIAD_SIZE * device_pixel_ratio()) as i32;
+        gl.viewport(0, 0, size, size);
+        gl.clear(GL::DEPTH_BUFFER_BIT);
+        gl.use_program(Some(&self.line_program));
+        self.set_matrices(
+            &self.line_program,
+            &camera.rotation_matrix(),
+            &orthographic(1.2),
+        );
+        gl.bind_vertex_array(Some(&self.triad));
+        gl.draw_arrays(GL::LINES, 0, 6);
+    }
+
+    fn set_matrices(&self, program: &WebGlProgram, view: &Matrix4, projection: &Matrix4) {
+        let gl = &self.gl;
+        gl.uniform_matrix4fv_with_f32_array(
+            gl.get_uniform_location(program, "u_view").as_ref(),
+            false,
+            view,
+        );
+        gl.uniform_matrix4fv_with_f32_array(
+            gl.get_uniform_location(program, "u_projection").as_ref(),
+            false,
+            projection,
+        );
+    }
+
+    /// Matches the drawing buffer to the canvas's displayed size, returning it in device pixels.
+    fn resize_canvas(&self) -> (i32, i32) {
+        let ratio = device_pixel_ratio();
+        let width = (self.canvas.client_width() as f64 * ratio) as u32;
+        let height = (self.canvas.client_height() as f64 * ratio) as u32;
+        if self.canvas.width() != width || self.canvas.height() != height {
+            self.canvas.set_width(width);
+            self.canvas.set_height(height);
+        }
+        (width as i32, height as i32)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// GL Helpers
+// -------------------------------------------------------------------------------------------------
+
+fn device_pixel_ratio() -> f64 {
+    web_sys::window()
+        .map(|window| window.device_pixel_ratio())
+        .unwrap_or(1.0)
+}
+
+fn compile_shader(gl: &GL, kind: u32, source: &str) -> Result<WebGlShader, String> {
+    let shader = gl.create_shader(kind).ok_or("Failed to create shader")?;
+    gl.shader_source(&shader, source);
+    gl.compile_shader(&shader);
+
+    match gl
+        .get_shader_parameter(&shader, GL::COMPILE_STATUS)
+        .as_bool()
+    {
+        Some(true) => Ok(shader),
+        _ => Err(format!(
+            "Failed to compile shader: {}",
+            gl.get_shader_info_log(&shader).unwrap_or_default()
+        )),
+    }
+}
+
+fn link_program(
+    gl: &GL,
+    vertex_source: &str,
+    fragment_source: &str,
+) -> Result<WebGlProgram, String> {
+    let vertex_shader = compile_shader(gl, GL::VERTEX_SHADER, vertex_source)?;
+    let fragment_shader = compile_shader(gl, GL::FRAGMENT_SHADER, fragment_source)?;
+    let program = gl.create_program().ok_or("Failed to create program")?;
+    gl.attach_shader(&program, &vertex_shader);
+    gl.attach_shader(&program, &fragment_shader);
+    gl.link_program(&program);
+    gl.delete_shader(Some(&vertex_shader));
+    gl.delete_shader(Some(&fragment_shader));
+
+    match gl
+        .get_program_parameter(&program, GL::LINK_STATUS)
+        .as_bool()
+    {
+        Some(true) => Ok(program),
+        _ => Err(format!(
+            "Failed to link program: {}",
+            gl.get_program_info_log(&program).unwrap_or_default()
+        )),
+    }
+}
+
+/// Creates a vertex array and leaves it bound, so the buffers set up next belong to it.
+fn create_vertex_array(gl: &GL) -> Result<WebGlVertexArrayObject, String> {
+    let vertex_array = gl
+        .create_vertex_array()
+        .ok_or("Failed to create vertex array")?;
+    gl.bind_vertex_array(Some(&vertex_array));
+    Ok(vertex_array)
+}
+
+fn upload_floats(gl: &GL, data: &[f32]) -> Result<WebGlBuffer, String> {
+    let buffer = gl.create_buffer().ok_or("Failed to create buffer")?;
+    gl.bind_buffer(GL::ARRAY_BUFFER, Some(&buffer));
+    gl.buffer_data_with_array_buffer_view(
+        GL::ARRAY_BUFFER,
+        &js_sys::Float32Array::from(data),
+        GL::STATIC_DRAW,
+    );
+    Ok(buffer)
+}
+
+fn upload_indices(gl: &GL, data: &[u32]) -> Result<WebGlBuffer, String> {
+    let buffer = gl.create_buffer().ok_or("Failed to create buffer")?;
+    gl.bind_buffer(GL::ELEMENT_ARRAY_BUFFER, Some(&buffer));
+    gl.buffer_data_with_array_buffer_view(
+        GL::ELEMENT_ARRAY_BUFFER,
+        &js_sys::Uint32Array::from(data),
+        GL::STATIC_DRAW,
+    );
+    Ok(buffer)
+}
+
+/// Points a vec3 attribute of the bound vertex array at a buffer of packed floats.
+fn bind_attribute(gl: &GL, location: u32, buffer: &WebGlBuffer) {
+    gl.bind_buffer(GL::ARRAY_BUFFER, Some(buffer));
+    gl.enable_vertex_attrib_array(location);
+    gl.vertex_attrib_pointer_with_i32(location, 3, GL::FLOAT, false, 0, 0);
+}
+
+fn create_triad(gl: &GL) -> Result<WebGlVertexArrayObject, String> {
+    #[rustfmt::skip]
+    let positions = [
+        0.0, 0.0, 0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 0.0, 1.0, 0.0,
+        0.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+    ];
+    #[rustfmt::skip]
+    let colours = [
+        0.86, 0.15, 0.15, 0.86, 0.15, 0.15,
+        0.06, 0.62, 0.35, 0.06, 0.62, 0.35,
+        0.15, 0.39, 0.92, 0.15, 0.39, 0.92,
+    ];
+
+    let triad = create_vertex_array(gl)?;
+    bind_attribute(gl, POSITION_LOCATION, &upload_floats(gl, &positions)?);
+    bind_attribute(gl, COLOUR_LOCATION, &upload_floats(gl, &colours)?);
+    gl.bind_vertex_array(None);
+    Ok(triad)
+}
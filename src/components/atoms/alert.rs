@@ -21,6 +21,7 @@
  */
 
 use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::common::size::*;
 use crate::components::atoms::layout::*;
@@ -30,7 +31,7 @@ use crate::components::atoms::typography::{text_size, FONT_STR};
 //  Alert Type
 // ------------------------------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum AlertType {
     Error,
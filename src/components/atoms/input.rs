@@ -32,19 +32,37 @@ use crate::components::atoms::typography::{FONT_CLR, FONT_STR};
 
 fn input_field_string(align: Align) -> String {
     return format!(
-        "{} {} {} {} {} {} {} {} {}",
+        "{} {} {} {} {} {} {} {} {} {}",
         "bg-surface-50 dark:bg-surface-950",
         "w-64",
         standard_border(Some(BorderColor::Surface)),
         ROUND_BORDER,
         padding(Size::Sm),
         "invalid:border-red-500 invalid:ring-red-500",
+        "disabled:opacity-50 disabled:cursor-not-allowed",
         align,
         FONT_STR,
         FONT_CLR
     );
 }
 
+/// Writes a numeric signal back into its input when it is changed from elsewhere. The input is left
+/// alone while its text already parses to the signal's value, so partial entries such as "1." or
+/// "1e" are not overwritten mid-typing.
+fn sync_number_input<T>(input_ref: NodeRef<leptos::html::Input>, signal: RwSignal<Option<T>>)
+where
+    T: std::str::FromStr + PartialEq + ToString + Clone + Send + Sync + 'static,
+{
+    Effect::new(move |_| {
+        let value = signal.get();
+        if let Some(input) = input_ref.get() {
+            if input.value().parse::<T>().ok() != value {
+                input.set_value(&value.map(|v| v.to_string()).unwrap_or_default());
+            }
+        }
+    });
+}
+
 // ------------------------------------------------------------------------------------------------
 //  Components
 // ------------------------------------------------------------------------------------------------
@@ -56,6 +74,7 @@ pub fn Text(
     signal: RwSignal<String>,
     #[prop(default = None)] placeholder: Option<String>,
     #[prop(default = false)] required: bool,
+    #[prop(default = false)] disabled: bool,
 ) -> impl IntoView {
     let class_str = input_field_string(Align::Left);
 
@@ -66,7 +85,8 @@ pub fn Text(
             name={key}
             placeholder={placeholder.unwrap_or("text".to_string())}
             required={required}
-            prop:signal=move || signal.get()
+            disabled={disabled}
+            prop:value=move || signal.get()
             on:input=move |ev| {
                 signal.set(event_target_value(&ev));
             }
@@ -84,21 +104,25 @@ pub fn Float(
     #[prop(default = None)] min: Option<f64>,
     #[prop(default = None)] max: Option<f64>,
     #[prop(default = None)] step: Option<f64>,
+    #[prop(default = false)] disabled: bool,
 ) -> impl IntoView {
     let class_str = input_field_string(Align::Left);
+    let input_ref = NodeRef::<leptos::html::Input>::new();
+    sync_number_input(input_ref, signal);
 
     return view! {
         <input
+            node_ref={input_ref}
             class={class_str}
             type="number"
             id={id}
             name={key}
             placeholder={placeholder.unwrap_or("enter value".to_string())}
             required={required}
+            disabled={disabled}
             min={min}
             max={max}
             step={step.map_or("any".to_string(), |s| s.to_string())}
-            prop:signal=move || signal.get().map(|v| v.to_string()).unwrap_or_default()
             on:input=move |ev| {
                 let input_str = event_target_value(&ev);
                 if input_str.is_empty() {
@@ -121,21 +145,25 @@ pub fn Integer(
     #[prop(default = None)] min: Option<i64>,
     #[prop(default = None)] max: Option<i64>,
     #[prop(default = None)] step: Option<i64>,
+    #[prop(default = false)] disabled: bool,
 ) -> impl IntoView {
     let class_str = input_field_string(Align::Left);
+    let input_ref = NodeRef::<leptos::html::Input>::new();
+    sync_number_input(input_ref, signal);
 
     return view! {
         <input
+            node_ref={input_ref}
             class={class_str}
             type="number"
             id={id}
             name={key}
             placeholder={placeholder.unwrap_or("enter value".to_string())}
             required={required}
+            disabled={disabled}
             min={min}
             max={max}
             step={step.map_or("1".to_string(), |s| s.to_string())}
-            prop:signal=move || signal.get().map(|v| v.to_string()).unwrap_or_default()
             on:input=move |ev| {
                 let input_str = event_target_value(&ev);
                 if input_str.is_empty() {
@@ -166,7 +194,7 @@ pub fn Email(
             name={key}
             placeholder={placeholder}
             required={required}
-            prop:signal=move || signal.get()
+            prop:value=move || signal.get()
             on:input=move |ev| {
                 signal.set(event_target_value(&ev));
             }
@@ -192,7 +220,7 @@ pub fn Password(
             name={key}
             placeholder={placeholder}
             required={required}
-            prop:signal=move || signal.get()
+            prop:value=move || signal.get()
             on:input=move |ev| {
                 signal.set(event_target_value(&ev));
             }
@@ -207,6 +235,8 @@ pub fn File(
     key: String,
     signal: RwSignal<String>,
     #[prop(default = false)] required: bool,
+    #[prop(default = false)] multiple: bool,
+    #[prop(optional)] on_files: Option<Callback<Vec<web_sys::File>>>,
 ) -> impl IntoView {
     let class_str = format!(
         "{} 
@@ -231,31 +261,49 @@ pub fn File(
             id={id}
             name={key}
             required={required}
+            multiple={multiple}
             prop:signal=move || signal.get()
             on:input=move |ev| {
                 signal.set(event_target_value(&ev));
+                if let Some(on_files) = on_files {
+                    let files = event_target::<web_sys::HtmlInputElement>(&ev)
+                        .files()
+                        .map(|list| (0..list.length()).filter_map(|index| list.get(index)).collect())
+                        .unwrap_or_default();
+                    on_files.run(files);
+                }
             }
         />
     };
 }
 
-#[component]
-pub fn CheckBox(id: String, key: String, signal: RwSignal<bool>) -> impl IntoView {
-    let class_str = format!(
-        "w-5 h-5 {} {} {} {} {}",
+/// Classes of a checkbox, shared with checkboxes that are not bound to a single signal.
+pub fn checkbox_class() -> String {
+    return format!(
+        "w-5 h-5 {} {} {} {} {} {}",
         "bg-surface-50 dark:bg-surface-950",
         standard_border(Some(BorderColor::Surface)),
         ROUND_BORDER,
         "accent-primary-500",
-        "focus:ring-2 focus:ring-primary-300 focus:ring-opacity-50"
+        "focus:ring-2 focus:ring-primary-300 focus:ring-opacity-50",
+        "disabled:opacity-50 disabled:cursor-not-allowed"
     );
+}
 
+#[component]
+pub fn CheckBox(
+    id: String,
+    key: String,
+    signal: RwSignal<bool>,
+    #[prop(default = false)] disabled: bool,
+) -> impl IntoView {
     return view! {
         <input
-            class={class_str}
+            class={checkbox_class()}
             type="checkbox"
             id={id}
             name={key}
+            disabled={disabled}
             prop:checked=move || signal.get()
             on:change=move |ev| {
                 let checked = event_target_checked(&ev);
@@ -273,6 +321,7 @@ pub fn SelectText(
     signal: RwSignal<String>,
     #[prop(default = None)] placeholder: Option<String>,
     #[prop(default = false)] required: bool,
+    #[prop(default = false)] disabled: bool,
 ) -> impl IntoView {
     let class_str = input_field_string(Align::Left);
 
@@ -282,6 +331,7 @@ pub fn SelectText(
             id={id}
             name={key}
             required={required}
+            disabled={disabled}
             prop:value=move || signal.get()
             on:change=move |ev| {
                 signal.set(event_target_value(&ev));
@@ -321,6 +371,7 @@ pub fn SelectInteger(
     signal: RwSignal<Option<i64>>,
     #[prop(default = None)] placeholder: Option<String>,
     #[prop(default = false)] required: bool,
+    #[prop(default = false)] disabled: bool,
 ) -> impl IntoView {
     let class_str = input_field_string(Align::Left);
 
@@ -330,6 +381,7 @@ pub fn SelectInteger(
             id={id}
             name={key}
             required={required}
+            disabled={disabled}
             prop:value=move || {  // Convert Option<i64> to String for HTML
                 signal.get()
                     .map(|v| v.to_string())
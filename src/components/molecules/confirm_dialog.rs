@@ -0,0 +1,79 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: confirm_dialog.rs
+ * description: Modal dialog asking to confirm an action
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::prelude::*;
+
+use crate::common::size::*;
+use crate::components::atoms::button::*;
+use crate::components::atoms::layout::*;
+use crate::components::atoms::typography::H3;
+
+/// Shown while `open` is set. Confirming runs `on_confirm` and closes the dialog, cancelling or
+/// clicking outside of it only closes it.
+#[component]
+pub fn ConfirmDialog(
+    open: RwSignal<bool>,
+    title: String,
+    #[prop(default = "Confirm".to_string())] confirm_text: String,
+    on_confirm: Callback<()>,
+    children: ChildrenFn,
+) -> impl IntoView {
+    let title = StoredValue::new(title);
+    let confirm_text = StoredValue::new(confirm_text);
+
+    view! {
+        <Show when=move || open.get()>
+            <div
+                class="fixed inset-0 z-50 flex items-center justify-center bg-black/50"
+                on:click=move |_| open.set(false)
+            >
+                <div
+                    class="w-full max-w-2xl max-h-[80vh] overflow-auto p-4 rounded-lg shadow-lg bg-surface-50 dark:bg-surface-950"
+                    on:click=|ev| ev.stop_propagation()
+                >
+                    <Stack size={Size::Md}>
+                        <H3>{title.get_value()}</H3>
+                        {children()}
+                        <Stack horizontal={true} size={Size::Sm}>
+                            <Button button_data={
+                                ButtonData::new()
+                                    .size(Size::Sm)
+                                    .variant(Variant::Secondary)
+                                    .text("Cancel")
+                                    .on_click(move || open.set(false))
+                            } />
+                            <Button button_data={
+                                ButtonData::new()
+                                    .size(Size::Sm)
+                                    .text(&confirm_text.get_value())
+                                    .on_click(move || {
+                                        open.set(false);
+                                        on_confirm.run(());
+                                    })
+                            } />
+                        </Stack>
+                    </Stack>
+                </div>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,84 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: file_drop_zone.rs
+ * description: Area that accepts files dropped onto it or picked through a file input
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::prelude::*;
+
+use crate::common::size::*;
+use crate::components::atoms::input::File;
+use crate::components::atoms::layout::*;
+use crate::components::atoms::typography::*;
+
+#[component]
+pub fn FileDropZone(
+    key: String,
+    on_files: Callback<Vec<web_sys::File>>,
+    #[prop(default = "Drop files here or choose them below".to_string())] text: String,
+) -> impl IntoView {
+    let dragging = RwSignal::new(false);
+    let selection = RwSignal::new(String::new());
+
+    let on_drop = move |ev: leptos::ev::DragEvent| {
+        ev.prevent_default();
+        dragging.set(false);
+        let files = ev
+            .data_transfer()
+            .and_then(|transfer| transfer.files())
+            .map(|list| {
+                (0..list.length())
+                    .filter_map(|index| list.get(index))
+                    .collect()
+            })
+            .unwrap_or_default();
+        on_files.run(files);
+    };
+
+    return view! {
+        <div
+            class=move || format!(
+                "border-2 border-dashed {} {} {}",
+                ROUND_BORDER,
+                padding(Size::Md),
+                match dragging.get() {
+                    true => "border-primary-500 bg-primary-100 dark:bg-primary-900",
+                    false => "border-surface-300 dark:border-surface-700",
+                }
+            )
+            on:dragover=move |ev| {
+                ev.prevent_default();
+                dragging.set(true);
+            }
+            on:dragleave=move |_| dragging.set(false)
+            on:drop=on_drop
+        >
+            <Stack size=Size::Sm align=FlexAlign::Center>
+                <P>{text}</P>
+                <File
+                    id={format!("field-{}", key)}
+                    key={key}
+                    signal={selection}
+                    multiple={true}
+                    on_files={on_files}
+                />
+            </Stack>
+        </div>
+    };
+}
@@ -23,9 +23,10 @@
 use leptos::prelude::*;
 
 use crate::common::size::Size;
+use crate::components::atoms::alert::AlertType;
 use crate::components::atoms::input::*;
 use crate::components::atoms::layout::*;
-use crate::components::atoms::typography::{FONT_CLR, H4_CLASS};
+use crate::components::atoms::typography::{text_size, FONT_CLR, H4_CLASS};
 
 #[derive(Debug, Clone, PartialEq)]
 #[allow(dead_code)]
@@ -72,6 +73,7 @@ fn build_input(
     key: String,
     input_type: InputType,
     required: bool,
+    disabled: bool,
     placeholder: Option<String>,
 ) -> impl IntoView {
     return match input_type {
@@ -79,6 +81,7 @@ fn build_input(
           <Text
             id={id.clone()}
             key={key}
+            disabled={disabled}
             placeholder={placeholder}
             required={required}
             signal={signal}
@@ -94,6 +97,7 @@ fn build_input(
           <Float
             id={id.clone()}
             key={key}
+            disabled={disabled}
             placeholder={placeholder}
             required={required}
             signal={signal}
@@ -112,6 +116,7 @@ fn build_input(
           <Integer
             id={id.clone()}
             key={key}
+            disabled={disabled}
             placeholder={placeholder}
             required={required}
             signal={signal}
@@ -134,13 +139,14 @@ fn build_input(
         }
         .into_any(),
         InputType::CheckBox { signal } => view! {
-          <CheckBox id={id.clone()} key={key} signal={signal}/>
+          <CheckBox id={id.clone()} key={key} signal={signal} disabled={disabled}/>
         }
         .into_any(),
         InputType::SelectText { options, signal } => view! {
           <SelectText
             id={id.clone()}
             key={key}
+            disabled={disabled}
             placeholder={placeholder}
             required={required}
             options={options}
@@ -152,6 +158,7 @@ fn build_input(
           <SelectInteger
             id={id.clone()}
             key={key}
+            disabled={disabled}
             placeholder={placeholder}
             required={required}
             options={options}
@@ -171,9 +178,30 @@ pub fn FormField(
     #[prop(default = true)] horizontal: bool,
     #[prop(optional)] placeholder: Option<String>,
     #[prop(default = false)] required: bool,
+    #[prop(default = false)] disabled: bool,
+    #[prop(default = None)] help: Option<String>,
+    #[prop(default = None)] unit: Option<String>,
+    #[prop(optional, into)] error: Option<Signal<Option<String>>>,
 ) -> impl IntoView {
     let field_id = id.unwrap_or_else(|| format!("field-{}", key));
-    let input = build_input(field_id.clone(), key, input_type, required, placeholder);
+    let input = build_input(
+        field_id.clone(),
+        key,
+        input_type,
+        required,
+        disabled,
+        placeholder,
+    );
+    let input = match unit {
+        Some(unit) => view! {
+          <Stack size={Size::Xs} horizontal={true} align={FlexAlign::Center} fill_space={false}>
+            {input}
+            <span class={format!("{} {}", text_size(Size::Sm), FONT_CLR)}>{unit}</span>
+          </Stack>
+        }
+        .into_any(),
+        None => input.into_any(),
+    };
     let align = if horizontal {
         FlexAlign::Center
     } else {
@@ -181,10 +209,37 @@ pub fn FormField(
     };
     let spacing = if horizontal { Size::Sm } else { Size::Xs };
 
-    return view! {
+    let field = view! {
       <Stack size={spacing} horizontal={horizontal} align={align} add_class="justify-between".to_string()>
-        <label class={format!("{} {}", H4_CLASS, FONT_CLR)} for={field_id.clone()}>{label}</label>
+        <label class={format!("{} {}", H4_CLASS, FONT_CLR)} for={field_id.clone()} title={help.clone()}>{label}</label>
         {input}
       </Stack>
     };
+
+    if help.is_none() && error.is_none() {
+        return field.into_any();
+    }
+
+    return view! {
+      <Stack size={Size::Xs}>
+        {field}
+        {help.map(|help| view! {
+          <p class={format!("{} {} opacity-75", text_size(Size::Sm), FONT_CLR)}>{help}</p>
+        })}
+        {error.map(|error| view! { <FieldError error={error} /> })}
+      </Stack>
+    }
+    .into_any();
+}
+
+/// Validation message shown beneath a field, renders nothing while there is no error.
+#[component]
+pub fn FieldError(#[prop(into)] error: Signal<Option<String>>) -> impl IntoView {
+    return view! {
+      <Show when={move || error.with(Option::is_some)}>
+        <p class={format!("{} {}", text_size(Size::Sm), AlertType::Error.text_color())}>
+          {move || error.get().unwrap_or_default()}
+        </p>
+      </Show>
+    };
 }
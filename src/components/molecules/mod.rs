@@ -21,7 +21,9 @@
  */
 
 pub mod button_bar;
+pub mod confirm_dialog;
 pub mod drop_down;
+pub mod file_drop_zone;
 pub mod form_field;
 pub mod markdown_render;
 pub mod schema_form;
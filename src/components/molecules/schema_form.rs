@@ -20,73 +20,650 @@
  * ------------------------------------------------------------------------------------------------
  */
 
+use indexmap::IndexMap;
 use leptos::prelude::*;
 use serde::de::Error as ErrorDe;
-use serde_json::{Error, Value};
-use std::collections::HashMap;
+use serde_json::{Error, Map, Number, Value};
+use std::sync::Arc;
 
+use crate::common::json_schema;
+use crate::common::schema_validator::{validate, ValidationErrors};
 use crate::common::size::*;
+use crate::components::atoms::alert::*;
+use crate::components::atoms::button::*;
 use crate::components::atoms::layout::*;
+use crate::components::atoms::typography::{FONT_CLR, H4_CLASS, P};
 use crate::components::molecules::form_field::*;
+use crate::components::molecules::section::*;
+
+/// Nesting limit for objects and arrays, guards against definitions that contain themselves.
+const MAX_DEPTH: usize = 16;
+
+// -------------------------------------------------------------------------------------------------
+// Form State
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Clone)]
+enum FieldValue {
+    Text(RwSignal<String>),
+    Integer(RwSignal<Option<i64>>),
+    Float(RwSignal<Option<f64>>),
+    Boolean(RwSignal<bool>),
+    Object(ObjectState),
+    Array(ArrayState),
+    /// A `const` property, typically the discriminator of a `oneOf` alternative. It is not shown
+    /// but always exported.
+    Const(Value),
+}
+
+type Fields = IndexMap<String, SchemaField>;
+
+#[derive(Clone, Default)]
+struct ObjectState {
+    fields: Fields,
+    conditions: Vec<Condition>,
+}
+
+/// Properties that only apply to an object under some condition. Every branch is built up front
+/// so values survive switching back and forth, only the active branches are shown and exported.
+#[derive(Clone)]
+enum Condition {
+    /// `oneOf`/`anyOf`, the user picks which alternative applies.
+    Choice {
+        label: String,
+        selected: RwSignal<String>,
+        options: Vec<(String, ObjectState)>,
+    },
+    /// `if`/`then`/`else`, the branch follows from the object's own properties.
+    IfThenElse {
+        condition: Value,
+        root: Arc<Value>,
+        then: ObjectState,
+        otherwise: ObjectState,
+    },
+    /// `dependentSchemas`, applies while the named property has a value.
+    Dependent { key: String, state: ObjectState },
+}
+
+#[derive(Clone)]
+struct ArrayState {
+    /// Root schema, needed to resolve `$ref`s when rows are added.
+    root: Arc<Value>,
+    depth: usize,
+    items: RwSignal<Vec<SchemaField>>,
+}
+
+/// A form value together with the resolved schema it was built from.
+#[derive(Clone)]
+struct SchemaField {
+    schema: Value,
+    required: bool,
+    value: FieldValue,
+}
+
+impl SchemaField {
+    /// Creates the signals for a schema, or `None` when its type is not supported.
+    fn new(schema: &Value, root: &Arc<Value>, depth: usize) -> Option<Self> {
+        let schema = json_schema::resolve(schema, root);
+        let enum_list = schema["enum"].as_array();
+        let field_type = match schema.get("const") {
+            Some(_) => "const",
+            None if is_conditional(&schema) => {
+                json_schema::schema_type(&schema).unwrap_or("object")
+            }
+            None => json_schema::schema_type(&schema)?,
+        };
+
+        let value = match field_type {
+            "const" => FieldValue::Const(schema["const"].clone()),
+            "string" => {
+                // select the first option when the field is an enum
+                let first = enum_list
+                    .and_then(|options| options.first())
+                    .map(enum_option_text)
+                    .unwrap_or_default();
+                FieldValue::Text(RwSignal::new(first))
+            }
+            "integer" => {
+                let first = enum_list
+                    .and_then(|options| options.first())
+                    .and_then(|option| option.as_i64());
+                FieldValue::Integer(RwSignal::new(first))
+            }
+            "number" => FieldValue::Float(RwSignal::new(None)),
+            "boolean" => FieldValue::Boolean(RwSignal::new(false)),
+            "object" if depth < MAX_DEPTH => {
+                FieldValue::Object(object_from_schema(&schema, root, depth + 1))
+            }
+            "array" if depth < MAX_DEPTH && schema["items"].is_object() => {
+                let min_items = schema["minItems"].as_u64().unwrap_or(0);
+                let items = (0..min_items)
+                    .filter_map(|_| SchemaField::new(&schema["items"], root, depth + 1))
+                    .collect();
+                FieldValue::Array(ArrayState {
+                    root: root.clone(),
+                    depth,
+                    items: RwSignal::new(items),
+                })
+            }
+            _ => return None,
+        };
+
+        let field = Self {
+            schema,
+            required: false,
+            value,
+        };
+        if let Some(default) = field.schema.get("default") {
+            field.set_json(default, "", &mut Vec::new());
+        }
+        Some(field)
+    }
+
+    /// Sets the field from a JSON value, `null` clears it. Paths of values that do not fit the
+    /// schema (unknown keys, wrong types) are added to `unmatched` and otherwise ignored.
+    fn set_json(&self, value: &Value, path: &str, unmatched: &mut Vec<String>) {
+        let fits = match (&self.value, value) {
+            (FieldValue::Text(signal), Value::String(_) | Value::Number(_)) => {
+                signal.set(enum_option_text(value));
+                true
+            }
+            (FieldValue::Text(signal), Value::Null) => {
+                signal.set(String::new());
+                true
+            }
+            (FieldValue::Integer(signal), _) if value.is_i64() || value.is_null() => {
+                signal.set(value.as_i64());
+                true
+            }
+            (FieldValue::Float(signal), Value::Number(_) | Value::Null) => {
+                signal.set(value.as_f64());
+                true
+            }
+            (FieldValue::Boolean(signal), Value::Bool(bool_val)) => {
+                signal.set(*bool_val);
+                true
+            }
+            (FieldValue::Object(object), Value::Object(_)) => {
+                object.set_json(value, path, unmatched);
+                true
+            }
+            (FieldValue::Array(array), Value::Array(values)) => {
+                let items = values
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, item_value)| {
+                        let item =
+                            SchemaField::new(&self.schema["items"], &array.root, array.depth + 1)?;
+                        item.set_json(item_value, &child_path(path, &index.to_string()), unmatched);
+                        Some(item)
+                    })
+                    .collect();
+                array.items.set(items);
+                true
+            }
+            (FieldValue::Const(constant), _) => constant == value,
+            _ => false,
+        };
+        if !fits {
+            unmatched.push(path.to_string());
+        }
+    }
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    match path {
+        "" => key.to_string(),
+        _ => format!("{}.{}", path, key),
+    }
+}
+
+/// Fields in the order the schema declares its properties, properties carrying an `x-order`
+/// number are moved ahead of the rest and sorted by it.
+fn fields_from_schema(schema: &Value, root: &Arc<Value>, depth: usize) -> Fields {
+    let required = schema["required"].as_array();
+    let mut fields = schema["properties"]
+        .as_object()
+        .map(|properties| {
+            properties
+                .iter()
+                .filter_map(|(key, object)| {
+                    let mut field = SchemaField::new(object, root, depth)?;
+                    field.required = required
+                        .is_some_and(|required| required.contains(&Value::from(key.as_str())));
+                    Some((key.clone(), field))
+                })
+                .collect::<Fields>()
+        })
+        .unwrap_or_default();
+
+    let order = |field: &SchemaField| field.schema["x-order"].as_f64().unwrap_or(f64::INFINITY);
+    fields.sort_by(|_, a, _, b| order(a).total_cmp(&order(b)));
+    fields
+}
+
+fn fields_to_json(fields: &Fields) -> Map<String, Value> {
+    fields
+        .iter()
+        .filter_map(|(key, field)| Some((key.clone(), field.value.to_json()?)))
+        .collect()
+}
+
+fn is_conditional(schema: &Value) -> bool {
+    ["oneOf", "anyOf", "if", "dependentSchemas"]
+        .iter()
+        .any(|keyword| schema.get(keyword).is_some())
+}
+
+/// Name of the property holding a `const` in the first alternative, e.g. `model` in
+/// `oneOf: [{properties: {model: {const: "k-epsilon"}, ...}}, ...]`.
+fn discriminator(options: &[Value]) -> Option<(&String, &Value)> {
+    options.first()?["properties"]
+        .as_object()?
+        .iter()
+        .find(|(_, property)| property.get("const").is_some())
+}
+
+fn object_from_schema(schema: &Value, root: &Arc<Value>, depth: usize) -> ObjectState {
+    if depth >= MAX_DEPTH {
+        return ObjectState::default();
+    }
+    let branch =
+        |schema: &Value| object_from_schema(&json_schema::resolve(schema, root), root, depth + 1);
+    let mut conditions = Vec::new();
+
+    for keyword in ["oneOf", "anyOf"] {
+        let Some(options) = schema[keyword].as_array() else {
+            continue;
+        };
+        let options = options
+            .iter()
+            .map(|option| json_schema::resolve(option, root))
+            .collect::<Vec<Value>>();
+        let tag = discriminator(&options);
+        let label = tag
+            .map(|(key, property)| json_schema::title(property, key).to_string())
+            .unwrap_or("Type".to_string());
+
+        let options = options
+            .iter()
+            .enumerate()
+            .map(|(index, option)| {
+                let title = option["title"]
+                    .as_str()
+                    .map(|title| title.to_string())
+                    .or_else(|| {
+                        let (key, _) = tag?;
+                        Some(enum_option_text(&option["properties"][key]["const"]))
+                    })
+                    .unwrap_or(format!("Option {}", index + 1));
+                (title, branch(option))
+            })
+            .collect();
+
+        conditions.push(Condition::Choice {
+            label,
+            selected: RwSignal::new("0".to_string()),
+            options,
+        });
+    }
+
+    if let Some(condition) = schema.get("if") {
+        conditions.push(Condition::IfThenElse {
+            condition: condition.clone(),
+            root: root.clone(),
+            then: branch(&schema["then"]),
+            otherwise: branch(&schema["else"]),
+        });
+    }
+
+    for (key, dependent) in schema["dependentSchemas"].as_object().into_iter().flatten() {
+        conditions.push(Condition::Dependent {
+            key: key.clone(),
+            state: branch(dependent),
+        });
+    }
+
+    ObjectState {
+        fields: fields_from_schema(schema, root, depth),
+        conditions,
+    }
+}
+
+impl ObjectState {
+    /// Sets the object's properties, including those belonging to conditional branches. A `oneOf`
+    /// or `anyOf` selector switches to the alternative that best fits the value.
+    fn set_json(&self, value: &Value, path: &str, unmatched: &mut Vec<String>) {
+        let Some(map) = value.as_object() else {
+            return;
+        };
+        for condition in &self.conditions {
+            condition.select(map);
+        }
+        for (key, property) in map {
+            let fields = self.fields_named(key);
+            if fields.is_empty() {
+                unmatched.push(child_path(path, key));
+            }
+            for field in fields {
+                field.set_json(property, &child_path(path, key), unmatched);
+            }
+        }
+    }
+
+    /// Fields for a property, the object's own and any declared by its branches.
+    fn fields_named(&self, key: &str) -> Vec<&SchemaField> {
+        let mut fields = self.fields.get(key).into_iter().collect::<Vec<_>>();
+        for condition in &self.conditions {
+            for branch in condition.branches() {
+                fields.extend(branch.fields_named(key));
+            }
+        }
+        fields
+    }
+
+    /// Editable numeric fields of the object and its nested objects. Fields of conditional
+    /// branches and array items are skipped as they are not always part of the config.
+    fn numeric_fields<'a>(
+        &'a self,
+        path: &[String],
+        labels: &[&'a str],
+        fields: &mut Vec<NumericField>,
+    ) {
+        for (key, field) in &self.fields {
+            let mut field_path = path.to_vec();
+            field_path.push(key.clone());
+            let mut field_labels = labels.to_vec();
+            field_labels.push(json_schema::title(&field.schema, key));
+            let integer = match &field.value {
+                FieldValue::Integer(_) => true,
+                FieldValue::Float(_) => false,
+                FieldValue::Object(object) => {
+                    object.numeric_fields(&field_path, &field_labels, fields);
+                    continue;
+                }
+                _ => continue,
+            };
+            if !read_only(&field.schema) {
+                fields.push(NumericField {
+                    path: field_path,
+                    label: field_labels.join(" / "),
+                    integer,
+                });
+            }
+        }
+    }
+
+    /// Values of the object's own properties merged with those of its active branches.
+    fn to_json(&self) -> Map<String, Value> {
+        let mut map = fields_to_json(&self.fields);
+        let base = Value::Object(map.clone());
+        for condition in &self.conditions {
+            if let Some(branch) = condition.active(&base) {
+                map.extend(branch.to_json());
+            }
+        }
+        map
+    }
+}
+
+impl Condition {
+    /// Picks the alternative of a choice that shares most keys with `map`, skipping those whose
+    /// `const` properties disagree with it.
+    fn select(&self, map: &Map<String, Value>) {
+        let Condition::Choice {
+            selected, options, ..
+        } = self
+        else {
+            return;
+        };
+        let best = options
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, option))| {
+                option.fields.iter().all(|(key, field)| match &field.value {
+                    FieldValue::Const(constant) => {
+                        map.get(key).is_none_or(|value| value == constant)
+                    }
+                    _ => true,
+                })
+            })
+            .map(|(index, (_, option))| {
+                let shared = map
+                    .keys()
+                    .filter(|key| option.fields.contains_key(*key))
+                    .count();
+                (index, shared)
+            })
+            .filter(|(_, shared)| *shared > 0)
+            .max_by_key(|(index, shared)| (*shared, std::cmp::Reverse(*index)));
+        if let Some((index, _)) = best {
+            selected.set(index.to_string());
+        }
+    }
+
+    fn branches(&self) -> Vec<&ObjectState> {
+        match self {
+            Condition::Choice { options, .. } => options.iter().map(|(_, state)| state).collect(),
+            Condition::IfThenElse {
+                then, otherwise, ..
+            } => vec![then, otherwise],
+            Condition::Dependent { state, .. } => vec![state],
+        }
+    }
+
+    /// The branch that currently applies, given the values of the object's own properties.
+    fn active(&self, base: &Value) -> Option<&ObjectState> {
+        match self {
+            Condition::Choice {
+                selected, options, ..
+            } => options
+                .get(selected.get().parse::<usize>().unwrap_or(0))
+                .map(|(_, state)| state),
+            Condition::IfThenElse {
+                condition,
+                root,
+                then,
+                otherwise,
+            } => match validate(condition, root, base).is_empty() {
+                true => Some(then),
+                false => Some(otherwise),
+            },
+            Condition::Dependent { key, state } => base
+                .get(key)
+                .is_some_and(|value| !value.is_null())
+                .then_some(state),
+        }
+    }
+}
+
+impl FieldValue {
+    /// JSON value of the field, `None` when it has been left empty.
+    fn to_json(&self) -> Option<Value> {
+        match self {
+            FieldValue::Text(signal) => {
+                let value = signal.get();
+                (!value.is_empty()).then_some(Value::String(value))
+            }
+            FieldValue::Integer(signal) => signal.get().map(Value::from),
+            FieldValue::Float(signal) => signal.get().and_then(Number::from_f64).map(Value::Number),
+            FieldValue::Boolean(signal) => Some(Value::Bool(signal.get())),
+            FieldValue::Object(object) => Some(Value::Object(object.to_json())),
+            FieldValue::Array(array) => Some(Value::Array(array.items.with(|items| {
+                items
+                    .iter()
+                    .map(|item| item.value.to_json().unwrap_or(Value::Null))
+                    .collect()
+            }))),
+            FieldValue::Const(value) => Some(value.clone()),
+        }
+    }
+}
+
+fn enum_option_text(value: &Value) -> String {
+    match value {
+        Value::String(string_val) => string_val.clone(),
+        Value::Number(num_val) => num_val.to_string(),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// A numeric config value, `path` holds the keys leading to it from the config root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumericField {
+    pub path: Vec<String>,
+    pub label: String,
+    pub integer: bool,
+}
+
+#[derive(Clone, Default)]
+pub struct SchemaFormState {
+    object: ObjectState,
+    root: Arc<Value>,
+    /// `None` until the form is first validated, afterwards kept up to date as values change.
+    errors: RwSignal<Option<ValidationErrors>>,
+}
+
+impl SchemaFormState {
+    fn from_schema(root: Arc<Value>) -> Self {
+        let schema = json_schema::resolve(&root, &root);
+        Self {
+            object: object_from_schema(&schema, &root, 0),
+            root,
+            errors: RwSignal::new(None),
+        }
+    }
+
+    /// Export form data as JSON Value, nested objects and arrays mirror the schema
+    pub fn to_json(&self) -> serde_json::Value {
+        Value::Object(self.object.to_json())
+    }
+
+    /// Loads a config into the form, e.g. a previous job's config resource. Returns the dotted paths
+    /// of values the schema has no matching field for, these are left out of the form.
+    pub fn load_json(&self, value: &Value) -> Vec<String> {
+        let mut unmatched = Vec::new();
+        match value {
+            Value::Object(_) => self.object.set_json(value, "", &mut unmatched),
+            _ => unmatched.push(String::new()),
+        }
+        unmatched.sort();
+        unmatched.dedup();
+        unmatched
+    }
+
+    pub fn numeric_fields(&self) -> Vec<NumericField> {
+        let mut fields = Vec::new();
+        self.object.numeric_fields(&[], &[], &mut fields);
+        fields
+    }
+
+    /// Checks a config derived from the form, e.g. one case of a sweep, without touching the
+    /// errors shown in the form.
+    pub fn validate_config(&self, value: &Value) -> ValidationErrors {
+        validate(&self.root, &self.root, value)
+    }
+
+    /// Validates the form against its schema, errors are shown beneath the offending fields.
+    pub fn validate(&self) -> bool {
+        self.check(&self.to_json())
+    }
+
+    fn check(&self, value: &Value) -> bool {
+        let errors = validate(&self.root, &self.root, value);
+        let is_valid = errors.is_empty();
+        self.errors.set(Some(errors));
+        is_valid
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Field Builders
+// -------------------------------------------------------------------------------------------------
+
+/// The first of the schema's `examples`, shown as the input's placeholder.
+fn placeholder(object: &Value) -> String {
+    object["examples"]
+        .get(0)
+        .map(enum_option_text)
+        .unwrap_or_default()
+}
+
+fn help(object: &Value) -> Option<String> {
+    object["description"].as_str().map(|text| text.to_string())
+}
+
+/// Units of a numeric field, declared with the `x-unit` extension keyword (e.g. "m/s", "Pa").
+fn unit(object: &Value) -> Option<String> {
+    object["x-unit"].as_str().map(|text| text.to_string())
+}
+
+fn read_only(object: &Value) -> bool {
+    object["readOnly"].as_bool().unwrap_or(false)
+}
 
 fn build_string_form_field(
     key: &String,
+    label: String,
     object: &Value,
     signal: RwSignal<String>,
+    required: bool,
+    error: Signal<Option<String>>,
 ) -> impl IntoView {
-    let title = object["title"].as_str().unwrap_or("none");
     let enum_list = object["enum"].as_array();
 
     if enum_list.is_none() {
         return view! {<FormField
-        label={title.to_string()}
+        label={label}
         key={key.to_string()}
-        placeholder={"".to_string()}
+        placeholder={placeholder(object)}
+        required={required}
+        disabled={read_only(object)}
+        help={help(object)}
+        error={error}
         input_type=InputType::Text { signal: signal} />};
     } else {
         let options: Vec<(String, String)> = enum_list
             .unwrap()
             .iter()
             .map(|v| {
-                if let Some(string_val) = v.as_str() {
-                    (string_val.to_string(), string_val.to_string())
-                } else if let Some(int_val) = v.as_i64() {
-                    let s = int_val.to_string();
-                    (s.clone(), s)
-                } else {
-                    ("unknown".to_string(), "unknown".to_string())
-                }
+                let s = enum_option_text(v);
+                (s.clone(), s)
             })
             .collect();
 
-        // set a default if none was provided
-        if signal.get() == "".to_string() {
-            signal.set(options[0].0.clone());
-        }
-
         return view! {<FormField
-        label={title.to_string()}
+        label={label}
         key={key.to_string()}
-        placeholder={"".to_string()}
+        placeholder={placeholder(object)}
+        required={required}
+        disabled={read_only(object)}
+        help={help(object)}
+        error={error}
         input_type=InputType::SelectText { options: (options), signal: signal }/> };
     }
 }
 
 fn build_integer_form_field(
     key: &String,
+    label: String,
     object: &Value,
     signal: RwSignal<Option<i64>>,
+    required: bool,
+    error: Signal<Option<String>>,
 ) -> impl IntoView {
-    let title = object["title"].as_str().unwrap_or("none");
     let enum_list = object["enum"].as_array();
 
-    signal.set(object["default"].as_i64());
-
     if enum_list.is_none() {
         return view! {<FormField
-        label={title.to_string()}
+        label={label}
         key={key.to_string()}
-        placeholder={"".to_string()}
+        placeholder={placeholder(object)}
+        required={required}
+        disabled={read_only(object)}
+        help={help(object)}
+        unit={unit(object)}
+        error={error}
         input_type=InputType::Integer {
           signal: signal,
           min: (object["minimum"].as_i64()),
@@ -96,37 +673,39 @@ fn build_integer_form_field(
         let options: Vec<(i64, String)> = enum_list
             .unwrap()
             .iter()
-            .map(|v| {
-                let int_val = v.as_i64().unwrap();
-                (int_val, int_val.to_string())
-            })
+            .filter_map(|v| v.as_i64())
+            .map(|int_val| (int_val, int_val.to_string()))
             .collect();
 
-        // set a default if none was provided
-        if signal.get().is_none() {
-            signal.set(Some(options[0].0.clone()));
-        }
-
         return view! {<FormField
-        label={title.to_string()}
+        label={label}
         key={key.to_string()}
-        placeholder={"".to_string()}
+        placeholder={placeholder(object)}
+        required={required}
+        disabled={read_only(object)}
+        help={help(object)}
+        error={error}
         input_type=InputType::SelectInteger { options: (options), signal: signal }/> };
     }
 }
 
 fn build_float_form_field(
     key: &String,
+    label: String,
     object: &Value,
     signal: RwSignal<Option<f64>>,
+    required: bool,
+    error: Signal<Option<String>>,
 ) -> impl IntoView {
-    let title = object["title"].as_str().unwrap_or("none");
-    signal.set(object["default"].as_f64());
-
     return view! {<FormField
-    label={title.to_string()}
+    label={label}
     key={key.to_string()}
-    placeholder={"".to_string()}
+    placeholder={placeholder(object)}
+    required={required}
+    disabled={read_only(object)}
+    help={help(object)}
+    unit={unit(object)}
+    error={error}
     input_type=InputType::Float {
       signal: signal,
       min: (object["minimum"].as_f64()),
@@ -134,97 +713,310 @@ fn build_float_form_field(
       step: (None) }/>};
 }
 
-fn schema_to_form_fields(
-    schema_json: &str,
-    form_state: &mut SchemaFormState,
-) -> Result<Vec<AnyView>, Error> {
-    let schema: Value = serde_json::from_str(schema_json)?;
-    let properties = schema["properties"]
-        .as_object()
-        .ok_or_else(move || ErrorDe::custom("Missing 'properties' field in schema"))?;
+fn build_boolean_form_field(
+    key: &String,
+    label: String,
+    object: &Value,
+    signal: RwSignal<bool>,
+    error: Signal<Option<String>>,
+) -> impl IntoView {
+    return view! {<FormField
+    label={label}
+    key={key.to_string()}
+    disabled={read_only(object)}
+    help={help(object)}
+    error={error}
+    input_type=InputType::CheckBox { signal: signal }/>};
+}
 
-    let mut form_fields = Vec::new();
+/// Heading level of a nested object or `x-group` section, the form itself sits below an H2.
+fn section_level(depth: usize) -> SectionLevel {
+    match depth {
+        0 => SectionLevel::H3,
+        _ => SectionLevel::H4,
+    }
+}
 
-    for (field_key, object) in properties.iter() {
-        let field_type = object["type"].as_str().unwrap_or("none");
+fn build_object_form_field(
+    key: &String,
+    label: String,
+    schema: &Value,
+    object: &ObjectState,
+    depth: usize,
+    errors: RwSignal<Option<ValidationErrors>>,
+) -> impl IntoView {
+    let fields = build_object_fields(object, key, depth + 1, errors);
 
-        form_fields.push(match field_type {
-            "string" => {
-                let signal = form_state
-                    .text_signals
-                    .entry(field_key.clone())
-                    .or_insert_with(|| RwSignal::new(String::new()));
-                build_string_form_field(field_key, object, *signal).into_any()
-            }
-            "integer" => {
-                let signal = form_state
-                    .int_signals
-                    .entry(field_key.clone())
-                    .or_insert_with(|| RwSignal::new(None));
-                build_integer_form_field(field_key, object, *signal).into_any()
-            }
-            "number" => {
-                let signal = form_state
-                    .float_signals
-                    .entry(field_key.clone())
-                    .or_insert_with(|| RwSignal::new(None));
-                build_float_form_field(field_key, object, *signal).into_any()
-            }
-            _ => continue,
-        });
-    }
+    return view! {
+        <Section level={section_level(depth)} collapsible={true} title={label}>
+            {help(schema).map(|help| view! { <P>{help}</P> })}
+            <Grid size={Size::Md} cols=1>
+                {fields}
+            </Grid>
+        </Section>
+    };
+}
 
-    return Ok(form_fields);
+fn build_array_form_field(
+    key: &String,
+    label: String,
+    object: &Value,
+    array: &ArrayState,
+    depth: usize,
+    errors: RwSignal<Option<ValidationErrors>>,
+) -> impl IntoView {
+    let min_items = object["minItems"].as_u64().unwrap_or(0) as usize;
+    let max_items = object["maxItems"]
+        .as_u64()
+        .map(|max| max as usize)
+        .unwrap_or(usize::MAX);
+    let item_schema = object["items"].clone();
+    let root = array.root.clone();
+    let items = array.items;
+    let error = field_error(errors, key);
+    let key = key.clone();
+
+    // rows are rebuilt whenever one is added or removed, values live in the row signals
+    let rows = move || {
+        let current = items.get();
+        let count = current.len();
+        let item_schema = item_schema.clone();
+        let root = root.clone();
+
+        let add_button = ButtonData::new()
+            .size(Size::Sm)
+            .variant(Variant::Secondary)
+            .text("Add")
+            .state(match count < max_items {
+                true => State::Default,
+                false => State::Disabled,
+            })
+            .on_click(move || {
+                if items.with(|items| items.len() < max_items) {
+                    if let Some(item) = SchemaField::new(&item_schema, &root, depth + 1) {
+                        items.update(|items| items.push(item));
+                    }
+                }
+            });
+
+        let row_views = current
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let remove_button = ButtonData::new()
+                    .size(Size::Sm)
+                    .variant(Variant::Secondary)
+                    .text("Remove")
+                    .state(match count > min_items {
+                        true => State::Default,
+                        false => State::Disabled,
+                    })
+                    .on_click(move || {
+                        items.update(|items| {
+                            if items.len() > min_items && index < items.len() {
+                                items.remove(index);
+                            }
+                        })
+                    });
+                let item_key = format!("{}.{}", key, index);
+                let item_label = format!("#{}", index + 1);
+
+                view! {
+                    <Stack horizontal={true} align={FlexAlign::Start}>
+                        <div class="grow">{build_form_field(&item_key, item_label, item, depth + 1, errors)}</div>
+                        <Button button_data={remove_button} />
+                    </Stack>
+                }
+            })
+            .collect::<Vec<_>>();
+
+        view! {
+            <Button button_data={add_button} />
+            {row_views}
+        }
+    };
+
+    return view! {
+        <Stack size={Size::Sm}>
+            <label class={format!("{} {}", H4_CLASS, FONT_CLR)}>{label}</label>
+            {help(object).map(|help| view! { <P>{help}</P> })}
+            <FieldError error={error} />
+            <Stack size={Size::Sm} align={FlexAlign::Start} add_class="pl-4".to_string()>
+                {rows}
+            </Stack>
+        </Stack>
+    };
 }
 
-#[derive(Clone, Default)]
-pub struct SchemaFormState {
-    text_signals: HashMap<String, RwSignal<String>>,
-    float_signals: HashMap<String, RwSignal<Option<f64>>>,
-    int_signals: HashMap<String, RwSignal<Option<i64>>>,
+/// Validation messages for the field at `key`, joined into a single line.
+fn field_error(errors: RwSignal<Option<ValidationErrors>>, key: &str) -> Signal<Option<String>> {
+    let key = key.to_string();
+    Signal::derive(move || errors.with(|errors| Some(errors.as_ref()?.get(&key)?.join(". "))))
 }
 
-impl SchemaFormState {
-    /// Export form data as JSON Value
-    pub fn to_json(&self) -> serde_json::Value {
-        let mut map = serde_json::Map::new();
+fn build_form_field(
+    key: &String,
+    label: String,
+    field: &SchemaField,
+    depth: usize,
+    errors: RwSignal<Option<ValidationErrors>>,
+) -> AnyView {
+    let (schema, required, error) = (&field.schema, field.required, field_error(errors, key));
 
-        // Add text fields
-        for (key, signal) in &self.text_signals {
-            let value = signal.get();
-            if !value.is_empty() {
-                map.insert(key.clone(), serde_json::Value::String(value));
-            }
+    match &field.value {
+        FieldValue::Text(signal) => {
+            build_string_form_field(key, label, schema, *signal, required, error).into_any()
+        }
+        FieldValue::Integer(signal) => {
+            build_integer_form_field(key, label, schema, *signal, required, error).into_any()
         }
+        FieldValue::Float(signal) => {
+            build_float_form_field(key, label, schema, *signal, required, error).into_any()
+        }
+        FieldValue::Boolean(signal) => {
+            build_boolean_form_field(key, label, schema, *signal, error).into_any()
+        }
+        FieldValue::Object(object) => {
+            build_object_form_field(key, label, schema, object, depth, errors).into_any()
+        }
+        FieldValue::Array(array) => {
+            build_array_form_field(key, label, schema, array, depth, errors).into_any()
+        }
+        FieldValue::Const(_) => ().into_any(),
+    }
+}
 
-        // Add integer fields
-        for (key, signal) in &self.int_signals {
-            if let Some(value) = signal.get() {
-                map.insert(key.clone(), serde_json::Value::Number(value.into()));
-            }
+/// Views for every field of an object, keyed by their dotted path (e.g. `mesh.cells`, `probes.0`).
+fn build_form_fields(
+    fields: &Fields,
+    prefix: &str,
+    depth: usize,
+    errors: RwSignal<Option<ValidationErrors>>,
+) -> Vec<AnyView> {
+    let mut views = Vec::new();
+    let mut groups: IndexMap<String, Vec<AnyView>> = IndexMap::new();
+
+    for (key, field) in fields {
+        let path = child_path(prefix, key);
+        let label = json_schema::title(&field.schema, key).to_string();
+        let view = build_form_field(&path, label, field, depth, errors);
+
+        match field.schema["x-group"].as_str() {
+            Some(group) => groups.entry(group.to_string()).or_default().push(view),
+            None => views.push(view),
         }
+    }
 
-        // Add float fields
-        for (key, signal) in &self.float_signals {
-            if let Some(value) = signal.get() {
-                if let Some(num) = serde_json::Number::from_f64(value) {
-                    map.insert(key.clone(), serde_json::Value::Number(num));
-                }
+    // grouped fields follow the ungrouped ones, groups in the order they are first used
+    for (group, group_views) in groups {
+        views.push(
+            view! {
+                <Section level={section_level(depth)} collapsible={true} title={group}>
+                    <Grid size={Size::Md} cols=1>
+                        {group_views}
+                    </Grid>
+                </Section>
             }
+            .into_any(),
+        );
+    }
+    views
+}
+
+/// An object's own fields followed by its conditional branches.
+fn build_object_fields(
+    object: &ObjectState,
+    prefix: &str,
+    depth: usize,
+    errors: RwSignal<Option<ValidationErrors>>,
+) -> Vec<AnyView> {
+    let mut views = build_form_fields(&object.fields, prefix, depth, errors);
+    for condition in &object.conditions {
+        views.push(build_condition(object, condition, prefix, depth, errors));
+    }
+    views
+}
+
+fn build_condition(
+    object: &ObjectState,
+    condition: &Condition,
+    prefix: &str,
+    depth: usize,
+    errors: RwSignal<Option<ValidationErrors>>,
+) -> AnyView {
+    let selector = match condition {
+        Condition::Choice {
+            label,
+            selected,
+            options,
+        } => {
+            let options = options
+                .iter()
+                .enumerate()
+                .map(|(index, (title, _))| (index.to_string(), title.clone()))
+                .collect::<Vec<(String, String)>>();
+            Some(view! {<FormField
+            label={label.clone()}
+            key={format!("{}#choice", prefix)}
+            input_type=InputType::SelectText { options: (options), signal: *selected }/>})
         }
+        _ => None,
+    };
+
+    // branches are rebuilt as the properties they depend on change, values live in their signals
+    let fields = object.fields.clone();
+    let condition = condition.clone();
+    let prefix = prefix.to_string();
+    let branch = move || {
+        let base = Value::Object(fields_to_json(&fields));
+        condition
+            .active(&base)
+            .map(|branch| build_object_fields(branch, &prefix, depth, errors))
+    };
+
+    return view! {
+        {selector}
+        {branch}
+    }
+    .into_any();
+}
 
-        serde_json::Value::Object(map)
+fn schema_to_form_fields(
+    schema_json: &str,
+    form_state: &mut SchemaFormState,
+) -> Result<Vec<AnyView>, Error> {
+    let schema: Value = serde_json::from_str(schema_json)?;
+    let resolved = json_schema::resolve(&schema, &schema);
+    if !resolved["properties"].is_object() && !is_conditional(&resolved) {
+        return Err(ErrorDe::custom("Missing 'properties' field in schema"));
     }
+
+    *form_state = SchemaFormState::from_schema(Arc::new(schema));
+
+    return Ok(build_object_fields(
+        &form_state.object,
+        "",
+        0,
+        form_state.errors,
+    ));
 }
 
+// -------------------------------------------------------------------------------------------------
+// Component
+// -------------------------------------------------------------------------------------------------
+
 #[component]
 pub fn SchemaForm(
     schema_json: String,
     #[prop(optional)] _key: String,
     #[prop(optional)] form_state_out: Option<RwSignal<Option<SchemaFormState>>>,
+    /// Config to prefill the form with, values the schema does not know are reported and dropped.
+    #[prop(default = None)]
+    initial: Option<Value>,
 ) -> impl IntoView {
     // Use StoredValue to persist form state across re-renders
     let form_state = StoredValue::new(SchemaFormState::default());
+    let unmatched_message = RwSignal::new(None::<String>);
 
     let field_views = {
         let mut state = form_state.get_value();
@@ -237,6 +1029,19 @@ pub fn SchemaForm(
         };
         form_state.set_value(state.clone());
 
+        // a prefilled config is checked straight away so problems are highlighted on arrival
+        if let Some(initial) = &initial {
+            let unmatched = state.load_json(initial);
+            if !unmatched.is_empty() {
+                log::warn!("Config keys not in the schema: {:?}", unmatched);
+                unmatched_message.set(Some(format!(
+                    "Ignored values that do not match the schema: {}",
+                    unmatched.join(", ")
+                )));
+            }
+            state.validate();
+        }
+
         // Expose form state to parent if signal provided
         if let Some(signal) = form_state_out {
             signal.set(Some(state));
@@ -245,8 +1050,18 @@ pub fn SchemaForm(
         views
     };
 
+    // once validated, errors follow the values so they clear as fields are corrected
+    Effect::new(move |_| {
+        let state = form_state.get_value();
+        let value = state.to_json();
+        if state.errors.with_untracked(Option::is_some) {
+            state.check(&value);
+        }
+    });
+
     return view! {
         <Grid size={Size::Md} cols=1>
+            <WarningAlert message={unmatched_message.read_only()} />
             {field_views.into_iter().collect::<Vec<_>>()}
         </Grid>
     };
@@ -40,6 +40,8 @@ pub fn Section(
     #[prop(default = false)] is_first: bool,
     #[prop(default = false)] centre: bool,
     #[prop(default = false)] spaced: bool,
+    #[prop(default = false)] collapsible: bool,
+    #[prop(default = false)] collapsed: bool,
     level: SectionLevel,
     title: String,
     children: Children,
@@ -58,19 +60,42 @@ pub fn Section(
     let class_str = format!("mt-{} mb-{}", space, space);
     let align = if centre { Align::Center } else { Align::Left };
 
+    let is_open = RwSignal::new(!collapsed);
+    let title = match collapsible {
+        true => view! {
+            <span class="inline-block w-4">{move || if is_open.get() { "\u{25be}" } else { "\u{25b8}" }}</span>
+            {title}
+        }
+        .into_any(),
+        false => view! { {title} }.into_any(),
+    };
+
     let heading = match level {
-        SectionLevel::H1 => view! { <H1 align={align}>{title.clone()}</H1> }.into_any(),
-        SectionLevel::H2 => view! { <H2 align={align}>{title.clone()}</H2> }.into_any(),
-        SectionLevel::H3 => view! { <H3 align={align}>{title.clone()}</H3> }.into_any(),
-        SectionLevel::H4 => view! { <H4 align={align}>{title.clone()}</H4> }.into_any(),
+        SectionLevel::H1 => view! { <H1 align={align}>{title}</H1> }.into_any(),
+        SectionLevel::H2 => view! { <H2 align={align}>{title}</H2> }.into_any(),
+        SectionLevel::H3 => view! { <H3 align={align}>{title}</H3> }.into_any(),
+        SectionLevel::H4 => view! { <H4 align={align}>{title}</H4> }.into_any(),
+    };
+
+    // collapsed content stays mounted so its inputs keep their values
+    let heading = match collapsible {
+        true => view! {
+            <div class="cursor-pointer select-none" on:click=move |_| is_open.update(|open| *open = !*open)>
+                {heading}
+            </div>
+        }
+        .into_any(),
+        false => heading,
     };
 
     view! {
         <Stack size={if spaced { Size::Lg } else { Size::Sm }} add_class={class_str}>
             {heading}
-            <Stack>
-                {children()}
-            </Stack>
+            <div class:hidden=move || !is_open.get()>
+                <Stack>
+                    {children()}
+                </Stack>
+            </div>
         </Stack>
     }
 }
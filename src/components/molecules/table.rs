@@ -20,13 +20,24 @@
  * ------------------------------------------------------------------------------------------------
  */
 
-
-use leptos::prelude::*;
+use chrono::{DateTime, Utc};
+use leptos::{logging::warn, prelude::*, reactive::spawn_local};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::time::Duration;
+use uuid::Uuid;
 
+use crate::common::clipboard;
 use crate::common::size::*;
-use crate::components::atoms::layout::{spacing, Align, BorderColor, BorderedDiv};
-use crate::components::atoms::typography::{FONT_CLR, H3, H4_CLASS, NORMAL_CLASS};
+use crate::components::atoms::alert::AlertType;
+use crate::components::atoms::button::*;
+use crate::components::atoms::input::{checkbox_class, CheckBox, Text};
+use crate::components::atoms::layout::{
+    spacing, Align, BorderColor, BorderedDiv, FlexAlign, Stack,
+};
+use crate::components::atoms::typography::{A, FONT_CLR, H3, H4_CLASS, LINK_CLR, NORMAL_CLASS};
+use crate::components::molecules::drop_down::DropDown;
 
 // ------------------------------------------------------------------------------------------------
 //  Data Structs
@@ -49,7 +60,50 @@ pub struct TableStruct {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableData {
     pub col_def: Vec<ColumnDefinition>,
-    pub rows: Vec<Vec<String>>,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+/// Contents of a cell. Cells are sorted and filtered by their `text()`, timestamps by time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Cell {
+    Text(String),
+    /// A label coloured by e.g. a job or runner status.
+    Badge {
+        text: String,
+        tone: AlertType,
+    },
+    /// Text linking to another page, e.g. a job name to the job's detail view.
+    Link {
+        text: String,
+        href: String,
+    },
+    /// A point in time shown relative to now ("3 min ago"), `None` is shown as "Never".
+    Timestamp(Option<DateTime<Utc>>),
+    /// An id shown shortened, with a button copying the full id.
+    Uuid(Uuid),
+}
+
+impl Cell {
+    pub fn text(&self) -> String {
+        match self {
+            Cell::Text(text) | Cell::Badge { text, .. } | Cell::Link { text, .. } => text.clone(),
+            Cell::Timestamp(Some(time)) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            Cell::Timestamp(None) => "Never".to_string(),
+            Cell::Uuid(id) => id.to_string(),
+        }
+    }
+}
+
+impl From<String> for Cell {
+    fn from(text: String) -> Self {
+        Cell::Text(text)
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(text: &str) -> Self {
+        Cell::Text(text.to_string())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,10 +135,17 @@ fn TH(children: Children) -> impl IntoView {
 }
 
 #[component]
-fn TD(cell_type: CellType, children: Children) -> impl IntoView {
+fn TD(
+    cell_type: CellType,
+    #[prop(optional)] shown: Option<RwSignal<bool>>,
+    children: Children,
+) -> impl IntoView {
     return view! {
-        <td class=format!("{} {} {} {}", NORMAL_CLASS, FONT_CLR, cell_format(),
-                        if cell_type == CellType::Text {Align::Left} else {Align::Right})>
+        <td
+            class=format!("{} {} {} {}", NORMAL_CLASS, FONT_CLR, cell_format(),
+                        if cell_type == CellType::Text {Align::Left} else {Align::Right})
+            class:hidden=move || shown.is_some_and(|shown| !shown.get())
+        >
             {children()}
         </td>
     };
@@ -103,51 +164,497 @@ fn TR(children: Children) -> impl IntoView {
     };
 }
 
+/// `time` relative to `now`, e.g. "3 min ago" or "in 2 h".
+fn relative_time(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - time).num_seconds();
+    let (amount, unit) = match seconds.unsigned_abs() {
+        0..60 => return "just now".to_string(),
+        elapsed @ 60..3_600 => (elapsed / 60, "min"),
+        elapsed @ 3_600..86_400 => (elapsed / 3_600, "h"),
+        elapsed => (elapsed / 86_400, "d"),
+    };
+    match seconds >= 0 {
+        true => format!("{} {} ago", amount, unit),
+        false => format!("in {} {}", amount, unit),
+    }
+}
+
+/// First block of a UUID, enough to tell apart the ids in a table.
+fn short_uuid(id: &Uuid) -> String {
+    id.to_string().chars().take(8).collect()
+}
+
+fn cell_view(cell: &Cell, now: RwSignal<DateTime<Utc>>) -> AnyView {
+    match cell.clone() {
+        Cell::Text(text) => text.into_any(),
+        Cell::Badge { text, tone } => view! {
+            <span class=format!(
+                "px-2 rounded-full border whitespace-nowrap {} {} {}",
+                tone.background_color(),
+                tone.border_color(),
+                tone.text_color()
+            )>
+                {text}
+            </span>
+        }
+        .into_any(),
+        Cell::Link { text, href } => view! {
+            <A href=href text_class=NORMAL_CLASS.to_string()>{text}</A>
+        }
+        .into_any(),
+        Cell::Timestamp(Some(time)) => view! {
+            <span title=cell.text()>{move || relative_time(time, now.get())}</span>
+        }
+        .into_any(),
+        Cell::Timestamp(None) => cell.text().into_any(),
+        Cell::Uuid(id) => view! {
+            <Stack horizontal={true} align={FlexAlign::Center} size={Size::Xs}>
+                <span class="font-mono" title=id.to_string()>{short_uuid(&id)}</span>
+                <Button button_data={
+                    ButtonData::new()
+                        .size(Size::Xs)
+                        .variant(Variant::Tertiary)
+                        .text("Copy")
+                        .on_click(move || {
+                            spawn_local(async move {
+                                if let Err(e) = clipboard::write_text(&id.to_string()).await {
+                                    warn!("{}", e);
+                                }
+                            });
+                        })
+                } />
+            </Stack>
+        }
+        .into_any(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// Orders two cells of a column, timestamps by time and numeric columns by value with unparsable
+/// cells last.
+fn compare_cells(a: &Cell, b: &Cell, cell_type: CellType) -> Ordering {
+    if let (Cell::Timestamp(a), Cell::Timestamp(b)) = (a, b) {
+        return a.cmp(b);
+    }
+    let (a, b) = (a.text(), b.text());
+    let (a, b) = (a.as_str(), b.as_str());
+    match cell_type {
+        CellType::Text => a.to_lowercase().cmp(&b.to_lowercase()).then(a.cmp(b)),
+        CellType::Int | CellType::Float => match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.total_cmp(&b),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b),
+        },
+    }
+}
+
+/// Whether a cell passes its column's filter. Text is matched case-insensitively anywhere in the
+/// cell, numeric columns also accept a comparison such as `>= 10`.
+fn matches_filter(cell: &str, filter: &str, cell_type: CellType) -> bool {
+    let filter = filter.trim();
+    if filter.is_empty() {
+        return true;
+    }
+    if cell_type != CellType::Text {
+        let comparison = ["<=", ">=", "<", ">", "="]
+            .into_iter()
+            .find_map(|op| Some((op, filter.strip_prefix(op)?.trim().parse::<f64>().ok()?)));
+        if let Some((op, bound)) = comparison {
+            return cell.parse::<f64>().is_ok_and(|value| match op {
+                "<=" => value <= bound,
+                ">=" => value >= bound,
+                "<" => value < bound,
+                ">" => value > bound,
+                _ => value == bound,
+            });
+        }
+    }
+    cell.to_lowercase().contains(&filter.to_lowercase())
+}
+
+/// Indices of the rows passing every filter, in display order.
+fn visible_rows(
+    rows: &[Vec<Cell>],
+    col_defs: &[ColumnDefinition],
+    filters: &[String],
+    sort: Option<(usize, SortDirection)>,
+) -> Vec<usize> {
+    let mut indices = (0..rows.len())
+        .filter(|&index| {
+            rows[index]
+                .iter()
+                .zip(col_defs.iter().zip(filters))
+                .all(|(cell, (col_def, filter))| {
+                    matches_filter(&cell.text(), filter, col_def.data_type)
+                })
+        })
+        .collect::<Vec<usize>>();
+
+    if let Some((column, direction)) = sort {
+        let cell_type = col_defs[column].data_type;
+        let empty = Cell::Text(String::new());
+        let cell = |index: usize| rows[index].get(column).unwrap_or(&empty);
+        indices.sort_by(|&a, &b| {
+            let ordering = compare_cells(cell(a), cell(b), cell_type);
+            match direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        });
+    }
+    indices
+}
+
+/// `row_actions`, when given, builds the view (e.g. buttons) shown in a trailing column for the
+/// row at an index of `table.data.rows`.
+///
+/// An `interactive` table sorts on a click of a column header (again to reverse), filters each
+/// column, pages through its rows `page_size` at a time and lets columns be hidden.
+///
+/// Rows that change while the table is shown (e.g. a polled list) are passed as `rows`, which
+/// replaces `table.data.rows`. The table stays mounted as they change, keeping its sort, filters,
+/// page and column choice, and `row_actions` is called with indices of the current rows.
+///
+/// With a `selection`, a leading column of checkboxes adds and removes the keys of rows from it,
+/// e.g. for bulk operations. A row's key is its entry in `row_keys`, or its index in the rows
+/// without them.
 #[component]
-pub fn Table(table: TableStruct) -> impl IntoView {
+pub fn Table(
+    table: TableStruct,
+    #[prop(optional, into)] rows: Option<Signal<Vec<Vec<Cell>>>>,
+    #[prop(optional)] row_actions: Option<Callback<usize, AnyView>>,
+    #[prop(default = false)] interactive: bool,
+    #[prop(default = 25)] page_size: usize,
+    #[prop(optional)] selection: Option<RwSignal<HashSet<String>>>,
+    #[prop(optional, into)] row_keys: Option<Signal<Vec<String>>>,
+) -> impl IntoView {
     // title
     let title = if !table.name.is_empty() {
-        Some(view! {<H3 align=Align::Center>{table.name}</H3>})
+        Some(view! {<H3 align=Align::Center>{table.name.clone()}</H3>})
     } else {
         None
     };
 
-    // Headers and Data
-    let col_defs = table.data.col_def;
-    let rows = table.data.rows;
-    let column_headers: Vec<_> = col_defs
-        .clone()
-        .into_iter()
-        .map(|col_def| view! {<TH>{col_def.name.clone()}</TH>})
-        .collect();
-    let row_data: Vec<_> = rows
-        .into_iter()
-        .map(|row_cells| {
-            let cells: Vec<_> = row_cells
-                .into_iter()
-                .zip(col_defs.iter()) // Now col_defs is available
-                .map(
-                    |(col_data, col_def)| view! {<TD cell_type={col_def.data_type}>{col_data}</TD>},
-                )
-                .collect();
-            view! {<TR>{cells}</TR>}
+    let row_key = move |index: usize| {
+        row_keys
+            .and_then(|keys| keys.with_untracked(|keys| keys.get(index).cloned()))
+            .unwrap_or_else(|| index.to_string())
+    };
+    let col_defs = StoredValue::new(table.data.col_def);
+    let rows = rows.unwrap_or_else(|| Signal::stored(table.data.rows));
+    let column_count = col_defs.with_value(|col_defs| col_defs.len());
+    let page_size = match interactive {
+        true => page_size.max(1),
+        false => usize::MAX,
+    };
+
+    let sort = RwSignal::new(None::<(usize, SortDirection)>);
+    let filters = (0..column_count)
+        .map(|_| RwSignal::new(String::new()))
+        .collect::<Vec<_>>();
+    let shown = (0..column_count)
+        .map(|_| RwSignal::new(true))
+        .collect::<Vec<_>>();
+    let page = RwSignal::new(0usize);
+
+    let filtered = Memo::new({
+        let filters = filters.clone();
+        move |_| {
+            let filters = filters
+                .iter()
+                .map(|filter| filter.get())
+                .collect::<Vec<_>>();
+            rows.with(|rows| {
+                col_defs.with_value(|col_defs| visible_rows(rows, col_defs, &filters, sort.get()))
+            })
+        }
+    });
+    // relative timestamps are kept current
+    let now = RwSignal::new(Utc::now());
+    let has_timestamps = move || {
+        rows.with_untracked(|rows| {
+            rows.iter()
+                .flatten()
+                .any(|cell| matches!(cell, Cell::Timestamp(Some(_))))
         })
-        .collect();
+    };
+    if let Ok(handle) = set_interval_with_handle(
+        move || {
+            if has_timestamps() {
+                now.set(Utc::now());
+            }
+        },
+        Duration::from_secs(30),
+    ) {
+        on_cleanup(move || handle.clear());
+    }
+
+    let page_count = move || filtered.with(|indices| indices.len().div_ceil(page_size).max(1));
+    // a narrower filter may leave the current page past the end
+    Effect::new(move |_| {
+        let last_page = page_count() - 1;
+        if page.get_untracked() > last_page {
+            page.set(last_page);
+        }
+    });
+
+    // Headers
+    let key_prefix = table
+        .name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    let column_headers = col_defs.with_value(|col_defs| {
+        col_defs
+            .iter()
+            .enumerate()
+            .map(|(column, col_def)| {
+                let name = col_def.name.clone();
+                let shown = shown[column];
+                let indicator = move || match sort.get() {
+                    Some((sorted, SortDirection::Ascending)) if sorted == column => " \u{25b4}",
+                    Some((sorted, SortDirection::Descending)) if sorted == column => " \u{25be}",
+                    _ => "",
+                };
+                let on_click = move |_| {
+                    if interactive {
+                        sort.update(|sort| {
+                            *sort = match *sort {
+                                Some((sorted, SortDirection::Ascending)) if sorted == column => {
+                                    Some((column, SortDirection::Descending))
+                                }
+                                _ => Some((column, SortDirection::Ascending)),
+                            }
+                        });
+                    }
+                };
+                view! {
+                    <th
+                        class=format!("{} {} {} {}", H4_CLASS, FONT_CLR, cell_format(), Align::Left)
+                        class:cursor-pointer=interactive
+                        class:hidden=move || !shown.get()
+                        on:click=on_click
+                    >
+                        {name}{indicator}
+                    </th>
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+    let action_header = row_actions.as_ref().map(|_| view! {<TH>"Actions"</TH>});
+    // the header checkbox selects every row passing the filters, not just the current page
+    let select_header = selection.map(|selection| {
+        let all_selected = move || {
+            filtered.with(|indices| {
+                !indices.is_empty()
+                    && selection.with(|selected| {
+                        indices
+                            .iter()
+                            .all(|&index| selected.contains(&row_key(index)))
+                    })
+            })
+        };
+        let on_change = move |ev: leptos::ev::Event| {
+            let checked = event_target_checked(&ev);
+            let keys = filtered.with(|indices| {
+                indices
+                    .iter()
+                    .map(|&index| row_key(index))
+                    .collect::<Vec<String>>()
+            });
+            selection.update(|selected| match checked {
+                true => selected.extend(keys),
+                false => keys.iter().for_each(|key| {
+                    selected.remove(key);
+                }),
+            });
+        };
+        view! {
+            <th class=cell_format()>
+                <input
+                    class={checkbox_class()}
+                    type="checkbox"
+                    prop:checked=all_selected
+                    on:change=on_change
+                />
+            </th>
+        }
+    });
+
+    let filter_row = interactive.then(|| {
+        let cells = filters
+            .iter()
+            .enumerate()
+            .map(|(column, filter)| {
+                let shown = shown[column];
+                view! {
+                    <td class=cell_format() class:hidden=move || !shown.get()>
+                        <Text
+                            id={format!("{}-filter-{}", key_prefix, column)}
+                            key={format!("{}_filter_{}", key_prefix, column)}
+                            placeholder={Some("Filter".to_string())}
+                            signal={*filter}
+                        />
+                    </td>
+                }
+            })
+            .collect::<Vec<_>>();
+        let select_cell = selection.map(|_| view! {<td class=cell_format()></td>});
+        let action_cell = row_actions
+            .as_ref()
+            .map(|_| view! {<td class=cell_format()></td>});
+        view! {<tr class="bg-surface-100 dark:bg-surface-900">{select_cell}{cells}{action_cell}</tr>}
+    });
+
+    // Data
+    let shown_columns = shown.clone();
+    let row_data = move || {
+        let start = page.get().saturating_mul(page_size);
+        let indices = filtered.with(|indices| {
+            indices
+                .iter()
+                .skip(start)
+                .take(page_size)
+                .copied()
+                .collect::<Vec<usize>>()
+        });
+        indices
+            .into_iter()
+            .map(|index| {
+                // tracked, as updated rows may keep the same order
+                let cells = rows.with(|rows| {
+                    col_defs.with_value(|col_defs| {
+                        rows[index]
+                            .iter()
+                            .zip(col_defs.iter().zip(&shown_columns))
+                            .map(|(col_data, (col_def, shown))| {
+                                let shown = *shown;
+                                let content = cell_view(col_data, now);
+                                view! {
+                                    <TD cell_type={col_def.data_type} shown={shown}>
+                                        {content}
+                                    </TD>
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                });
+                let select = selection.map(|selection| {
+                    let key = row_key(index);
+                    let checked = {
+                        let key = key.clone();
+                        move || selection.with(|selected| selected.contains(&key))
+                    };
+                    let on_change = move |ev: leptos::ev::Event| {
+                        let checked = event_target_checked(&ev);
+                        selection.update(|selected| match checked {
+                            true => {
+                                selected.insert(key.clone());
+                            }
+                            false => {
+                                selected.remove(&key);
+                            }
+                        });
+                    };
+                    view! {
+                        <td class=cell_format()>
+                            <input
+                                class={checkbox_class()}
+                                type="checkbox"
+                                prop:checked=checked
+                                on:change=on_change
+                            />
+                        </td>
+                    }
+                });
+                let actions = row_actions
+                    .map(|actions| view! {<td class=cell_format()>{actions.run(index)}</td>});
+                view! {<TR>{select}{cells}{actions}</TR>}
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Column chooser and pagination
+    let column_chooser = interactive.then(|| {
+        let options = col_defs.with_value(|col_defs| {
+            col_defs
+                .iter()
+                .enumerate()
+                .map(|(column, col_def)| {
+                    view! {
+                        <Stack horizontal={true} align={FlexAlign::Center} size={Size::Xs}>
+                            <CheckBox
+                                id={format!("{}-column-{}", key_prefix, column)}
+                                key={format!("{}_column_{}", key_prefix, column)}
+                                signal={shown[column]}
+                            />
+                            <span class=format!("{} {}", NORMAL_CLASS, FONT_CLR)>{col_def.name.clone()}</span>
+                        </Stack>
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+        view! {
+            <DropDown trigger={view! {<span class=format!("{} {}", NORMAL_CLASS, LINK_CLR)>"Columns"</span>}}>
+                {options}
+            </DropDown>
+        }
+    });
+    let pagination = interactive.then(|| {
+        view! {
+            <Stack horizontal={true} align={FlexAlign::Center} size={Size::Sm}>
+                <Button button_data={
+                    ButtonData::new()
+                        .size(Size::Sm)
+                        .variant(Variant::Secondary)
+                        .text("Previous")
+                        .on_click(move || page.update(|page| *page = page.saturating_sub(1)))
+                } />
+                <span class=format!("{} {}", NORMAL_CLASS, FONT_CLR)>
+                    {move || format!(
+                        "Page {} of {} ({} rows)",
+                        page.get() + 1,
+                        page_count(),
+                        filtered.with(Vec::len)
+                    )}
+                </span>
+                <Button button_data={
+                    ButtonData::new()
+                        .size(Size::Sm)
+                        .variant(Variant::Secondary)
+                        .text("Next")
+                        .on_click(move || page.update(|page| *page = (*page + 1).min(page_count() - 1)))
+                } />
+            </Stack>
+        }
+    });
 
     return view! {
 
         {title}
+        {column_chooser}
         <BorderedDiv>
             <table class="w-full border-collapse">
                 <thead>
                     <tr class="bg-surface-200 dark:bg-surface-800">
+                        {select_header}
                         {column_headers}
+                        {action_header}
                     </tr>
+                    {filter_row}
                 </thead>
                 <tbody>
                     {row_data}
                 </tbody>
             </table>
         </BorderedDiv>
+        {pagination}
     };
 }
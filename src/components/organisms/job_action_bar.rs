@@ -0,0 +1,187 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: job_action_bar.rs
+ * description: Cancel, pause, resume and resubmit buttons for a single job
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::{prelude::*, reactive::spawn_local};
+use uuid::Uuid;
+
+use crate::application::job_service::JobService;
+use crate::common::size::*;
+use crate::components::atoms::alert::*;
+use crate::components::atoms::button::*;
+use crate::components::atoms::layout::*;
+use crate::components::atoms::typography::*;
+use crate::components::molecules::confirm_dialog::*;
+use crate::components::molecules::form_field::*;
+use crate::domain::job_context::*;
+use crate::domain::runner_info::RunnerInfo;
+
+fn action_variant(action: JobAction) -> Variant {
+    match action {
+        JobAction::Cancel => Variant::Secondary,
+        JobAction::Pause | JobAction::Resume | JobAction::Resubmit => Variant::Primary,
+    }
+}
+
+/// Only the actions the domain allows for the job's current status are offered. `on_applied`
+/// receives the job the action produced, the patched job or the resubmitted clone. `on_deleted`
+/// receives the id of a job once it is deleted.
+#[component]
+pub fn JobActionBar(
+    job: JobInfo,
+    runner: Option<RunnerInfo>,
+    #[prop(optional)] on_applied: Option<Callback<JobInfo>>,
+    #[prop(default = None)] on_deleted: Option<Callback<Uuid>>,
+    #[prop(default = Size::Sm)] size: Size,
+) -> impl IntoView {
+    let job_service = JobService::new();
+    let (error, set_error) = signal(None::<String>);
+    let (resubmitted, set_resubmitted) = signal(None::<JobInfo>);
+    let busy = RwSignal::new(false);
+
+    let buttons = job
+        .available_actions(runner.as_ref())
+        .into_iter()
+        .map(|action| {
+            let job = job.clone();
+            let job_service = job_service.clone();
+            let button_data = ButtonData::new()
+                .size(size)
+                .variant(action_variant(action))
+                .text(&action.to_string());
+            let state_signal = button_data.state_signal;
+
+            let button_data = button_data.on_click(move || {
+                if busy.get_untracked() {
+                    return;
+                }
+                busy.set(true);
+                state_signal.set(State::Loading);
+
+                let job = job.clone();
+                let job_service = job_service.clone();
+                spawn_local(async move {
+                    match job_service.apply_action(&job, action).await {
+                        Ok(updated) => {
+                            state_signal.set(State::Default);
+                            set_error.set(None);
+                            if action == JobAction::Resubmit {
+                                set_resubmitted.set(Some(updated.clone()));
+                            }
+                            if let Some(on_applied) = on_applied {
+                                on_applied.run(updated);
+                            }
+                        }
+                        Err(e) => {
+                            leptos::logging::error!("{} failed for job {}: {}", action, job.id, e);
+                            state_signal.set(State::Error);
+                            set_error.set(Some(e.to_string()));
+                        }
+                    }
+                    busy.set(false);
+                });
+            });
+            view! { <Button button_data=button_data /> }
+        })
+        .collect::<Vec<_>>();
+
+    // deleting asks first, and whether to download the job's results before they are gone
+    let delete_open = RwSignal::new(false);
+    let keep_results = RwSignal::new(false);
+    let delete_button = job.can_delete().then(|| {
+        let button_data = ButtonData::new()
+            .size(size)
+            .variant(Variant::Secondary)
+            .text("Delete");
+        let state_signal = button_data.state_signal;
+        let button_data = button_data.on_click(move || {
+            if !busy.get_untracked() {
+                delete_open.set(true);
+            }
+        });
+
+        let (job_id, job_name) = (job.id, job.name.clone());
+        let job = job.clone();
+        let on_confirm = Callback::new(move |_| {
+            busy.set(true);
+            state_signal.set(State::Loading);
+            let job = job.clone();
+            let job_service = job_service.clone();
+            spawn_local(async move {
+                match job_service
+                    .delete_job(&job, keep_results.get_untracked())
+                    .await
+                {
+                    Ok(()) => {
+                        state_signal.set(State::Default);
+                        set_error.set(None);
+                        if let Some(on_deleted) = on_deleted {
+                            on_deleted.run(job.id);
+                        }
+                    }
+                    Err(e) => {
+                        leptos::logging::error!("Deleting job {} failed: {}", job.id, e);
+                        state_signal.set(State::Error);
+                        set_error.set(Some(e.to_string()));
+                    }
+                }
+                busy.set(false);
+            });
+        });
+
+        view! {
+            <Button button_data=button_data />
+            <ConfirmDialog
+                open=delete_open
+                title=format!("Delete {}", job_name)
+                confirm_text="Delete".to_string()
+                on_confirm=on_confirm
+            >
+                <P>
+                    {move || match keep_results.get() {
+                        true => "The job's output and result files are downloaded, then the job and all of its files are deleted.",
+                        false => "The job and all of its files are deleted, this cannot be undone.",
+                    }}
+                </P>
+                <FormField
+                    label="Download results before deleting".to_string()
+                    key=format!("keep_results_{}", job_id)
+                    input_type=InputType::CheckBox { signal: keep_results }
+                />
+            </ConfirmDialog>
+        }
+    });
+
+    return view! {
+        <Stack size=Size::Xs>
+            <Stack horizontal=true size=Size::Sm>
+                {buttons}
+                {delete_button}
+            </Stack>
+            {move || resubmitted.get().map(|new_job| view! {
+                <A href=format!("/jobs/{}", new_job.id) text_class=NORMAL_CLASS.to_string()>
+                    {format!("Resubmitted as job {}", new_job.id)}
+                </A>
+            })}
+            <ErrorAlert message=error size=Size::Sm />
+        </Stack>
+    };
+}
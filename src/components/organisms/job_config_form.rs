@@ -20,24 +20,37 @@
  * ------------------------------------------------------------------------------------------------
  */
 use leptos::{prelude::*, reactive::spawn_local};
-use serde_json::json;
+use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::time::Duration;
 use uuid::Uuid;
 
+use crate::application::job_service::{InputUpload, JobService};
+use crate::common::browser_download::save_blob;
+use crate::common::browser_file::read_text;
+use crate::common::local_storage;
 use crate::common::size::*;
 use crate::components::atoms::alert::*;
 use crate::components::atoms::button::*;
+use crate::components::atoms::input::File;
 use crate::components::atoms::layout::*;
 use crate::components::atoms::typography::*;
+use crate::components::molecules::file_drop_zone::FileDropZone;
 use crate::components::molecules::form_field::*;
 use crate::components::molecules::schema_form::{SchemaForm, SchemaFormState};
 use crate::components::molecules::section::*;
+use crate::components::organisms::job_preset_bar::JobPresetBar;
+use crate::components::organisms::parameter_sweep_panel::ParameterSweepPanel;
 use crate::domain::application_info::AppInfo;
+use crate::domain::command_line::build_args;
+use crate::domain::job_config::JobConfig;
 use crate::domain::job_context::*;
+use crate::domain::parameter_sweep::*;
 use crate::domain::runner_info::RunnerInfo;
 use crate::domain::user_context::UserContext;
 use crate::infrastructure::fyn_api_client::FynApiClient;
+use crate::infrastructure::fyn_api_error::FynApiError;
 
 // -------------------------------------------------------------------------------------------------
 // Support Functions
@@ -51,7 +64,7 @@ fn get_application_list() -> LocalResource<Option<Vec<(String, String)>>> {
             let user_context =
                 use_context::<RwSignal<Option<UserContext>>>().expect("user should be provided");
             if user_context.get().is_some() && user_context.get().unwrap().apps.len() == 0 {
-                let app_info: Option<HashMap<Uuid, AppInfo>> =
+                let app_info: Result<HashMap<Uuid, AppInfo>, FynApiError> =
                     fyn_api_client.get_applications().await;
                 let user_cont = user_context.get();
                 match user_cont {
@@ -106,8 +119,8 @@ fn get_application_schema(application_id: RwSignal<String>) -> LocalResource<Opt
                                 // need to fetch new value
                                 let new_schema =
                                     match fyn_api_client.get_app_schema(selected_app_id).await {
-                                        Some(schema) => schema,
-                                        None => serde_json::Value::Null,
+                                        Ok(schema) => schema,
+                                        Err(_) => serde_json::Value::Null,
                                     };
 
                                 // Update the user context with the new schema
@@ -130,6 +143,194 @@ fn get_application_schema(application_id: RwSignal<String>) -> LocalResource<Opt
     })
 }
 
+/// File name for an exported config, derived from the job name.
+fn config_file_name(job_name: &str) -> String {
+    let stem = job_name
+        .trim()
+        .chars()
+        .map(
+            |c| match c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                true => c,
+                false => '_',
+            },
+        )
+        .collect::<String>();
+    match stem.is_empty() {
+        true => "job_config.json".to_string(),
+        false => format!("{}.json", stem),
+    }
+}
+
+/// Submits one job per case of the sweep, each with its own config and a copy of the inputs,
+/// named after the batch.
+/// `progress` follows the submission. Returns how many jobs were submitted.
+async fn submit_sweep(
+    job_service: &JobService,
+    sweep: &ParameterSweep,
+    form_state: Option<SchemaFormState>,
+    batch: String,
+    job_request: impl Fn(String, &Value) -> Result<JobInfo, String>,
+    inputs: &[InputUpload],
+    progress: RwSignal<String>,
+) -> Result<usize, String> {
+    let batch = batch.trim();
+    if batch.is_empty() {
+        return Err("Enter a job name, it names the jobs of the sweep".to_string());
+    }
+    let form_state = form_state.ok_or("The application has no config to sweep".to_string())?;
+    let base_config = form_state.to_json();
+    let cases = sweep.cases()?;
+
+    // every case is checked before anything is submitted
+    let configs = cases
+        .iter()
+        .map(|case| case.apply(&base_config))
+        .collect::<Vec<Value>>();
+    let invalid = configs
+        .iter()
+        .enumerate()
+        .filter(|(_, config)| !form_state.validate_config(config).is_empty())
+        .map(|(index, _)| (index + 1).to_string())
+        .collect::<Vec<String>>();
+    if !invalid.is_empty() {
+        return Err(format!(
+            "Sweep jobs {} do not satisfy the schema, adjust the swept values",
+            invalid.join(", ")
+        ));
+    }
+
+    let mut failures = Vec::new();
+    for (index, (case, config)) in cases.iter().zip(&configs).enumerate() {
+        progress.set(format!("Submitting {}/{}", index + 1, cases.len()));
+        let name = batch_job_name(batch, index, cases.len(), &case.label());
+        let result = match job_request(name.clone(), config) {
+            Ok(job) => job_service
+                .submit_with_config(&job, Some(config), inputs)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            leptos::logging::error!("Failed to submit {}: {}", name, e);
+            failures.push(e);
+        }
+    }
+
+    match failures.is_empty() {
+        true => Ok(cases.len()),
+        false => Err(format!(
+            "{} of {} jobs failed: {}",
+            failures.len(),
+            cases.len(),
+            failures.join("; ")
+        )),
+    }
+}
+
+/// Arguments as they would be typed, those containing spaces are quoted.
+fn args_display(args: &Value) -> String {
+    args.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|arg| arg.as_str())
+        .map(
+            |arg| match arg.is_empty() || arg.contains(char::is_whitespace) {
+                true => format!("\"{}\"", arg),
+                false => arg.to_string(),
+            },
+        )
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn file_size_text(bytes: f64) -> String {
+    match bytes {
+        b if b >= 1e9 => format!("{:.1} GB", b / 1e9),
+        b if b >= 1e6 => format!("{:.1} MB", b / 1e6),
+        b if b >= 1e3 => format!("{:.1} kB", b / 1e3),
+        b => format!("{} B", b),
+    }
+}
+
+/// A file waiting to be uploaded with the job: its description, upload progress and a way to
+/// remove it again.
+fn build_input_upload_row(
+    index: usize,
+    upload: &InputUpload,
+    uploads: RwSignal<Vec<InputUpload>, LocalStorage>,
+) -> impl IntoView {
+    let progress = upload.progress;
+    let progress_text = move || match progress.get() {
+        Some(fraction) if fraction >= 1.0 => "Uploaded".to_string(),
+        Some(fraction) => format!("{:.0}%", fraction * 100.0),
+        None => String::new(),
+    };
+
+    return view! {
+        <Stack horizontal={true} align={FlexAlign::Center}>
+            <P>{format!("{} ({})", upload.file.name(), file_size_text(upload.file.size()))}</P>
+            <FormField
+                label={"Description".to_string()}
+                key={format!("input_description_{}", index)}
+                placeholder={"e.g. volume mesh".to_string()}
+                input_type=InputType::Text { signal: upload.description }
+            />
+            <P>{progress_text}</P>
+            <Button button_data={
+                ButtonData::new()
+                    .size(Size::Sm)
+                    .variant(Variant::Secondary)
+                    .text("Remove")
+                    .on_click(move || {
+                        uploads.update(|uploads| {
+                            if index < uploads.len() {
+                                uploads.remove(index);
+                            }
+                        })
+                    })
+            } />
+        </Stack>
+    };
+}
+
+/// How long the form has to be left alone before a draft is written.
+const DRAFT_DEBOUNCE: Duration = Duration::from_millis(1000);
+
+fn draft_key(application_id: &str) -> String {
+    format!("job_draft:{}", application_id)
+}
+
+/// The draft saved for an application, unreadable drafts are discarded.
+fn load_draft(application_id: &str) -> Option<JobConfig> {
+    let key = draft_key(application_id);
+    let text = local_storage::get_item(&key)?;
+    match JobConfig::from_json_str(&text) {
+        Ok(draft) => Some(draft),
+        Err(e) => {
+            leptos::logging::warn!("Discarding unreadable draft {}: {}", key, e);
+            local_storage::remove_item(&key);
+            None
+        }
+    }
+}
+
+/// Loads an imported config into a form that is already showing, describing anything that did
+/// not carry over.
+fn load_imported_config(form_state: &SchemaFormState, config: &Value) -> Option<String> {
+    let unmatched = form_state.load_json(config);
+    let is_valid = form_state.validate();
+    match (unmatched.is_empty(), is_valid) {
+        (true, true) => None,
+        (true, false) => {
+            Some("The imported config has errors, see the highlighted fields".to_string())
+        }
+        (false, _) => Some(format!(
+            "Ignored values that do not match the schema: {}",
+            unmatched.join(", ")
+        )),
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 // Component
 // -------------------------------------------------------------------------------------------------
@@ -147,15 +348,221 @@ pub fn JobConfigForm(runner_list: Option<HashMap<Uuid, RunnerInfo>>) -> impl Int
     // Signal to receive form data from SchemaForm
     let schema_form_state: RwSignal<Option<SchemaFormState>> = RwSignal::new(None);
 
+    // config import and export
+    let user_context =
+        use_context::<RwSignal<Option<UserContext>>>().expect("UserContext should be provided.");
+    let import_file = RwSignal::new(String::new());
+    let import_message = RwSignal::new(None::<String>);
+    let pending_import: RwSignal<Option<Value>> = RwSignal::new(None);
+
+    // drafts, saved per application while the form is edited
+    let draft_app = StoredValue::new(String::new());
+    let draft_prompt: RwSignal<Option<JobConfig>> = RwSignal::new(None);
+    let draft_message = RwSignal::new(None::<String>);
+    let draft_timeout = StoredValue::new(None::<TimeoutHandle>);
+    let skip_draft_prompt = StoredValue::new(false);
+
+    Effect::new(move |previous_app: Option<String>| {
+        let Some(form_state) = schema_form_state.get() else {
+            return String::new();
+        };
+        let config = form_state.to_json();
+        let name = job_name.get();
+        let priority = job_priority.get().unwrap_or(0);
+        let app_id = draft_app.get_value();
+
+        // a fresh form offers the stored draft instead of overwriting it
+        if previous_app.as_ref() != Some(&app_id) {
+            draft_message.set(None);
+            let skip = skip_draft_prompt.get_value();
+            skip_draft_prompt.set_value(false);
+            draft_prompt.set(match skip {
+                true => None,
+                false => load_draft(&app_id),
+            });
+            return app_id;
+        }
+        if draft_prompt.with_untracked(Option::is_some) {
+            return app_id;
+        }
+        let Ok(app_uuid) = Uuid::from_str(&app_id) else {
+            return app_id;
+        };
+
+        if let Some(handle) = draft_timeout.get_value() {
+            handle.clear();
+        }
+        let draft = JobConfig::new(app_uuid, name, priority, config);
+        let handle = set_timeout_with_handle(
+            move || {
+                let key = draft_key(&draft.application_id.to_string());
+                if let Err(e) = local_storage::set_item(&key, &draft.to_json().to_string()) {
+                    leptos::logging::warn!("Failed to save draft: {}", e);
+                }
+            },
+            DRAFT_DEBOUNCE,
+        );
+        draft_timeout.set_value(handle.ok());
+        app_id
+    });
+
+    let on_restore_draft_click = move || {
+        let Some(draft) = draft_prompt.get_untracked() else {
+            return;
+        };
+        draft_prompt.set(None);
+        job_name.set(draft.name);
+        job_priority.set(Some(draft.priority));
+        if let Some(form_state) = schema_form_state.get_untracked() {
+            let unmatched = form_state.load_json(&draft.config);
+            if !unmatched.is_empty() {
+                draft_message.set(Some(format!(
+                    "The application has changed since the draft was saved, dropped: {}",
+                    unmatched.join(", ")
+                )));
+            }
+        }
+    };
+
+    let on_discard_draft_click = move || {
+        draft_prompt.set(None);
+        local_storage::remove_item(&draft_key(&draft_app.get_value()));
+    };
+
+    let on_export_click = move || {
+        error_message.set(None);
+        let Ok(app_uuid) = Uuid::from_str(&application_id.get_untracked()) else {
+            error_message.set(Some(
+                "Select an application to export its config".to_string(),
+            ));
+            return;
+        };
+        let config = schema_form_state
+            .get_untracked()
+            .map(|form_state| form_state.to_json())
+            .unwrap_or(json!({}));
+        let job_config = JobConfig::new(
+            app_uuid,
+            job_name.get_untracked(),
+            job_priority.get_untracked().unwrap_or(0),
+            config,
+        );
+        let file_name = config_file_name(&job_config.name);
+        if let Err(e) = FynApiClient::create_json_file(&job_config.to_json(), &file_name)
+            .and_then(|file| save_blob(&file, &file_name))
+        {
+            error_message.set(Some(format!("Failed to export config: {}", e)));
+        }
+    };
+
+    let on_import = Callback::new(move |files: Vec<web_sys::File>| {
+        let Some(file) = files.into_iter().next() else {
+            return;
+        };
+        spawn_local(async move {
+            import_message.set(None);
+            let job_config = match read_text(&file)
+                .await
+                .and_then(|text| JobConfig::from_json_str(&text))
+            {
+                Ok(job_config) => job_config,
+                Err(e) => {
+                    import_message.set(Some(e));
+                    return;
+                }
+            };
+
+            let app_known = user_context.with_untracked(|user| {
+                user.as_ref()
+                    .is_some_and(|user| user.apps.contains_key(&job_config.application_id))
+            });
+            if !app_known {
+                import_message.set(Some(format!(
+                    "The config is for an application that is not available ({})",
+                    job_config.application_id
+                )));
+                return;
+            }
+
+            job_name.set(job_config.name.clone());
+            job_priority.set(Some(job_config.priority));
+
+            // the same application keeps its form, otherwise the config waits for the new schema
+            let app_id = job_config.application_id.to_string();
+            if application_id.get_untracked() == app_id {
+                if let Some(form_state) = schema_form_state.get_untracked() {
+                    draft_prompt.set(None);
+                    import_message.set(load_imported_config(&form_state, &job_config.config));
+                    return;
+                }
+            }
+            skip_draft_prompt.set_value(true);
+            pending_import.set(Some(job_config.config));
+            application_id.set(app_id);
+        });
+    });
+
     // Clone for closure
     let runner_list_clone = runner_list.clone();
 
+    // Executable and command line, the arguments may refer to config values as `{{path}}`
+    let executable = RwSignal::new(String::new());
+    let args_text = RwSignal::new(String::new());
+    let selected_app = Signal::derive(move || {
+        let app_id = Uuid::from_str(&application_id.get()).ok()?;
+        user_context.with(|user| user.as_ref()?.apps.get(&app_id).cloned())
+    });
+    // only a change of application (or its schema arriving) resets the command line
+    Effect::new(move |previous: Option<(Vec<String>, String)>| {
+        let current = selected_app.with(|app| {
+            app.as_ref()
+                .map(|app| (app.entry_points(), app.default_args()))
+                .unwrap_or_default()
+        });
+        if previous.as_ref() != Some(&current) {
+            executable.set(current.0.first().cloned().unwrap_or_default());
+            args_text.set(current.1.clone());
+        }
+        current
+    });
+    let args_preview = Memo::new(move |_| {
+        let config = schema_form_state
+            .with(|state| state.as_ref().map(|state| state.to_json()))
+            .unwrap_or(json!({}));
+        args_text.with(|text| build_args(text, &config))
+    });
+
+    // Input files uploaded with the job, e.g. meshes and geometry
+    let input_uploads: RwSignal<Vec<InputUpload>, LocalStorage> = RwSignal::new_local(Vec::new());
+    let on_input_files = Callback::new(move |files: Vec<web_sys::File>| {
+        input_uploads.update(|uploads| {
+            uploads.extend(files.into_iter().map(|file| InputUpload {
+                file,
+                description: RwSignal::new(String::new()),
+                progress: RwSignal::new(None),
+            }))
+        });
+    });
+
+    // Parameter sweep, submitted as a batch of jobs instead of a single one
+    let sweep: RwSignal<Result<ParameterSweep, String>> =
+        RwSignal::new(Ok(ParameterSweep::default()));
+
     // Button state signal
     let button_state_signal = RwSignal::new(State::Default);
     let button_text_signal = RwSignal::new("Submit".to_string());
+    Effect::new(move |_| {
+        let case_count = sweep.with(|sweep| match sweep {
+            Ok(sweep) if !sweep.is_empty() => sweep.cases().map(|cases| cases.len()).ok(),
+            _ => None,
+        });
+        button_text_signal.set(match case_count {
+            Some(count) => format!("Submit {} jobs", count),
+            None => "Submit".to_string(),
+        });
+    });
     let on_submit_click = move || {
-        let fyn_api_client =
-            use_context::<FynApiClient>().expect("FynApiClient should be provided");
+        let job_service = JobService::new();
         let cl_button_state_signal = button_state_signal.clone();
         let cl_button_text_signal = button_text_signal.clone();
 
@@ -168,6 +575,17 @@ pub fn JobConfigForm(runner_list: Option<HashMap<Uuid, RunnerInfo>>) -> impl Int
             let error_signal = error_message.clone();
             error_signal.set(None);
 
+            if let Some(form_state) = schema_form_state.get() {
+                if !form_state.validate() {
+                    error_signal.set(Some(
+                        "The configuration is invalid, please correct the highlighted fields"
+                            .to_string(),
+                    ));
+                    cl_button_state_signal.set(State::Error);
+                    return;
+                }
+            }
+
             let runner_uuid = match parse_uuid(&runner_id.get(), "runner UUID") {
                 Ok(id) => id,
                 Err(e) => {
@@ -186,87 +604,96 @@ pub fn JobConfigForm(runner_list: Option<HashMap<Uuid, RunnerInfo>>) -> impl Int
                 }
             };
 
-            let new_job_request = match JobInfo::new()
-                .name(job_name.get())
-                .application_id(app_uuid)
-                .runner_id(runner_uuid)
-                .priority(job_priority.get().unwrap_or(0))
-                .executable("executable")
-                .command_line_args(&json!(["arg1", "arg2", "arg3"]))
-                .build()
-            {
-                Ok(job) => job,
-                Err(e) => {
-                    error_signal.set(Some(format!("Failed to create job: {:?}", e)));
-                    cl_button_state_signal.set(State::Error);
-                    return;
+            let job_request = |name: String, config: &Value| {
+                let executable = executable.get_untracked();
+                if executable.is_empty() {
+                    return Err("Select the executable to run".to_string());
                 }
+                JobInfo::new()
+                    .name(name)
+                    .application_id(app_uuid)
+                    .runner_id(runner_uuid)
+                    .priority(job_priority.get_untracked().unwrap_or(0))
+                    .executable(executable)
+                    .command_line_args(&build_args(&args_text.get_untracked(), config)?)
+                    .build()
             };
 
-            let mut created_job = match fyn_api_client.submit_new_job(&new_job_request).await {
-                Ok(job) => {
-                    leptos::logging::log!("Job created: {}", job.id);
-                    job
-                }
+            let sweep = match sweep.get_untracked() {
+                Ok(sweep) => sweep,
                 Err(e) => {
-                    error_signal.set(Some(format!("Failed to submit new job: {}", e)));
+                    error_signal.set(Some(format!("Invalid parameter sweep: {}", e)));
                     cl_button_state_signal.set(State::Error);
                     return;
                 }
             };
-
-            // Upload config file if we have form data
-            if let Some(form_state) = schema_form_state.get() {
-                let config_json = form_state.to_json();
-                leptos::logging::log!("Creating config file from form data");
-
-                match FynApiClient::create_json_file(&config_json, "config_file.json") {
-                    Ok(config_file) => {
-                        leptos::logging::log!("Uploading config file for job {}", created_job.id);
-
-                        match fyn_api_client
-                            .upload_job_resource_file(
-                                created_job.id,
-                                config_file,
-                                "CFG", // CONFIG resource type
-                                Some("Application configuration file"),
-                            )
-                            .await
-                        {
-                            Ok(_) => {
-                                leptos::logging::log!("Config file uploaded successfully");
-                            }
-                            Err(e) => {
-                                error_signal
-                                    .set(Some(format!("Failed to upload config file: {}", e)));
-                                cl_button_state_signal.set(State::Error);
-                            }
+            if !sweep.is_empty() {
+                match submit_sweep(
+                    &job_service,
+                    &sweep,
+                    schema_form_state.get_untracked(),
+                    job_name.get_untracked(),
+                    &job_request,
+                    &input_uploads.get_untracked(),
+                    cl_button_text_signal,
+                )
+                .await
+                {
+                    Ok(count) => {
+                        // every job of the sweep is submitted, so the draft is no longer needed
+                        if let Some(handle) = draft_timeout.get_value() {
+                            handle.clear();
                         }
+                        local_storage::remove_item(&draft_key(&app_uuid.to_string()));
+                        cl_button_text_signal.set(format!("Submitted {} jobs", count));
+                        cl_button_state_signal.set(State::Success);
                     }
                     Err(e) => {
-                        error_signal.set(Some(format!("Failed to create config file: {}", e)));
+                        error_signal.set(Some(e));
                         cl_button_state_signal.set(State::Error);
                     }
                 }
-            } else {
-                leptos::logging::log!("No form data to upload");
+                return;
             }
 
-            // place into queued state to trigger runner pick up
-            created_job.status = JobStatus::Queued;
-            match fyn_api_client.patch_job(&created_job).await {
+            let config = schema_form_state
+                .get()
+                .map(|form_state| form_state.to_json());
+            let new_job_request =
+                match job_request(job_name.get(), config.as_ref().unwrap_or(&json!({}))) {
+                    Ok(job) => job,
+                    Err(e) => {
+                        error_signal.set(Some(format!("Failed to create job: {}", e)));
+                        cl_button_state_signal.set(State::Error);
+                        return;
+                    }
+                };
+
+            let inputs = input_uploads.get_untracked();
+            match job_service
+                .submit_with_config(&new_job_request, config.as_ref(), &inputs)
+                .await
+            {
                 Ok(job_info) => {
                     leptos::logging::log!("Job {} ({}) set to queued", job_info.name, job_info.id);
                 }
                 Err(e) => {
-                    error_signal.set(Some(format!(
-                        "Error setting job {} ({}) to queue: {:?}",
-                        created_job.name, created_job.id, e
-                    )));
+                    let message = match e.api_error() {
+                        Some(error @ FynApiError::Validation(_)) => {
+                            format!("Job rejected by the server: {}", error)
+                        }
+                        _ => e.to_string(),
+                    };
+                    error_signal.set(Some(message));
                     cl_button_state_signal.set(State::Error);
                     return;
                 }
             };
+            // the job is submitted, so its draft is no longer needed
+            if let Some(handle) = draft_timeout.get_value() {
+                handle.clear();
+            }
+            local_storage::remove_item(&draft_key(&app_uuid.to_string()));
             cl_button_text_signal.set("Success".to_string());
             cl_button_state_signal.set(State::Success);
         });
@@ -289,6 +716,7 @@ pub fn JobConfigForm(runner_list: Option<HashMap<Uuid, RunnerInfo>>) -> impl Int
                             signal: application_id
                         }
                     />
+                    <JobPresetBar application_id={application_id} form_state={schema_form_state} />
                     <FormField
                         label={"Job Name".to_string()}
                         key={"job_name".to_string()}
@@ -303,12 +731,47 @@ pub fn JobConfigForm(runner_list: Option<HashMap<Uuid, RunnerInfo>>) -> impl Int
                         placeholder={"0".to_string()}
                         input_type=InputType::Integer { signal: job_priority, min: Some(0), max: Some(100), step: Some(1) }
                     />
+                    <Stack horizontal={true} align={FlexAlign::Center} add_class="justify-between".to_string()>
+                        <Button button_data={
+                            ButtonData::new()
+                                .size(Size::Sm)
+                                .variant(Variant::Secondary)
+                                .text("Export config")
+                                .on_click(on_export_click)
+                        } />
+                        <File
+                            id={"field-import_config".to_string()}
+                            key={"import_config".to_string()}
+                            signal={import_file}
+                            on_files={on_import}
+                        />
+                    </Stack>
                 }
             }}
+            <WarningAlert message={import_message.read_only()} />
             </Section>
 
             // actual input data collection
             <Section level={SectionLevel::H2} centre={false} spaced={false} title={"Application Setup".to_string()}>
+            <Show when={move || draft_prompt.with(Option::is_some)}>
+                <Stack horizontal={true} align={FlexAlign::Center}>
+                    <P>"A draft from a previous session is available for this application."</P>
+                    <Button button_data={
+                        ButtonData::new()
+                            .size(Size::Sm)
+                            .text("Restore draft")
+                            .on_click(on_restore_draft_click)
+                    } />
+                    <Button button_data={
+                        ButtonData::new()
+                            .size(Size::Sm)
+                            .variant(Variant::Secondary)
+                            .text("Discard draft")
+                            .on_click(on_discard_draft_click)
+                    } />
+                </Stack>
+            </Show>
+            <WarningAlert message={draft_message.read_only()} />
             {move || {
                 match fetch_json_schema.get() {
                     Some(Some(value)) => view! {
@@ -316,7 +779,74 @@ pub fn JobConfigForm(runner_list: Option<HashMap<Uuid, RunnerInfo>>) -> impl Int
                             schema_json=value.to_string()
                             _key=application_id.get()
                             form_state_out=schema_form_state
+                            initial={
+                                draft_app.set_value(application_id.get_untracked());
+                                let initial = pending_import.get_untracked();
+                                if initial.is_some() {
+                                    pending_import.set(None);
+                                }
+                                initial
+                            }
                         />
+                        <Section
+                            level={SectionLevel::H3}
+                            centre={false}
+                            spaced={false}
+                            title={"Command Line".to_string()}
+                        >
+                            {move || view! {
+                                <FormField
+                                    label={"Executable".to_string()}
+                                    key={"executable".to_string()}
+                                    input_type=InputType::SelectText {
+                                        options: selected_app.with(|app| {
+                                            app.as_ref()
+                                                .map(|app| app.entry_points())
+                                                .unwrap_or_default()
+                                                .into_iter()
+                                                .map(|entry_point| (entry_point.clone(), entry_point))
+                                                .collect::<Vec<(String, String)>>()
+                                        }),
+                                        signal: executable
+                                    }
+                                />
+                            }}
+                            <FormField
+                                label={"Arguments".to_string()}
+                                key={"command_line_args".to_string()}
+                                placeholder={"e.g. --threads {{n_procs}}".to_string()}
+                                help={Some("Config values are inserted with {{path}}, e.g. {{solver.n_procs}}".to_string())}
+                                error={Signal::derive(move || args_preview.with(|args| args.as_ref().err().cloned()))}
+                                input_type=InputType::Text { signal: args_text }
+                            />
+                            {move || args_preview.with(|args| args.as_ref().ok().map(|args| view! {
+                                <P>{format!("{} {}", executable.get(), args_display(args))}</P>
+                            }))}
+                        </Section>
+                        <Section
+                            level={SectionLevel::H3}
+                            centre={false}
+                            spaced={false}
+                            collapsible={true}
+                            collapsed={true}
+                            title={"Parameter Sweep".to_string()}
+                        >
+                            <ParameterSweepPanel form_state={schema_form_state} sweep={sweep} batch={job_name} />
+                        </Section>
+                        <Section
+                            level={SectionLevel::H3}
+                            centre={false}
+                            spaced={false}
+                            collapsible={true}
+                            title={"Input Files".to_string()}
+                        >
+                            <FileDropZone key={"input_files".to_string()} on_files={on_input_files} />
+                            {move || input_uploads.with(|uploads| {
+                                uploads.iter().enumerate().map(|(index, upload)| {
+                                    build_input_upload_row(index, upload, input_uploads)
+                                }).collect::<Vec<_>>()
+                            })}
+                        </Section>
 
                         // Runner selection
                         {
@@ -0,0 +1,248 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: job_detail_view.rs
+ * description: Job detail view, metadata, status history and downloadable resources
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::{prelude::*, reactive::spawn_local};
+use uuid::Uuid;
+
+use crate::common::browser_download::save_blob;
+use crate::common::size::*;
+use crate::components::atoms::alert::*;
+use crate::components::atoms::button::*;
+use crate::components::atoms::layout::*;
+use crate::components::atoms::typography::*;
+use crate::components::molecules::section::*;
+use crate::components::molecules::table::*;
+use crate::components::organisms::job_action_bar::*;
+use crate::components::organisms::job_manager_view::job_status_cell;
+use crate::domain::job_context::*;
+use crate::domain::runner_info::RunnerInfo;
+use crate::infrastructure::fyn_api_client::FynApiClient;
+
+// -------------------------------------------------------------------------------------------------
+// Support Functions
+// -------------------------------------------------------------------------------------------------
+
+fn text_column(name: &str) -> ColumnDefinition {
+    ColumnDefinition {
+        name: name.to_string(),
+        data_type: CellType::Text,
+    }
+}
+
+fn job_details_rows(job: &JobInfo) -> Vec<Vec<Cell>> {
+    vec![
+        vec!["Job ID".into(), Cell::Uuid(job.id)],
+        vec!["Status".into(), job_status_cell(job.status)],
+        vec!["Application".into(), Cell::Uuid(job.application_id)],
+        vec!["Priority".into(), job.priority.to_string().into()],
+        vec![
+            "Runner".into(),
+            job.runner_id
+                .map(Cell::Uuid)
+                .unwrap_or_else(|| "Not Assigned".into()),
+        ],
+        vec!["Executable".into(), job.executable.clone().into()],
+        vec![
+            "Arguments".into(),
+            job.command_line_args
+                .as_ref()
+                .map(|args| args.to_string())
+                .unwrap_or_default()
+                .into(),
+        ],
+        vec![
+            "Exit Code".into(),
+            job.exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "-".to_string())
+                .into(),
+        ],
+    ]
+}
+
+fn status_history_rows(job: &JobInfo) -> Vec<Vec<Cell>> {
+    job.status_history
+        .iter()
+        .rev()
+        .map(|change| {
+            vec![
+                job_status_cell(change.status),
+                Cell::Timestamp(Some(change.observed_at)),
+            ]
+        })
+        .collect()
+}
+
+// -------------------------------------------------------------------------------------------------
+// Components
+// -------------------------------------------------------------------------------------------------
+
+#[component]
+fn ResourceRow(resource: JobResource, set_error: WriteSignal<Option<String>>) -> impl IntoView {
+    let fyn_api_client = use_context::<FynApiClient>().expect("FynApiClient should be provided");
+    let file_name = resource.file_name();
+    let description = resource.description.clone();
+
+    let download_button = ButtonData::new()
+        .size(Size::Sm)
+        .variant(Variant::Secondary)
+        .text("Download");
+    let state_signal = download_button.state_signal;
+    let download_button = download_button.on_click(move || {
+        if state_signal.get_untracked() == State::Loading {
+            return;
+        }
+        state_signal.set(State::Loading);
+
+        let fyn_api_client = fyn_api_client.clone();
+        let resource = resource.clone();
+        spawn_local(async move {
+            let result = match fyn_api_client.download_job_resource(&resource).await {
+                Ok(blob) => save_blob(&blob, &resource.file_name()),
+                Err(e) => Err(e.to_string()),
+            };
+            match result {
+                Ok(()) => {
+                    state_signal.set(State::Default);
+                    set_error.set(None);
+                }
+                Err(e) => {
+                    leptos::logging::error!("Download of resource {} failed: {}", resource.id, e);
+                    state_signal.set(State::Error);
+                    set_error.set(Some(format!(
+                        "Failed to download {}: {}",
+                        resource.file_name(),
+                        e
+                    )));
+                }
+            }
+        });
+    });
+
+    return view! {
+        <Stack horizontal=true align=FlexAlign::Center add_class="justify-between".to_string()>
+            <Stack size=Size::None>
+                <span class=format!("{} {}", H4_CLASS, FONT_CLR)>{file_name}</span>
+                <span class=format!("{} {}", NORMAL_CLASS, FONT_CLR)>{description}</span>
+            </Stack>
+            <Button button_data=download_button />
+        </Stack>
+    };
+}
+
+#[component]
+fn ResourceList(resources: Vec<JobResource>) -> impl IntoView {
+    let (error, set_error) = signal(None::<String>);
+
+    if resources.is_empty() {
+        return view! { <P>"No resources have been attached to this job."</P> }.into_any();
+    }
+
+    let groups = ResourceType::DISPLAY_ORDER
+        .iter()
+        .filter_map(|resource_type| {
+            let group = resources
+                .iter()
+                .filter(|resource| resource.resource_type == *resource_type)
+                .cloned()
+                .collect::<Vec<JobResource>>();
+            if group.is_empty() {
+                return None;
+            }
+
+            let rows = group
+                .into_iter()
+                .map(|resource| view! { <ResourceRow resource=resource set_error=set_error /> })
+                .collect::<Vec<_>>();
+            Some(view! {
+                <Section level=SectionLevel::H4 title=resource_type.to_string()>
+                    <BorderedDiv class=padding(Size::Md)>
+                        <Stack size=Size::Md>{rows}</Stack>
+                    </BorderedDiv>
+                </Section>
+            })
+        })
+        .collect::<Vec<_>>();
+
+    return view! {
+        <ErrorAlert message=error />
+        {groups}
+    }
+    .into_any();
+}
+
+#[component]
+pub fn JobDetailView(
+    job: JobInfo,
+    resources: Option<Vec<JobResource>>,
+    runner: Option<RunnerInfo>,
+    #[prop(optional)] on_action: Option<Callback<JobInfo>>,
+    #[prop(optional)] on_deleted: Option<Callback<Uuid>>,
+) -> impl IntoView {
+    let details = job_details_rows(&job);
+    let history = status_history_rows(&job);
+
+    return view! {
+        <Stack size=Size::Lg>
+            <Section level=SectionLevel::H1 title=job.name.clone() is_first=true>
+                {match on_action {
+                    Some(on_action) => view! {
+                        <JobActionBar
+                            job=job.clone()
+                            runner=runner
+                            size=Size::Md
+                            on_applied=on_action
+                            on_deleted=on_deleted
+                        />
+                    }.into_any(),
+                    None => view! {
+                        <JobActionBar job=job.clone() runner=runner size=Size::Md on_deleted=on_deleted />
+                    }.into_any(),
+                }}
+                <Table table={TableStruct {
+                    name: "".to_string(),
+                    data: TableData {
+                        col_def: vec![text_column("Field"), text_column("Value")],
+                        rows: details
+                    }
+                }}/>
+            </Section>
+
+            <Section level=SectionLevel::H2 title="Status History".to_string()>
+                <Table table={TableStruct {
+                    name: "".to_string(),
+                    data: TableData {
+                        col_def: vec![text_column("Status"), text_column("Observed")],
+                        rows: history
+                    }
+                }}/>
+            </Section>
+
+            <Section level=SectionLevel::H2 title="Resources".to_string()>
+                {match resources {
+                    Some(resources) => view! { <ResourceList resources=resources /> }.into_any(),
+                    None => view! { <div>"Loading resources..."</div> }.into_any(),
+                }}
+            </Section>
+        </Stack>
+    };
+}
@@ -0,0 +1,268 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: job_log_viewer.rs
+ * description: Tailing log viewer with ANSI colours, search and download
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::{prelude::*, reactive::spawn_local};
+
+use crate::application::job_service::ResourceTail;
+use crate::common::ansi::{AnsiColour, AnsiSpan, AnsiStyle};
+use crate::common::browser_download::save_blob;
+use crate::common::log_tail::split_matches;
+use crate::common::size::*;
+use crate::components::atoms::alert::*;
+use crate::components::atoms::button::*;
+use crate::components::atoms::input::{CheckBox, Text};
+use crate::components::atoms::layout::*;
+use crate::components::atoms::typography::*;
+use crate::domain::job_context::JobResource;
+use crate::infrastructure::fyn_api_client::FynApiClient;
+
+/// Older lines are still searchable and downloadable, but not rendered.
+const MAX_RENDERED_LINES: usize = 2000;
+
+// -------------------------------------------------------------------------------------------------
+// ANSI Styling
+// -------------------------------------------------------------------------------------------------
+
+/// Terminal palette on the viewer's dark background, standard colours then their bright variants.
+const FOREGROUND_PALETTE: [&str; 16] = [
+    "text-gray-500",
+    "text-red-400",
+    "text-green-400",
+    "text-yellow-400",
+    "text-blue-400",
+    "text-fuchsia-400",
+    "text-cyan-400",
+    "text-gray-200",
+    "text-gray-400",
+    "text-red-300",
+    "text-green-300",
+    "text-yellow-300",
+    "text-blue-300",
+    "text-fuchsia-300",
+    "text-cyan-300",
+    "text-white",
+];
+
+const BACKGROUND_PALETTE: [&str; 16] = [
+    "bg-gray-950",
+    "bg-red-800",
+    "bg-green-800",
+    "bg-yellow-800",
+    "bg-blue-800",
+    "bg-fuchsia-800",
+    "bg-cyan-800",
+    "bg-gray-400",
+    "bg-gray-700",
+    "bg-red-600",
+    "bg-green-600",
+    "bg-yellow-600",
+    "bg-blue-600",
+    "bg-fuchsia-600",
+    "bg-cyan-600",
+    "bg-gray-100",
+];
+
+fn span_class(style: &AnsiStyle) -> String {
+    let mut classes = Vec::new();
+    if let Some(AnsiColour::Palette(index)) = style.foreground {
+        classes.push(FOREGROUND_PALETTE[index as usize % 16]);
+    }
+    if let Some(AnsiColour::Palette(index)) = style.background {
+        classes.push(BACKGROUND_PALETTE[index as usize % 16]);
+    }
+    if style.bold {
+        classes.push("font-bold");
+    }
+    if style.dim {
+        classes.push("opacity-60");
+    }
+    if style.italic {
+        classes.push("italic");
+    }
+    if style.underline {
+        classes.push("underline");
+    }
+    classes.join(" ")
+}
+
+/// 256 and true colour values have no utility class and are set inline.
+fn span_style(style: &AnsiStyle) -> String {
+    let mut styles = Vec::new();
+    if let Some(AnsiColour::Rgb(r, g, b)) = style.foreground {
+        styles.push(format!("color: rgb({}, {}, {})", r, g, b));
+    }
+    if let Some(AnsiColour::Rgb(r, g, b)) = style.background {
+        styles.push(format!("background-color: rgb({}, {}, {})", r, g, b));
+    }
+    styles.join("; ")
+}
+
+fn render_line(spans: &[AnsiSpan], query: &str) -> impl IntoView {
+    let spans = spans
+        .iter()
+        .map(|span| {
+            let parts = split_matches(&span.text, query)
+                .into_iter()
+                .map(|(text, hit)| match hit {
+                    true => view! { <mark class="bg-yellow-300 text-gray-900">{text.to_string()}</mark> }
+                        .into_any(),
+                    false => text.to_string().into_any(),
+                })
+                .collect::<Vec<_>>();
+            view! {
+                <span class=span_class(&span.style) style=span_style(&span.style)>{parts}</span>
+            }
+        })
+        .collect::<Vec<_>>();
+
+    view! { <div class="whitespace-pre-wrap break-all min-h-[1em]">{spans}</div> }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Components
+// -------------------------------------------------------------------------------------------------
+
+/// Shows a log resource as its `tail` from `JobService::tail_resource` fetches it, `follow` being
+/// true while the job runs and the log is followed.
+#[component]
+pub fn JobLogViewer(
+    resource: JobResource,
+    tail: ResourceTail,
+    follow: Signal<bool>,
+) -> impl IntoView {
+    let fyn_api_client = use_context::<FynApiClient>().expect("FynApiClient should be provided");
+    let query = RwSignal::new(String::new());
+    let auto_scroll = RwSignal::new(true);
+    let (download_error, set_download_error) = signal(None::<String>);
+    let log_ref = NodeRef::<leptos::html::Div>::new();
+    // several logs may be open at once, e.g. on a comparison page
+    let (search_id, auto_scroll_id) = (
+        format!("log-search-{}", resource.id),
+        format!("log-auto-scroll-{}", resource.id),
+    );
+
+    // keep the newest lines in view as they arrive.
+    Effect::new(move |_| {
+        tail.log.track();
+        if auto_scroll.get() {
+            if let Some(log_div) = log_ref.get() {
+                log_div.set_scroll_top(log_div.scroll_height());
+            }
+        }
+    });
+
+    let download_button = ButtonData::new()
+        .size(Size::Sm)
+        .variant(Variant::Secondary)
+        .text("Download full log");
+    let state_signal = download_button.state_signal;
+    let download_button = download_button.on_click(move || {
+        state_signal.set(State::Loading);
+        let fyn_api_client = fyn_api_client.clone();
+        let resource = resource.clone();
+        spawn_local(async move {
+            let result = match fyn_api_client.download_job_resource(&resource).await {
+                Ok(blob) => save_blob(&blob, &resource.file_name()),
+                Err(e) => Err(e.to_string()),
+            };
+            match result {
+                Ok(()) => {
+                    state_signal.set(State::Default);
+                    set_download_error.set(None);
+                }
+                Err(e) => {
+                    state_signal.set(State::Error);
+                    set_download_error.set(Some(format!("Failed to download log: {}", e)));
+                }
+            }
+        });
+    });
+
+    let status = move || {
+        let query = query.get();
+        tail.log.with(|log| match query.is_empty() {
+            true => format!("{} lines", log.lines().len()),
+            false => format!(
+                "{} of {} lines match",
+                log.count_matching_lines(&query),
+                log.lines().len()
+            ),
+        })
+    };
+
+    // complete lines never change, so keyed by their number only newly arrived lines are
+    // rendered and the rest stay in the DOM until they fall out of the window.
+    let skipped = Memo::new(move |_| {
+        tail.log
+            .with(|log| log.lines().len().saturating_sub(MAX_RENDERED_LINES))
+    });
+    let line_numbers = move || tail.log.with(|log| skipped.get()..log.lines().len());
+    let render_numbered_line = move |number: usize| {
+        let spans = tail.log.with_untracked(|log| log.lines()[number].clone());
+        move || render_line(&spans, &query.get())
+    };
+    let partial_line = move || {
+        tail.log
+            .with(|log| log.partial_line())
+            .map(|partial| render_line(&partial, &query.get()))
+    };
+    let notice = move || {
+        let skipped = skipped.get();
+        (skipped > 0).then(|| {
+            view! {
+                <div class="text-gray-500 italic">
+                    {format!("{} earlier lines not shown, download the full log to view them", skipped)}
+                </div>
+            }
+        })
+    };
+
+    return view! {
+        <Stack size=Size::Sm>
+            <Stack horizontal=true align=FlexAlign::Center size=Size::Md>
+                <Text id=search_id key="log_search".to_string() signal=query
+                    placeholder=Some("Search log".to_string()) />
+                <label class=format!("flex items-center gap-2 {} {}", NORMAL_CLASS, FONT_CLR)>
+                    <CheckBox id=auto_scroll_id key="log_auto_scroll".to_string()
+                        signal=auto_scroll />
+                    "Auto-scroll"
+                </label>
+                <Button button_data=download_button />
+                <span class=format!("{} {}", NORMAL_CLASS, FONT_CLR)>
+                    {status}
+                    {move || follow.get().then_some(" (following)")}
+                </span>
+            </Stack>
+            <ErrorAlert message=tail.error.read_only() size=Size::Sm />
+            <ErrorAlert message=download_error size=Size::Sm />
+            <div
+                node_ref=log_ref
+                class=format!("h-96 overflow-y-auto bg-gray-900 text-gray-100 font-technical text-sm {} {}",
+                    padding(Size::Md), ROUND_BORDER)
+            >
+                {notice}
+                <For each=line_numbers key=|number| *number children=render_numbered_line />
+                {partial_line}
+            </div>
+        </Stack>
+    };
+}
@@ -20,70 +20,439 @@
  * ------------------------------------------------------------------------------------------------
  */
 
-use leptos::prelude::*;
-use std::collections::HashMap;
+use leptos::{prelude::*, reactive::spawn_local};
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use uuid::Uuid;
 
+use crate::application::job_service::JobService;
+use crate::common::size::*;
+use crate::components::atoms::alert::*;
+use crate::components::atoms::button::*;
+use crate::components::atoms::layout::*;
+use crate::components::atoms::typography::*;
+use crate::components::molecules::confirm_dialog::*;
+use crate::components::molecules::form_field::*;
+use crate::components::molecules::section::*;
 use crate::components::molecules::table::*;
+use crate::components::organisms::job_action_bar::*;
 use crate::domain::job_context::*;
+use crate::domain::parameter_sweep::batch_name;
 use crate::domain::runner_info::*;
 
+/// A badge for a job's status, coloured by how it ended or whether it still needs attention.
+pub fn job_status_cell(status: JobStatus) -> Cell {
+    let tone = match status {
+        JobStatus::Succeeded => AlertType::Success,
+        JobStatus::Failed
+        | JobStatus::FailedResourceError
+        | JobStatus::FailedTerminated
+        | JobStatus::FailedTimeout
+        | JobStatus::FailedRunnerException => AlertType::Error,
+        JobStatus::UploadingInputResources | JobStatus::Paused => AlertType::Warning,
+        JobStatus::Queued
+        | JobStatus::Preparing
+        | JobStatus::FetchingResources
+        | JobStatus::Starting
+        | JobStatus::Running
+        | JobStatus::CleaningUp
+        | JobStatus::UploadingResults => AlertType::Info,
+    };
+    Cell::Badge {
+        text: status.to_string(),
+        tone,
+    }
+}
+
+/// Options for the batch filter, an empty value shows every job.
+fn batch_options(jobs: &HashMap<Uuid, JobInfo>) -> Vec<(String, String)> {
+    let batches = jobs
+        .values()
+        .filter_map(|job| batch_name(&job.name).map(|(batch, _)| batch.to_string()))
+        .collect::<BTreeSet<String>>();
+    std::iter::once((String::new(), "All jobs".to_string()))
+        .chain(batches.into_iter().map(|batch| (batch.clone(), batch)))
+        .collect()
+}
+
+/// Keeps a stable row order as jobs are updated in place, jobs of a batch are kept together in
+/// the order they were generated.
+fn list_order(a: &JobInfo, b: &JobInfo) -> Ordering {
+    let a_key = batch_name(&a.name).unwrap_or((a.name.as_str(), 0));
+    let b_key = batch_name(&b.name).unwrap_or((b.name.as_str(), 0));
+    a_key
+        .cmp(&b_key)
+        .then(a.name.cmp(&b.name))
+        .then(a.id.cmp(&b.id))
+}
+
+fn job_row(job: &JobInfo, runners: Option<&HashMap<Uuid, RunnerInfo>>) -> Vec<Cell> {
+    vec![
+        batch_name(&job.name)
+            .map(|(batch, _)| batch.to_string())
+            .unwrap_or_default()
+            .into(),
+        Cell::Link {
+            text: job.name.clone(),
+            href: format!("/jobs/{}", job.id),
+        },
+        job_status_cell(job.status),
+        job.priority.to_string().into(),
+        match job.runner_id.and_then(|id| runners?.get(&id)) {
+            Some(runner) => runner.name.clone().into(),
+            None => "Not Assigned".into(),
+        },
+        Cell::Timestamp(job.status_history.last().map(|change| change.observed_at)),
+        Cell::Uuid(job.id),
+    ]
+}
+
+/// What a bulk operation did to one of the selected jobs.
+#[derive(Debug, Clone, PartialEq)]
+enum BulkOutcome {
+    Applied(String),
+    Skipped(String),
+    Failed(String),
+}
+
+fn outcome_row(job_name: &str, outcome: &BulkOutcome) -> Vec<Cell> {
+    let (text, tone, detail) = match outcome {
+        BulkOutcome::Applied(detail) => ("Applied", AlertType::Success, detail),
+        BulkOutcome::Skipped(detail) => ("Skipped", AlertType::Warning, detail),
+        BulkOutcome::Failed(detail) => ("Failed", AlertType::Error, detail),
+    };
+    vec![
+        job_name.into(),
+        Cell::Badge {
+            text: text.to_string(),
+            tone,
+        },
+        detail.clone().into(),
+    ]
+}
+
+fn bulk_button(
+    text: &str,
+    variant: Variant,
+    on_click: impl Fn() + Send + Sync + 'static,
+) -> impl IntoView {
+    view! {
+        <Button button_data={
+            ButtonData::new()
+                .size(Size::Sm)
+                .variant(variant)
+                .text(text)
+                .on_click(on_click)
+        } />
+    }
+}
+
+fn text_column(name: &str) -> ColumnDefinition {
+    ColumnDefinition {
+        name: name.to_string(),
+        data_type: CellType::Text,
+    }
+}
+
+/// Jobs are selected in the table for bulk operations, each one is confirmed with a summary of
+/// what changes on which job and reported job by job once applied.
 #[component]
 pub fn JobManagerView(
-    jobs: Option<HashMap<Uuid, JobInfo>>,
-    runners: Option<HashMap<Uuid, RunnerInfo>>,
+    jobs: Signal<Option<HashMap<Uuid, JobInfo>>>,
+    runners: Signal<Option<HashMap<Uuid, RunnerInfo>>>,
 ) -> impl IntoView {
-    view! {
-        {move || {
-            match &jobs {
-                Some(jobs) => {
-                    let rows = jobs.iter().map(|(_, job)| {
-                        vec![
-                            job.name.clone(),
-                            job.status.to_string(),
-                            job.priority.to_string(),
-                            match &runners {
-                                Some(list) => list.get(&job.runner_id.unwrap())
-                                .unwrap_or(&RunnerInfo::new().name("Not Assigned")).name.clone(),
-                                None => "Not Assigned".to_string(),
-                            },
-                            job.id.to_string()
-                        ]
-                    }).collect::<Vec<Vec<String>>>();
-
-                    view! {
-                        <Table table={TableStruct {
-                            name: "Job List".to_string(),
-                            data: TableData {
-                                col_def: vec![
-                                    ColumnDefinition {
-                                        name: "Name".to_string(),
-                                        data_type: CellType::Text
-                                    },
-                                    ColumnDefinition {
-                                        name: "Status".to_string(),
-                                        data_type: CellType::Text
-                                    },
-                                    ColumnDefinition {
-                                        name: "Priority".to_string(),
-                                        data_type: CellType::Int
-                                    },
-                                    ColumnDefinition {
-                                        name: "Runner".to_string(),
-                                        data_type: CellType::Text
-                                    },
-                                    ColumnDefinition {
-                                        name: "Job ID".to_string(),
-                                        data_type: CellType::Text
-                                    }
-                                ],
-                                rows
-                            }
-                        }}/>
-                    }.into_any()
-                },
-                None => view! { <div>"Loading Jobs..."</div> }.into_any()
+    let job_service = JobService::new();
+    let batch_filter = RwSignal::new(String::new());
+    let selection = RwSignal::new(HashSet::<String>::new());
+    let priority = RwSignal::new(None::<i64>);
+    let keep_results = RwSignal::new(false);
+    let pending = RwSignal::new(None::<BulkOperation>);
+    let dialog_open = RwSignal::new(false);
+    let running = RwSignal::new(false);
+    let report = RwSignal::new(Vec::<(String, BulkOutcome)>::new());
+    let (progress, set_progress) = signal(None::<String>);
+
+    // deleted jobs drop out of the selection
+    Effect::new(move |_| {
+        let ids = jobs.with(|jobs| {
+            jobs.as_ref().map(|jobs| {
+                jobs.keys()
+                    .map(Uuid::to_string)
+                    .collect::<HashSet<String>>()
+            })
+        });
+        if let Some(ids) = ids {
+            selection.maybe_update(|selected| {
+                let selected_len = selected.len();
+                selected.retain(|id| ids.contains(id));
+                selected.len() != selected_len
+            });
+        }
+    });
+
+    let has_selection = Memo::new(move |_| selection.with(|selected| !selected.is_empty()));
+    let selected_jobs = move || {
+        let mut selected = jobs.with(|jobs| {
+            selection.with(|selected| {
+                jobs.iter()
+                    .flat_map(|jobs| jobs.values())
+                    .filter(|job| selected.contains(&job.id.to_string()))
+                    .cloned()
+                    .collect::<Vec<JobInfo>>()
+            })
+        });
+        selected.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+        selected
+    };
+
+    let confirm = move |operation: BulkOperation| {
+        if running.get_untracked() || !has_selection.get_untracked() {
+            return;
+        }
+        pending.set(Some(operation));
+        dialog_open.set(true);
+    };
+
+    let on_confirm = Callback::new(move |_| {
+        let Some(operation) = pending.get_untracked() else {
+            return;
+        };
+        let selected = selected_jobs();
+        let job_service = job_service.clone();
+        running.set(true);
+        report.set(Vec::new());
+        spawn_local(async move {
+            let total = selected.len();
+            for (index, job) in selected.into_iter().enumerate() {
+                set_progress.set(Some(format!(
+                    "{}: job {} of {}",
+                    operation,
+                    index + 1,
+                    total
+                )));
+                let outcome = match operation.plan(&job) {
+                    Err(reason) => BulkOutcome::Skipped(reason),
+                    Ok(change) => match job_service.apply_bulk_operation(&job, operation).await {
+                        Ok(()) => {
+                            selection.update(|selected| {
+                                selected.remove(&job.id.to_string());
+                            });
+                            BulkOutcome::Applied(change)
+                        }
+                        Err(e) => BulkOutcome::Failed(e.to_string()),
+                    },
+                };
+                report.update(|report| report.push((job.name.clone(), outcome)));
             }
-        }}
+            set_progress.set(None);
+            running.set(false);
+        });
+    });
+
+    let bulk_bar = move || {
+        has_selection.get().then(|| {
+            view! {
+                <Stack horizontal={true} align={FlexAlign::Center} size={Size::Sm}>
+                    <span class=format!("{} {}", NORMAL_CLASS, FONT_CLR)>
+                        {move || format!("{} selected", selection.with(HashSet::len))}
+                    </span>
+                    {bulk_button("Cancel", Variant::Secondary, move || confirm(BulkOperation::Cancel))}
+                    {bulk_button("Re-queue", Variant::Primary, move || confirm(BulkOperation::Requeue))}
+                    {bulk_button("Delete", Variant::Secondary, move || {
+                        confirm(BulkOperation::Delete { keep_results: keep_results.get_untracked() });
+                    })}
+                    <FormField
+                        label={"Download results before deleting".to_string()}
+                        key={"bulk_keep_results".to_string()}
+                        input_type=InputType::CheckBox { signal: keep_results }
+                    />
+                    <FormField
+                        label={"Priority".to_string()}
+                        key={"bulk_priority".to_string()}
+                        input_type=InputType::Integer { signal: priority, min: Some(0), max: Some(100), step: Some(1) }
+                    />
+                    {bulk_button("Set Priority", Variant::Primary, move || {
+                        if let Some(priority) = priority.get_untracked() {
+                            confirm(BulkOperation::Reprioritise(priority));
+                        }
+                    })}
+                    {bulk_button("Clear Selection", Variant::Tertiary, move || selection.set(HashSet::new()))}
+                </Stack>
+            }
+        })
+    };
+
+    // the table is fed rows derived from the polled jobs rather than rebuilt, so its sort,
+    // filters, page and column choice survive each poll.
+    let loaded = Memo::new(move |_| jobs.with(Option::is_some));
+    let listed_jobs = Memo::new(move |_| {
+        let filter = batch_filter.get();
+        jobs.with(|jobs| {
+            let mut listed = jobs
+                .iter()
+                .flat_map(HashMap::values)
+                .filter(|job| {
+                    filter.is_empty()
+                        || batch_name(&job.name).is_some_and(|(batch, _)| batch == filter)
+                })
+                .cloned()
+                .collect::<Vec<JobInfo>>();
+            listed.sort_by(list_order);
+            listed
+        })
+    });
+    let rows = Signal::derive(move || {
+        runners.with(|runners| {
+            listed_jobs.with(|jobs| {
+                jobs.iter()
+                    .map(|job| job_row(job, runners.as_ref()))
+                    .collect::<Vec<Vec<Cell>>>()
+            })
+        })
+    });
+    let row_keys = Signal::derive(move || {
+        listed_jobs.with(|jobs| {
+            jobs.iter()
+                .map(|job| job.id.to_string())
+                .collect::<Vec<String>>()
+        })
+    });
+    let row_actions = Callback::new(move |index: usize| {
+        let job = listed_jobs.with_untracked(|jobs| jobs[index].clone());
+        let runner = job
+            .runner_id
+            .and_then(|id| runners.with_untracked(|runners| runners.as_ref()?.get(&id).cloned()));
+        view! { <JobActionBar job=job runner=runner /> }.into_any()
+    });
+    let batches = Memo::new(move |_| jobs.with(|jobs| jobs.as_ref().map(batch_options)));
+    let batch_filter_field = move || {
+        batches.get().map(|options| {
+            view! {
+                <FormField
+                    label={"Batch".to_string()}
+                    key={"batch_filter".to_string()}
+                    input_type=InputType::SelectText { options, signal: batch_filter }
+                />
+            }
+        })
+    };
+
+    let dialog_summary = move || {
+        let operation = pending.get()?;
+        let selected = selected_jobs();
+        let rows = selected
+            .iter()
+            .map(|job| {
+                let change = match operation.plan(job) {
+                    Ok(change) => change.into(),
+                    Err(reason) => Cell::Badge {
+                        text: format!("Skipped: {}", reason),
+                        tone: AlertType::Warning,
+                    },
+                };
+                vec![job.name.clone().into(), job_status_cell(job.status), change]
+            })
+            .collect::<Vec<Vec<Cell>>>();
+        let applied = selected
+            .iter()
+            .filter(|job| operation.plan(job).is_ok())
+            .count();
+        let warning = matches!(operation, BulkOperation::Delete { .. })
+            .then_some(" Deleted jobs cannot be restored.");
+        Some(view! {
+            <P>
+                {format!(
+                    "{}: {} of {} selected jobs change, {} are skipped.{}",
+                    operation,
+                    applied,
+                    selected.len(),
+                    selected.len() - applied,
+                    warning.unwrap_or_default()
+                )}
+            </P>
+            <Table table={TableStruct {
+                name: String::new(),
+                data: TableData {
+                    col_def: vec![text_column("Name"), text_column("Status"), text_column("Change")],
+                    rows
+                }
+            }} />
+        })
+    };
+
+    let report_view = move || {
+        let rows = report.with(|report| {
+            report
+                .iter()
+                .map(|(job_name, outcome)| outcome_row(job_name, outcome))
+                .collect::<Vec<Vec<Cell>>>()
+        });
+        (!rows.is_empty()).then(|| {
+            view! {
+                <Section level=SectionLevel::H2 title="Bulk Operation Report".to_string()>
+                    <Table table={TableStruct {
+                        name: String::new(),
+                        data: TableData {
+                            col_def: vec![text_column("Name"), text_column("Outcome"), text_column("Details")],
+                            rows
+                        }
+                    }} />
+                    {bulk_button("Dismiss", Variant::Secondary, move || report.set(Vec::new()))}
+                </Section>
+            }
+        })
+    };
+
+    view! {
+        {bulk_bar}
+        <InfoAlert message=progress size=Size::Sm />
+        {report_view}
+        <Show when=move || loaded.get() fallback=|| view! { <div>"Loading Jobs..."</div> }>
+            {batch_filter_field}
+            <Table table={TableStruct {
+                name: "Job List".to_string(),
+                data: TableData {
+                    col_def: vec![
+                        ColumnDefinition {
+                            name: "Batch".to_string(),
+                            data_type: CellType::Text
+                        },
+                        ColumnDefinition {
+                            name: "Name".to_string(),
+                            data_type: CellType::Text
+                        },
+                        ColumnDefinition {
+                            name: "Status".to_string(),
+                            data_type: CellType::Text
+                        },
+                        ColumnDefinition {
+                            name: "Priority".to_string(),
+                            data_type: CellType::Int
+                        },
+                        ColumnDefinition {
+                            name: "Runner".to_string(),
+                            data_type: CellType::Text
+                        },
+                        ColumnDefinition {
+                            name: "Updated".to_string(),
+                            data_type: CellType::Text
+                        },
+                        ColumnDefinition {
+                            name: "Job ID".to_string(),
+                            data_type: CellType::Text
+                        }
+                    ],
+                    rows: Vec::new()
+                }
+            }} rows=rows row_actions=row_actions interactive=true selection=selection row_keys=row_keys />
+        </Show>
+        <ConfirmDialog
+            open=dialog_open
+            title={"Confirm Bulk Operation".to_string()}
+            confirm_text={"Apply".to_string()}
+            on_confirm=on_confirm
+        >
+            {dialog_summary}
+        </ConfirmDialog>
     }
 }
@@ -0,0 +1,269 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: job_preset_bar.rs
+ * description: Save, apply, export and import named config presets for an application
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::{prelude::*, reactive::spawn_local};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::common::browser_download::save_blob;
+use crate::common::browser_file::read_text;
+use crate::common::local_storage;
+use crate::common::size::*;
+use crate::components::atoms::alert::*;
+use crate::components::atoms::button::*;
+use crate::components::atoms::input::File;
+use crate::components::atoms::layout::*;
+use crate::components::molecules::form_field::*;
+use crate::components::molecules::schema_form::SchemaFormState;
+use crate::domain::job_config::{JobPreset, JobPresets};
+use crate::infrastructure::fyn_api_client::FynApiClient;
+
+fn presets_key(application_id: Uuid) -> String {
+    format!("job_presets:{}", application_id)
+}
+
+/// The presets stored for an application, an unreadable entry is reported and treated as empty.
+fn load_presets(application_id: Uuid) -> JobPresets {
+    let stored = local_storage::get_item(&presets_key(application_id))
+        .map(|text| JobPresets::from_json_str(&text));
+    match stored {
+        Some(Ok(presets)) if presets.application_id == application_id => presets,
+        Some(Err(e)) => {
+            leptos::logging::warn!("Ignoring stored presets for {}: {}", application_id, e);
+            JobPresets::new(application_id)
+        }
+        _ => JobPresets::new(application_id),
+    }
+}
+
+fn store_presets(presets: &JobPresets) -> Result<(), String> {
+    local_storage::set_item(
+        &presets_key(presets.application_id),
+        &presets.to_json().to_string(),
+    )
+}
+
+// -------------------------------------------------------------------------------------------------
+// Component
+// -------------------------------------------------------------------------------------------------
+
+/// Presets are kept in the browser per application. Applying one loads its config into the form
+/// in `form_state`, values the current schema does not know are dropped with a warning.
+#[component]
+pub fn JobPresetBar(
+    application_id: RwSignal<String>,
+    form_state: RwSignal<Option<SchemaFormState>>,
+) -> impl IntoView {
+    let presets: RwSignal<Option<JobPresets>> = RwSignal::new(None);
+    let selected = RwSignal::new(String::new());
+    let preset_name = RwSignal::new(String::new());
+    let import_file = RwSignal::new(String::new());
+    let message = RwSignal::new(None::<String>);
+    let error = RwSignal::new(None::<String>);
+
+    Effect::new(move |_| {
+        let app_presets = Uuid::from_str(&application_id.get()).ok().map(load_presets);
+        selected.set(
+            app_presets
+                .as_ref()
+                .and_then(|p| p.presets.first())
+                .map(|preset| preset.name.clone())
+                .unwrap_or_default(),
+        );
+        message.set(None);
+        error.set(None);
+        presets.set(app_presets);
+    });
+
+    // applies a change to the presets and persists the result
+    let update_presets = move |change: &dyn Fn(&mut JobPresets) -> Result<(), String>| {
+        let Some(mut app_presets) = presets.get_untracked() else {
+            return;
+        };
+        match change(&mut app_presets).and_then(|_| store_presets(&app_presets)) {
+            Ok(()) => {
+                error.set(None);
+                presets.set(Some(app_presets));
+            }
+            Err(e) => error.set(Some(e)),
+        }
+    };
+
+    let on_save_click = move || {
+        message.set(None);
+        let name = preset_name.get_untracked().trim().to_string();
+        if name.is_empty() {
+            error.set(Some("Enter a name for the preset".to_string()));
+            return;
+        }
+        let Some(config) = form_state.get_untracked().map(|state| state.to_json()) else {
+            return;
+        };
+        update_presets(&|app_presets| {
+            app_presets.insert(JobPreset {
+                name: name.clone(),
+                config: config.clone(),
+            });
+            Ok(())
+        });
+        selected.set(name);
+        preset_name.set(String::new());
+    };
+
+    let on_apply_click = move || {
+        message.set(None);
+        let name = selected.get_untracked();
+        let Some(preset) = presets.with_untracked(|p| p.as_ref()?.get(&name).cloned()) else {
+            return;
+        };
+        if let Some(state) = form_state.get_untracked() {
+            let unmatched = state.load_json(&preset.config);
+            if !unmatched.is_empty() {
+                message.set(Some(format!(
+                    "Ignored values that do not match the schema: {}",
+                    unmatched.join(", ")
+                )));
+            }
+        }
+    };
+
+    let on_delete_click = move || {
+        message.set(None);
+        let name = selected.get_untracked();
+        update_presets(&|app_presets| {
+            app_presets.remove(&name);
+            Ok(())
+        });
+        selected.set(
+            presets
+                .with_untracked(|p| Some(p.as_ref()?.presets.first()?.name.clone()))
+                .unwrap_or_default(),
+        );
+    };
+
+    let on_export_click = move || {
+        let Some(app_presets) = presets.get_untracked() else {
+            return;
+        };
+        let file_name = format!("presets_{}.json", app_presets.application_id);
+        if let Err(e) = FynApiClient::create_json_file(&app_presets.to_json(), &file_name)
+            .and_then(|file| save_blob(&file, &file_name))
+        {
+            error.set(Some(format!("Failed to export presets: {}", e)));
+        }
+    };
+
+    let on_import = Callback::new(move |files: Vec<web_sys::File>| {
+        let Some(file) = files.into_iter().next() else {
+            return;
+        };
+        spawn_local(async move {
+            message.set(None);
+            let imported = match read_text(&file)
+                .await
+                .and_then(|text| JobPresets::from_json_str(&text))
+            {
+                Ok(imported) => imported,
+                Err(e) => {
+                    error.set(Some(e));
+                    return;
+                }
+            };
+            let count = imported.presets.len();
+            update_presets(&|app_presets| app_presets.merge(imported.clone()).map(|_| ()));
+            if error.with_untracked(Option::is_none) {
+                message.set(Some(format!("Imported {} preset(s)", count)));
+            }
+        });
+    });
+
+    return view! {
+        {move || presets.with(|app_presets| app_presets.as_ref().map(|app_presets| {
+            let options = app_presets
+                .presets
+                .iter()
+                .map(|preset| (preset.name.clone(), preset.name.clone()))
+                .collect::<Vec<(String, String)>>();
+            let preset_select = (!options.is_empty()).then(|| view! {
+                <Stack horizontal={true} align={FlexAlign::Center}>
+                    <FormField
+                        label={"Preset".to_string()}
+                        key={"preset".to_string()}
+                        input_type=InputType::SelectText {
+                            options,
+                            signal: selected
+                        }
+                    />
+                    <Button button_data={
+                        ButtonData::new()
+                            .size(Size::Sm)
+                            .text("Apply")
+                            .on_click(on_apply_click)
+                    } />
+                    <Button button_data={
+                        ButtonData::new()
+                            .size(Size::Sm)
+                            .variant(Variant::Secondary)
+                            .text("Delete")
+                            .on_click(on_delete_click)
+                    } />
+                </Stack>
+            });
+            view! {
+                <Stack size=Size::Xs>
+                    {preset_select}
+                    <Stack horizontal={true} align={FlexAlign::Center}>
+                        <FormField
+                            label={"Preset Name".to_string()}
+                            key={"preset_name".to_string()}
+                            placeholder={"e.g. Cavity Re=100".to_string()}
+                            input_type=InputType::Text { signal: preset_name }
+                        />
+                        <Button button_data={
+                            ButtonData::new()
+                                .size(Size::Sm)
+                                .text("Save preset")
+                                .on_click(on_save_click)
+                        } />
+                    </Stack>
+                    <Stack horizontal={true} align={FlexAlign::Center} add_class="justify-between".to_string()>
+                        <Button button_data={
+                            ButtonData::new()
+                                .size(Size::Sm)
+                                .variant(Variant::Secondary)
+                                .text("Export presets")
+                                .on_click(on_export_click)
+                        } />
+                        <File
+                            id={"field-import_presets".to_string()}
+                            key={"import_presets".to_string()}
+                            signal={import_file}
+                            on_files={on_import}
+                        />
+                    </Stack>
+                </Stack>
+            }
+        }))}
+        <WarningAlert message={message.read_only()} />
+        <ErrorAlert message={error.read_only()} />
+    };
+}
@@ -0,0 +1,385 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: mesh_viewer.rs
+ * description: Interactive WebGL2 viewer for surface meshes
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::prelude::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+use crate::common::colour_map::ColourMap;
+use crate::common::orbit_camera::OrbitCamera;
+use crate::common::size::*;
+use crate::common::webgl_renderer::{MeshRenderer, RenderMode};
+use crate::components::atoms::alert::*;
+use crate::components::atoms::button::*;
+use crate::components::atoms::input::{Float, SelectText};
+use crate::components::atoms::layout::*;
+use crate::components::molecules::button_bar::*;
+use crate::domain::mesh::{Mesh, MeshField};
+
+/// Radians turned per pixel dragged.
+const ORBIT_SPEED: f32 = 0.01;
+const ZOOM_STEP: f32 = 1.1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DragAction {
+    Orbit,
+    Pan,
+}
+
+/// How the surface is coloured by a field: the field's index on the mesh, the map and the range
+/// of values spread over it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Colouring {
+    field: usize,
+    map: ColourMap,
+    range: (f32, f32),
+}
+
+/// Drag with the left button to orbit, with the right button (or shift) to pan, and scroll to
+/// zoom. The camera is fitted to each new mesh. Meshes carrying result fields can be coloured by
+/// any of them, over their full range or one entered by hand.
+#[component]
+pub fn MeshViewer(mesh: Signal<Option<Mesh>>) -> impl IntoView {
+    let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
+    let renderer = StoredValue::new_local(None::<MeshRenderer>);
+    let camera = RwSignal::new(OrbitCamera::new());
+    let mode = RwSignal::new(RenderMode::Shaded);
+    let resized = RwSignal::new(0u32);
+    let error = RwSignal::new(None::<String>);
+    let drag = RwSignal::new(None::<(DragAction, i32, i32)>);
+    let resize_observer = StoredValue::new_local(None::<ResizeObserver>);
+
+    // selects hold strings, an empty field is the plain surface colour.
+    let selected_field = RwSignal::new(String::new());
+    let selected_map = RwSignal::new("0".to_string());
+    let range_min = RwSignal::new(None::<f64>);
+    let range_max = RwSignal::new(None::<f64>);
+
+    let field = Memo::new(move |_| {
+        let index = selected_field.get().parse::<usize>().ok()?;
+        mesh.with(|mesh| Some((index, mesh.as_ref()?.fields.get(index)?.clone())))
+    });
+    let field_range = Memo::new(move |_| field.with(|field| field.as_ref()?.1.range()));
+    let colouring = Memo::new(move |_| {
+        let (index, _) = field.get()?;
+        let (auto_min, auto_max) = field_range.get()?;
+        let map = selected_map
+            .get()
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| ColourMap::ALL.get(index).copied())
+            .unwrap_or(ColourMap::Viridis);
+        Some(Colouring {
+            field: index,
+            map,
+            range: (
+                range_min.get().map_or(auto_min, |min| min as f32),
+                range_max.get().map_or(auto_max, |max| max as f32),
+            ),
+        })
+    });
+
+    // a new field starts from its full range.
+    Effect::new(move |_| {
+        selected_field.track();
+        range_min.set(None);
+        range_max.set(None);
+    });
+    // and a new mesh from the plain surface.
+    Effect::new(move |_| {
+        mesh.track();
+        selected_field.set(String::new());
+    });
+
+    // created once the canvas is mounted, then handed each new mesh.
+    Effect::new(move |_| {
+        let Some(canvas) = canvas_ref.get() else {
+            return;
+        };
+        if renderer.with_value(|renderer| renderer.is_none()) {
+            match MeshRenderer::new(canvas.clone()) {
+                Ok(created) => renderer.set_value(Some(created)),
+                Err(e) => {
+                    error.set(Some(e));
+                    return;
+                }
+            }
+            resize_observer.set_value(ResizeObserver::new(&canvas, resized));
+        }
+
+        mesh.with(|mesh| {
+            renderer.update_value(|renderer| {
+                if let Some(renderer) = renderer {
+                    if let Err(e) = renderer.set_mesh(mesh.as_ref()) {
+                        error.set(Some(e));
+                    }
+                }
+            });
+            if let Some(bounds) = mesh.as_ref().and_then(|mesh| mesh.bounds()) {
+                camera.update(|camera| *camera = camera.fit(&bounds));
+            }
+        });
+    });
+
+    Effect::new(move |_| {
+        let colouring = colouring.get();
+        mesh.with(|mesh| {
+            let colours = match (mesh.as_ref(), colouring) {
+                (Some(mesh), Some(colouring)) => {
+                    let scalars = mesh.corner_scalars(&mesh.fields[colouring.field]);
+                    colouring.map.colours(&scalars, colouring.range)
+                }
+                _ => Vec::new(),
+            };
+            let coloured_mesh = mesh.as_ref().filter(|_| !colours.is_empty());
+            renderer.update_value(|renderer| {
+                if let Some(renderer) = renderer {
+                    if let Err(e) = renderer.set_corner_colours(coloured_mesh, &colours) {
+                        error.set(Some(e));
+                    }
+                }
+            });
+        });
+    });
+
+    Effect::new(move |_| {
+        let (camera, mode) = (camera.get(), mode.get());
+        resized.track();
+        mesh.track();
+        colouring.track();
+        renderer.with_value(|renderer| {
+            if let Some(renderer) = renderer {
+                renderer.draw(&camera, mode);
+            }
+        });
+    });
+
+    let on_mouse_down = move |ev: leptos::ev::MouseEvent| {
+        let action = match ev.button() == 2 || ev.shift_key() {
+            true => DragAction::Pan,
+            false => DragAction::Orbit,
+        };
+        drag.set(Some((action, ev.client_x(), ev.client_y())));
+    };
+
+    let on_mouse_move = move |ev: leptos::ev::MouseEvent| {
+        let Some((action, x, y)) = drag.get_untracked() else {
+            return;
+        };
+        let (dx, dy) = ((ev.client_x() - x) as f32, (ev.client_y() - y) as f32);
+        let height = canvas_ref
+            .get_untracked()
+            .map(|canvas| canvas.client_height().max(1) as f32)
+            .unwrap_or(1.0);
+        camera.update(|camera| {
+            *camera = match action {
+                DragAction::Orbit => camera.orbit(-dx * ORBIT_SPEED, dy * ORBIT_SPEED),
+                DragAction::Pan => camera.pan(dx / height, dy / height),
+            }
+        });
+        drag.set(Some((action, ev.client_x(), ev.client_y())));
+    };
+
+    let on_wheel = move |ev: leptos::ev::WheelEvent| {
+        ev.prevent_default();
+        let factor = if ev.delta_y() > 0.0 {
+            ZOOM_STEP
+        } else {
+            1.0 / ZOOM_STEP
+        };
+        camera.update(|camera| *camera = camera.zoom(factor));
+    };
+
+    let fit = move || {
+        if let Some(bounds) =
+            mesh.with_untracked(|mesh| mesh.as_ref().and_then(|mesh| mesh.bounds()))
+        {
+            camera.update(|camera| *camera = camera.fit(&bounds));
+        }
+    };
+
+    let shaded_button = ButtonData::new()
+        .size(Size::Sm)
+        .text("Shaded")
+        .on_click(move || mode.set(RenderMode::Shaded));
+    let wireframe_button = ButtonData::new()
+        .size(Size::Sm)
+        .text("Wireframe")
+        .on_click(move || mode.set(RenderMode::Wireframe));
+    let (shaded_state, wireframe_state) =
+        (shaded_button.state_signal, wireframe_button.state_signal);
+    Effect::new(move |_| {
+        let active = |selected: bool| {
+            if selected {
+                State::Active
+            } else {
+                State::Default
+            }
+        };
+        shaded_state.set(active(mode.get() == RenderMode::Shaded));
+        wireframe_state.set(active(mode.get() == RenderMode::Wireframe));
+    });
+
+    let field_select = move || {
+        let options = mesh.with(|mesh| {
+            mesh.iter()
+                .flat_map(|mesh| mesh.fields.iter())
+                .map(MeshField::label)
+                .enumerate()
+                .map(|(index, label)| (index.to_string(), label))
+                .collect::<Vec<(String, String)>>()
+        });
+        (!options.is_empty()).then(|| {
+            let options = std::iter::once((String::new(), "Solid colour".to_string()))
+                .chain(options)
+                .collect::<Vec<(String, String)>>();
+            view! {
+                <SelectText
+                    id="mesh_field".to_string()
+                    key="mesh_field".to_string()
+                    options=options
+                    signal=selected_field
+                />
+            }
+        })
+    };
+
+    // rebuilt with each field so the placeholders show its full range.
+    let colour_controls = move || {
+        let (min, max) = field_range.get()?;
+        let map_options = ColourMap::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, map)| (index.to_string(), map.to_string()))
+            .collect::<Vec<(String, String)>>();
+        Some(view! {
+            <SelectText
+                id="mesh_colour_map".to_string()
+                key="mesh_colour_map".to_string()
+                options=map_options
+                signal=selected_map
+            />
+            <Stack horizontal=true size=Size::Sm>
+                <Float
+                    id="mesh_range_min".to_string()
+                    key="mesh_range_min".to_string()
+                    signal=range_min
+                    placeholder=Some(format!("{:.4}", min))
+                />
+                <Float
+                    id="mesh_range_max".to_string()
+                    key="mesh_range_max".to_string()
+                    signal=range_max
+                    placeholder=Some(format!("{:.4}", max))
+                />
+            </Stack>
+        })
+    };
+
+    let legend = move || {
+        let colouring = colouring.get()?;
+        let label = field.with(|field| field.as_ref().map(|(_, field)| field.label()))?;
+        Some(view! { <ColourLegend map=colouring.map range=colouring.range label=label /> })
+    };
+
+    let fit_button = ButtonData::new()
+        .size(Size::Sm)
+        .variant(Variant::Secondary)
+        .text("Fit")
+        .on_click(fit);
+
+    return view! {
+        <div class="relative h-full w-full">
+            <canvas
+                node_ref=canvas_ref
+                class="absolute inset-0 h-full w-full cursor-grab"
+                on:mousedown=on_mouse_down
+                on:mousemove=on_mouse_move
+                on:mouseup=move |_| drag.set(None)
+                on:mouseleave=move |_| drag.set(None)
+                on:contextmenu=move |ev| ev.prevent_default()
+                on:wheel=on_wheel
+            ></canvas>
+            <div class="absolute top-2 right-2">
+                <Stack horizontal=true size=Size::Sm fill_space=false>
+                    <ButtonBar items=vec![
+                        view! { <GroupButton button_data=shaded_button /> },
+                        view! { <GroupButton button_data=wireframe_button /> },
+                    ] />
+                    <Button button_data=fit_button />
+                </Stack>
+            </div>
+            <div class="absolute top-2 left-2 w-64">
+                <Stack size=Size::Sm>
+                    {field_select}
+                    {colour_controls}
+                    <ErrorAlert message=error.read_only() size=Size::Sm />
+                </Stack>
+            </div>
+            <div class="absolute bottom-2 right-2">{legend}</div>
+        </div>
+    };
+}
+
+/// A vertical colour bar labelled with the ends and middle of its range.
+#[component]
+fn ColourLegend(map: ColourMap, range: (f32, f32), label: String) -> impl IntoView {
+    let (min, max) = range;
+    let ticks = [max, 0.5 * (min + max), min]
+        .into_iter()
+        .map(|value| view! { <span>{format!("{:.4}", value)}</span> })
+        .collect::<Vec<_>>();
+
+    return view! {
+        <div class="flex flex-col gap-1 p-2 rounded bg-white/80 text-xs text-surface-900">
+            <span class="font-semibold">{label}</span>
+            <div class="flex gap-2 h-40">
+                <div class="w-4 rounded-sm" style=format!("background: {}", map.css_gradient())></div>
+                <div class="flex flex-col justify-between">{ticks}</div>
+            </div>
+        </div>
+    };
+}
+
+/// Bumps a counter whenever an element changes size, e.g. while the layout splitter is dragged.
+/// Stops observing when dropped, along with the viewer that owns it.
+struct ResizeObserver {
+    observer: web_sys::ResizeObserver,
+    _callback: Closure<dyn FnMut()>,
+}
+
+impl ResizeObserver {
+    fn new(element: &web_sys::Element, resized: RwSignal<u32>) -> Option<Self> {
+        let callback = Closure::<dyn FnMut()>::new(move || resized.update(|count| *count += 1));
+        let observer = web_sys::ResizeObserver::new(callback.as_ref().unchecked_ref()).ok()?;
+        observer.observe(element);
+        Some(Self {
+            observer,
+            _callback: callback,
+        })
+    }
+}
+
+impl Drop for ResizeObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
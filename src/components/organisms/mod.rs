@@ -22,8 +22,15 @@
 
 pub mod footer;
 pub mod fyn_tech_profile;
+pub mod job_action_bar;
 pub mod job_config_form;
+pub mod job_detail_view;
+pub mod job_log_viewer;
 pub mod job_manager_view;
+pub mod job_preset_bar;
+pub mod mesh_viewer;
 pub mod navigation;
+pub mod parameter_sweep_panel;
+pub mod residual_chart;
 pub mod user_registration_form;
 pub mod user_sign_in;
@@ -0,0 +1,306 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: parameter_sweep_panel.rs
+ * description: Mark numeric config fields as swept and preview the jobs of the sweep
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::prelude::*;
+
+use crate::common::size::*;
+use crate::components::atoms::alert::*;
+use crate::components::atoms::button::*;
+use crate::components::atoms::layout::*;
+use crate::components::molecules::form_field::*;
+use crate::components::molecules::schema_form::{NumericField, SchemaFormState};
+use crate::components::molecules::table::*;
+use crate::domain::parameter_sweep::*;
+
+/// The inputs of one swept parameter, `field` holds the dotted path of the swept field.
+#[derive(Debug, Clone, Copy)]
+struct SweepRow {
+    field: RwSignal<String>,
+    mode: RwSignal<String>,
+    list: RwSignal<String>,
+    start: RwSignal<Option<f64>>,
+    stop: RwSignal<Option<f64>>,
+    count: RwSignal<Option<i64>>,
+}
+
+impl SweepRow {
+    fn new(field: String) -> Self {
+        Self {
+            field: RwSignal::new(field),
+            mode: RwSignal::new("list".to_string()),
+            list: RwSignal::new(String::new()),
+            start: RwSignal::new(None),
+            stop: RwSignal::new(None),
+            count: RwSignal::new(Some(5)),
+        }
+    }
+
+    fn to_parameter(self, fields: &[NumericField]) -> Result<SweepParameter, String> {
+        let field_name = self.field.get();
+        let field = fields
+            .iter()
+            .find(|field| field.path.join(".") == field_name)
+            .ok_or("Select a field to sweep".to_string())?;
+
+        let bounds = || match (self.start.get(), self.stop.get(), self.count.get()) {
+            (Some(start), Some(stop), Some(count)) if count > 0 => {
+                Ok((start, stop, count as usize))
+            }
+            _ => Err(format!("{}: enter a start, stop and count", field.label)),
+        };
+        let range = match self.mode.get().as_str() {
+            "linear" => {
+                bounds().map(|(start, stop, count)| SweepRange::Linear { start, stop, count })?
+            }
+            "log" => bounds().map(|(start, stop, count)| SweepRange::Log { start, stop, count })?,
+            _ => SweepRange::List(
+                self.list
+                    .get()
+                    .split([',', ' '])
+                    .filter(|value| !value.is_empty())
+                    .map(|value| {
+                        value
+                            .parse::<f64>()
+                            .map_err(|_| format!("{}: '{}' is not a number", field.label, value))
+                    })
+                    .collect::<Result<Vec<f64>, String>>()?,
+            ),
+        };
+
+        Ok(SweepParameter {
+            path: field.path.clone(),
+            integer: field.integer,
+            range,
+        })
+    }
+}
+
+fn mode_options() -> Vec<(String, String)> {
+    vec![
+        ("list".to_string(), "List".to_string()),
+        ("linear".to_string(), "Linear range".to_string()),
+        ("log".to_string(), "Log range".to_string()),
+    ]
+}
+
+fn build_sweep_row(row: SweepRow, index: usize, fields: &[NumericField]) -> impl IntoView {
+    let field_options = fields
+        .iter()
+        .map(|field| (field.path.join("."), field.label.clone()))
+        .collect::<Vec<(String, String)>>();
+    let key = |name: &str| format!("sweep_{}_{}", index, name);
+
+    let values = move || match row.mode.get().as_str() {
+        "linear" | "log" => view! {
+            <FormField
+                label={"Start".to_string()}
+                key={key("start")}
+                input_type=InputType::Float { signal: row.start, min: None, max: None, step: None }
+            />
+            <FormField
+                label={"Stop".to_string()}
+                key={key("stop")}
+                input_type=InputType::Float { signal: row.stop, min: None, max: None, step: None }
+            />
+            <FormField
+                label={"Count".to_string()}
+                key={key("count")}
+                input_type=InputType::Integer {
+                    signal: row.count,
+                    min: Some(1),
+                    max: Some(MAX_SWEEP_JOBS as i64),
+                    step: Some(1)
+                }
+            />
+        }
+        .into_any(),
+        _ => view! {
+            <FormField
+                label={"Values".to_string()}
+                key={key("values")}
+                placeholder={"e.g. 100, 400, 1000".to_string()}
+                input_type=InputType::Text { signal: row.list }
+            />
+        }
+        .into_any(),
+    };
+
+    return view! {
+        <FormField
+            label={"Field".to_string()}
+            key={key("field")}
+            input_type=InputType::SelectText { options: field_options, signal: row.field }
+        />
+        <FormField
+            label={"Sweep".to_string()}
+            key={key("mode")}
+            input_type=InputType::SelectText { options: mode_options(), signal: row.mode }
+        />
+        {values}
+    };
+}
+
+/// Preview of the jobs the sweep generates, one row per case.
+fn build_preview(sweep: &ParameterSweep, cases: &[SweepCase], batch: &str) -> impl IntoView {
+    let mut col_def = vec![ColumnDefinition {
+        name: "#".to_string(),
+        data_type: CellType::Int,
+    }];
+    col_def.extend(sweep.parameters.iter().map(|parameter| ColumnDefinition {
+        name: parameter.name(),
+        data_type: match parameter.integer {
+            true => CellType::Int,
+            false => CellType::Float,
+        },
+    }));
+    col_def.push(ColumnDefinition {
+        name: "Job Name".to_string(),
+        data_type: CellType::Text,
+    });
+
+    let rows = cases
+        .iter()
+        .enumerate()
+        .map(|(index, case)| {
+            let mut row = vec![Cell::from((index + 1).to_string())];
+            row.extend(
+                case.values
+                    .iter()
+                    .map(|(_, value)| value.to_string().into()),
+            );
+            row.push(batch_job_name(batch, index, cases.len(), &case.label()).into());
+            row
+        })
+        .collect::<Vec<Vec<Cell>>>();
+
+    return view! {
+        <Table table={TableStruct {
+            name: format!("{} Jobs", cases.len()),
+            data: TableData { col_def, rows }
+        }} />
+    };
+}
+
+// -------------------------------------------------------------------------------------------------
+// Component
+// -------------------------------------------------------------------------------------------------
+
+/// Numeric fields of the form in `form_state` can be swept. The sweep described by the inputs is
+/// written to `sweep`, it is empty while no field is swept.
+#[component]
+pub fn ParameterSweepPanel(
+    form_state: RwSignal<Option<SchemaFormState>>,
+    sweep: RwSignal<Result<ParameterSweep, String>>,
+    #[prop(into)] batch: Signal<String>,
+) -> impl IntoView {
+    let rows: RwSignal<Vec<SweepRow>> = RwSignal::new(Vec::new());
+    let fields = Memo::new(move |_| {
+        form_state.with(|state| {
+            state
+                .as_ref()
+                .map(|state| state.numeric_fields())
+                .unwrap_or_default()
+        })
+    });
+
+    // a different form has different fields, so its sweep starts over
+    Effect::new(move |_| {
+        fields.track();
+        rows.set(Vec::new());
+    });
+
+    Effect::new(move |_| {
+        let parameters = fields.with(|fields| {
+            rows.get()
+                .into_iter()
+                .map(|row| row.to_parameter(fields))
+                .collect::<Result<Vec<SweepParameter>, String>>()
+        });
+        sweep.set(parameters.map(|parameters| ParameterSweep { parameters }));
+    });
+
+    let on_add_click = move || {
+        let field = fields.with_untracked(|fields| {
+            fields
+                .first()
+                .map(|field| field.path.join("."))
+                .unwrap_or_default()
+        });
+        rows.update(|rows| rows.push(SweepRow::new(field)));
+    };
+    let on_remove_click = move || {
+        rows.update(|rows| {
+            rows.pop();
+        });
+    };
+
+    let cases = Memo::new(move |_| {
+        sweep.with(|sweep| match sweep {
+            Ok(sweep) if sweep.is_empty() => Ok(None),
+            Ok(sweep) => sweep.cases().map(|cases| Some((sweep.clone(), cases))),
+            Err(e) => Err(e.clone()),
+        })
+    });
+    let preview_error = RwSignal::new(None::<String>);
+    Effect::new(move |_| preview_error.set(cases.with(|cases| cases.as_ref().err().cloned())));
+
+    let preview = move || {
+        cases.with(|cases| match cases {
+            Ok(Some((sweep, cases))) => Some(build_preview(sweep, cases, &batch.get())),
+            _ => None,
+        })
+    };
+
+    return view! {
+        <Stack size=Size::Sm>
+            {move || fields.with(|fields| {
+                rows.get()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, row)| view! {
+                        <Stack horizontal={true} align={FlexAlign::Center}>
+                            {build_sweep_row(row, index, fields)}
+                        </Stack>
+                    })
+                    .collect::<Vec<_>>()
+            })}
+            <Stack horizontal={true}>
+                <Button button_data={
+                    ButtonData::new()
+                        .size(Size::Sm)
+                        .variant(Variant::Secondary)
+                        .text("Sweep a field")
+                        .on_click(on_add_click)
+                } />
+                <Button button_data={
+                    ButtonData::new()
+                        .size(Size::Sm)
+                        .variant(Variant::Secondary)
+                        .text("Remove last")
+                        .on_click(on_remove_click)
+                } />
+            </Stack>
+            <ErrorAlert message={preview_error.read_only()} />
+            {preview}
+        </Stack>
+    };
+}
@@ -0,0 +1,446 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: residual_chart.rs
+ * description: Live log-scale residual convergence chart rendered as SVG
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use leptos::prelude::*;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::application::job_service::{JobService, ResourceTail};
+use crate::common::size::*;
+use crate::components::atoms::alert::*;
+use crate::components::atoms::button::*;
+use crate::components::atoms::layout::*;
+use crate::components::atoms::typography::*;
+use crate::domain::job_context::*;
+use crate::domain::residual_history::ResidualHistory;
+
+// -------------------------------------------------------------------------------------------------
+// Plot Geometry
+// -------------------------------------------------------------------------------------------------
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 400.0;
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const MARGIN_TOP: f64 = 16.0;
+const MARGIN_BOTTOM: f64 = 40.0;
+const PLOT_WIDTH: f64 = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+const PLOT_HEIGHT: f64 = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+
+/// Points drawn per series, longer histories are thinned out.
+const MAX_POINTS_PER_SERIES: usize = 2000;
+const ZOOM_STEP: f64 = 1.2;
+
+const SERIES_COLOURS: [&str; 8] = [
+    "#0891B2", "#10B981", "#D97706", "#DC2626", "#7C3AED", "#DB2777", "#65A30D", "#475569",
+];
+
+/// Gives each chart its own clip path id.
+static CHART_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+fn series_colour(index: usize) -> &'static str {
+    SERIES_COLOURS[index % SERIES_COLOURS.len()]
+}
+
+/// The visible region, iterations along x and log10 of the value along y.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PlotWindow {
+    x_min: f64,
+    x_max: f64,
+    y_min: f64,
+    y_max: f64,
+}
+
+impl PlotWindow {
+    /// Fits the visible series, rounding y out to whole decades. Non-positive values have no
+    /// logarithm and are skipped.
+    fn fit(history: &ResidualHistory, hidden: &HashSet<String>) -> Option<Self> {
+        let points = history
+            .series
+            .iter()
+            .filter(|series| !hidden.contains(&series.name))
+            .flat_map(|series| series.points.iter())
+            .filter(|(_, value)| *value > 0.0 && value.is_finite());
+
+        let mut window: Option<PlotWindow> = None;
+        for (x, value) in points {
+            let y = value.log10();
+            window = Some(match window {
+                None => PlotWindow {
+                    x_min: *x,
+                    x_max: *x,
+                    y_min: y,
+                    y_max: y,
+                },
+                Some(w) => PlotWindow {
+                    x_min: w.x_min.min(*x),
+                    x_max: w.x_max.max(*x),
+                    y_min: w.y_min.min(y),
+                    y_max: w.y_max.max(y),
+                },
+            });
+        }
+
+        window.map(|w| PlotWindow {
+            x_min: w.x_min,
+            x_max: if w.x_max > w.x_min {
+                w.x_max
+            } else {
+                w.x_min + 1.0
+            },
+            y_min: w.y_min.floor(),
+            y_max: if w.y_max.ceil() > w.y_min.floor() {
+                w.y_max.ceil()
+            } else {
+                w.y_min.floor() + 1.0
+            },
+        })
+    }
+
+    fn to_screen(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            MARGIN_LEFT + (x - self.x_min) / (self.x_max - self.x_min) * PLOT_WIDTH,
+            MARGIN_TOP + (self.y_max - y) / (self.y_max - self.y_min) * PLOT_HEIGHT,
+        )
+    }
+
+    fn to_data(&self, screen_x: f64, screen_y: f64) -> (f64, f64) {
+        (
+            self.x_min + (screen_x - MARGIN_LEFT) / PLOT_WIDTH * (self.x_max - self.x_min),
+            self.y_max - (screen_y - MARGIN_TOP) / PLOT_HEIGHT * (self.y_max - self.y_min),
+        )
+    }
+
+    /// Scales the window about an anchor point given in data coordinates.
+    fn zoom(&self, factor: f64, (x, y): (f64, f64)) -> Self {
+        PlotWindow {
+            x_min: x - (x - self.x_min) * factor,
+            x_max: x + (self.x_max - x) * factor,
+            y_min: y - (y - self.y_min) * factor,
+            y_max: y + (self.y_max - y) * factor,
+        }
+    }
+
+    /// Moves the window by a distance in screen units.
+    fn pan(&self, dx: f64, dy: f64) -> Self {
+        let shift_x = dx / PLOT_WIDTH * (self.x_max - self.x_min);
+        let shift_y = dy / PLOT_HEIGHT * (self.y_max - self.y_min);
+        PlotWindow {
+            x_min: self.x_min - shift_x,
+            x_max: self.x_max - shift_x,
+            y_min: self.y_min + shift_y,
+            y_max: self.y_max + shift_y,
+        }
+    }
+}
+
+/// Roughly `count` evenly spaced ticks on 1, 2 or 5 times a power of ten.
+fn linear_ticks(min: f64, max: f64, count: usize) -> Vec<f64> {
+    let raw_step = (max - min) / count as f64;
+    let magnitude = 10f64.powf(raw_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|multiple| multiple * magnitude)
+        .find(|step| *step >= raw_step)
+        .unwrap_or(10.0 * magnitude);
+
+    let first = (min / step).ceil() as i64;
+    let last = (max / step).floor() as i64;
+    (first..=last).map(|tick| tick as f64 * step).collect()
+}
+
+/// Whole decades inside the window, thinned out to at most `count`.
+fn decade_ticks(min: f64, max: f64, count: usize) -> Vec<i32> {
+    let first = min.ceil() as i32;
+    let last = max.floor() as i32;
+    let stride = ((last - first) as usize / count.max(1)).max(1);
+    (first..=last).step_by(stride).collect()
+}
+
+fn format_iteration(value: f64) -> String {
+    match value.abs() >= 1e5 || (value != 0.0 && value.abs() < 1e-2) {
+        true => format!("{:.1e}", value),
+        false => format!("{}", (value * 1000.0).round() / 1000.0),
+    }
+}
+
+fn polyline_points(window: &PlotWindow, points: &[(f64, f64)]) -> String {
+    let stride = points.len().div_ceil(MAX_POINTS_PER_SERIES).max(1);
+    points
+        .iter()
+        .step_by(stride)
+        .filter(|(_, value)| *value > 0.0 && value.is_finite())
+        .map(|(x, value)| {
+            let (screen_x, screen_y) = window.to_screen(*x, value.log10());
+            format!("{:.1},{:.1}", screen_x, screen_y)
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// -------------------------------------------------------------------------------------------------
+// Components
+// -------------------------------------------------------------------------------------------------
+
+/// Log-scale line plot of residual histories. Scroll to zoom about the cursor, drag to pan, the
+/// plot follows new data until it is zoomed or panned.
+#[component]
+pub fn ResidualChart(history: Signal<ResidualHistory>) -> impl IntoView {
+    let clip_id = format!(
+        "residual-chart-clip-{}",
+        CHART_COUNT.fetch_add(1, Ordering::Relaxed)
+    );
+    let hidden = RwSignal::new(HashSet::<String>::new());
+    let manual_window = RwSignal::new(None::<PlotWindow>);
+    let drag_from = RwSignal::new(None::<(f64, f64)>);
+    let svg_ref = NodeRef::<leptos::svg::Svg>::new();
+
+    let window = Memo::new(move |_| match manual_window.get() {
+        Some(window) => Some(window),
+        None => hidden.with(|hidden| history.with(|history| PlotWindow::fit(history, hidden))),
+    });
+
+    // converts a pointer position to SVG units, the SVG scales uniformly with its width.
+    let to_svg_units = move |client_x: f64, client_y: f64| {
+        let rect = svg_ref.get_untracked()?.get_bounding_client_rect();
+        let scale = WIDTH / rect.width();
+        Some((
+            (client_x - rect.left()) * scale,
+            (client_y - rect.top()) * scale,
+        ))
+    };
+
+    let on_wheel = move |ev: leptos::ev::WheelEvent| {
+        ev.prevent_default();
+        let (Some(current), Some(pointer)) = (
+            window.get_untracked(),
+            to_svg_units(ev.client_x() as f64, ev.client_y() as f64),
+        ) else {
+            return;
+        };
+        let factor = if ev.delta_y() > 0.0 {
+            ZOOM_STEP
+        } else {
+            1.0 / ZOOM_STEP
+        };
+        manual_window.set(Some(
+            current.zoom(factor, current.to_data(pointer.0, pointer.1)),
+        ));
+    };
+
+    let on_mouse_move = move |ev: leptos::ev::MouseEvent| {
+        let (Some(from), Some(current)) = (drag_from.get_untracked(), window.get_untracked())
+        else {
+            return;
+        };
+        if let Some(to) = to_svg_units(ev.client_x() as f64, ev.client_y() as f64) {
+            manual_window.set(Some(current.pan(to.0 - from.0, to.1 - from.1)));
+            drag_from.set(Some(to));
+        }
+    };
+
+    let reset_button = ButtonData::new()
+        .size(Size::Sm)
+        .variant(Variant::Secondary)
+        .text("Reset view")
+        .on_click(move || manual_window.set(None));
+
+    let axes = move || {
+        window.get().map(|window| {
+            let x_ticks = linear_ticks(window.x_min, window.x_max, 6)
+                .into_iter()
+                .map(|tick| {
+                    let (x, _) = window.to_screen(tick, window.y_min);
+                    view! {
+                        <line x1=x x2=x y1=MARGIN_TOP y2=MARGIN_TOP + PLOT_HEIGHT
+                            class="stroke-surface-200 dark:stroke-surface-800" />
+                        <text x=x y=HEIGHT - MARGIN_BOTTOM + 18.0 text-anchor="middle"
+                            class="fill-current text-xs">{format_iteration(tick)}</text>
+                    }
+                })
+                .collect::<Vec<_>>();
+            let y_ticks = decade_ticks(window.y_min, window.y_max, 8)
+                .into_iter()
+                .map(|decade| {
+                    let (_, y) = window.to_screen(window.x_min, decade as f64);
+                    view! {
+                        <line x1=MARGIN_LEFT x2=MARGIN_LEFT + PLOT_WIDTH y1=y y2=y
+                            class="stroke-surface-200 dark:stroke-surface-800" />
+                        <text x=MARGIN_LEFT - 8.0 y=y + 4.0 text-anchor="end"
+                            class="fill-current text-xs">{format!("1e{}", decade)}</text>
+                    }
+                })
+                .collect::<Vec<_>>();
+            view! { {x_ticks}{y_ticks} }
+        })
+    };
+
+    let lines = move || {
+        let window = window.get()?;
+        let hidden = hidden.get();
+        let lines = history.with(|history| {
+            history
+                .series
+                .iter()
+                .enumerate()
+                .filter(|(_, series)| !hidden.contains(&series.name))
+                .map(|(index, series)| {
+                    view! {
+                        <polyline points=polyline_points(&window, &series.points) fill="none"
+                            stroke=series_colour(index) stroke-width="1.5" />
+                    }
+                })
+                .collect::<Vec<_>>()
+        });
+        Some(lines)
+    };
+
+    let legend = move || {
+        history.with(|history| {
+            history
+                .series
+                .iter()
+                .enumerate()
+                .map(|(index, series)| {
+                    let name = series.name.clone();
+                    let toggle_name = series.name.clone();
+                    view! {
+                        <label class=format!("flex items-center gap-1 {} {}", NORMAL_CLASS, FONT_CLR)>
+                            <input
+                                type="checkbox"
+                                prop:checked=move || hidden.with(|hidden| !hidden.contains(&name))
+                                on:change=move |_| hidden.update(|hidden| {
+                                    if !hidden.remove(&toggle_name) {
+                                        hidden.insert(toggle_name.clone());
+                                    }
+                                })
+                            />
+                            <span class="inline-block w-3 h-3 rounded-sm"
+                                style=format!("background-color: {}", series_colour(index))></span>
+                            {series.name.clone()}
+                        </label>
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let x_label = move || history.with(|history| history.iteration_column.clone());
+
+    return view! {
+        <Stack size=Size::Sm>
+            <svg
+                node_ref=svg_ref
+                viewBox=format!("0 0 {} {}", WIDTH, HEIGHT)
+                class=format!("w-full h-auto select-none cursor-grab {} {}", FONT_CLR, standard_border(None))
+                on:wheel=on_wheel
+                on:mousedown=move |ev| drag_from.set(to_svg_units(ev.client_x() as f64, ev.client_y() as f64))
+                on:mousemove=on_mouse_move
+                on:mouseup=move |_| drag_from.set(None)
+                on:mouseleave=move |_| drag_from.set(None)
+            >
+                <defs>
+                    <clipPath id=clip_id.clone()>
+                        <rect x=MARGIN_LEFT y=MARGIN_TOP width=PLOT_WIDTH height=PLOT_HEIGHT />
+                    </clipPath>
+                </defs>
+                {axes}
+                <g clip-path=format!("url(#{})", clip_id)>{lines}</g>
+                <rect x=MARGIN_LEFT y=MARGIN_TOP width=PLOT_WIDTH height=PLOT_HEIGHT fill="none"
+                    class="stroke-surface-500" />
+                <text x=MARGIN_LEFT + PLOT_WIDTH / 2.0 y=HEIGHT - 6.0 text-anchor="middle"
+                    class="fill-current text-sm">{x_label}</text>
+            </svg>
+            <Stack horizontal=true align=FlexAlign::Center size=Size::Md add_class="flex-wrap".to_string()>
+                {legend}
+                <Button button_data=reset_button />
+            </Stack>
+        </Stack>
+    };
+}
+
+/// Where a job's residuals are read from: a result file that looks like a residual table, or
+/// failing that the job's log.
+fn residual_source(resources: &[JobResource]) -> Option<JobResource> {
+    let is_residual_table = |resource: &&JobResource| {
+        let name = resource.file_name().to_lowercase();
+        resource.resource_type == ResourceType::Result
+            && (name.contains("residual") || name.ends_with(".csv") || name.ends_with(".dat"))
+    };
+    resources
+        .iter()
+        .find(is_residual_table)
+        .or_else(|| {
+            resources
+                .iter()
+                .find(|resource| resource.resource_type == ResourceType::Log)
+        })
+        .cloned()
+}
+
+#[component]
+fn ResidualFeed(
+    resource: JobResource,
+    follow: Signal<bool>,
+    log_tail: Option<ResourceTail>,
+) -> impl IntoView {
+    // reading the residuals from a log that is already tailed shares its fetches
+    let tail = match log_tail.filter(|tail| tail.resource_id == resource.id) {
+        Some(tail) => tail,
+        None => JobService::tail_resource(resource, follow),
+    };
+    let history = JobService::tail_residuals(tail);
+    // the chart is mounted once, keeping its zoom and hidden series as the history grows
+    let is_empty = Memo::new(move |_| history.with(|history| history.is_empty()));
+
+    return view! {
+        <ErrorAlert message=tail.error.read_only() size=Size::Sm />
+        {move || match is_empty.get() {
+            true => view! { <P>"Waiting for residual data..."</P> }.into_any(),
+            false => view! { <ResidualChart history=history.into() /> }.into_any(),
+        }}
+    };
+}
+
+/// Residual convergence of a job, updating live while the job runs. When the residuals are read
+/// from the job's log, a `log_tail` of that log is used instead of tailing it a second time.
+#[component]
+pub fn ResidualMonitor(
+    job: Signal<Option<JobInfo>>,
+    #[prop(default = None)] log_tail: Option<ResourceTail>,
+) -> impl IntoView {
+    let resources = JobService::get_job_resources(job);
+    let source = Memo::new(move |_| residual_source(&resources.get().flatten()?));
+    let follow = Signal::derive(move || {
+        job.with(|job| {
+            job.as_ref()
+                .is_some_and(|job| job.status == JobStatus::Running)
+        })
+    });
+
+    return move || match source.get() {
+        Some(resource) => {
+            view! { <ResidualFeed resource=resource follow=follow log_tail=log_tail /> }.into_any()
+        }
+        None => view! { <P>"No residual data available for this job."</P> }.into_any(),
+    };
+}
@@ -31,6 +31,7 @@ use crate::components::molecules::form_field::*;
 use crate::components::molecules::section::*;
 use crate::domain::user_context::UserContext;
 use crate::infrastructure::fyn_api_client::FynApiClient;
+use crate::infrastructure::fyn_api_error::FynApiError;
 
 #[derive(Clone)]
 struct RegisterForm {
@@ -103,6 +104,21 @@ impl RegisterForm {
     }
 }
 
+/// Maps backend serializer field names onto the labels shown in the form. Keys that are not a form
+/// field, such as `non_field_errors` or `detail`, have no label.
+fn field_label(field: &str) -> Option<&'static str> {
+    match field {
+        "first_name" => Some("First Name"),
+        "last_name" => Some("Last Name"),
+        "username" => Some("Username"),
+        "email" => Some("Email"),
+        "password" => Some("Password"),
+        "company" => Some("Company"),
+        "country" => Some("Country"),
+        _ => None,
+    }
+}
+
 #[component]
 pub fn UserRegisterForm() -> impl IntoView {
     let fyn_api_client = use_context::<FynApiClient>().expect("FynApiClient should be provided");
@@ -135,6 +151,16 @@ pub fn UserRegisterForm() -> impl IntoView {
                     Ok(_) => {
                         nav_fn("/sign_in", Default::default()); // Navigate to login after successful registration
                     }
+                    Err(FynApiError::Validation(errors)) => {
+                        let messages = errors
+                            .iter()
+                            .map(|(field, msgs)| match field_label(field) {
+                                Some(label) => format!("{}: {}", label, msgs.join(" ")),
+                                None => msgs.join(" "),
+                            })
+                            .collect::<Vec<String>>();
+                        form.set_error(format!("Please correct: {}", messages.join("; ")));
+                    }
                     Err(error) => {
                         form.set_error(format!("Registration failed: {}", error));
                     }
@@ -30,6 +30,7 @@ use crate::components::molecules::form_field::*;
 use crate::components::molecules::section::*;
 use crate::domain::user_context::UserContext;
 use crate::infrastructure::fyn_api_client::FynApiClient;
+use crate::infrastructure::fyn_api_error::FynApiError;
 
 #[component]
 pub fn UserSignIn() -> impl IntoView {
@@ -75,6 +76,11 @@ pub fn UserSignIn() -> impl IntoView {
                         user_signal.set(Some(user_data));
                         nav_fn("/", Default::default());
                     }
+                    Err(FynApiError::Unauthorized(_)) => {
+                        error_signal.set(Some(
+                            "Login failed: invalid username or password".to_string(),
+                        ));
+                    }
                     Err(error) => {
                         error_signal.set(Some(format!("Login failed: {}", error)));
                     }
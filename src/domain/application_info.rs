@@ -53,4 +53,34 @@ impl AppInfo {
             schema: None,
         }
     }
+
+    /// Executables the application can be run with: its registered file path followed by any
+    /// alternatives its schema lists under the `x-entry-points` extension keyword.
+    pub fn entry_points(&self) -> Vec<String> {
+        let mut entry_points = vec![self.file_path.clone()];
+        let declared = self
+            .schema
+            .as_ref()
+            .and_then(|schema| schema["x-entry-points"].as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry_point| entry_point.as_str());
+        for entry_point in declared {
+            if !entry_points.iter().any(|existing| existing == entry_point) {
+                entry_points.push(entry_point.to_string());
+            }
+        }
+        entry_points.retain(|entry_point| !entry_point.is_empty());
+        entry_points
+    }
+
+    /// Command-line template the schema suggests with the `x-args` extension keyword, e.g.
+    /// `--threads {{n_procs}}`.
+    pub fn default_args(&self) -> String {
+        self.schema
+            .as_ref()
+            .and_then(|schema| schema["x-args"].as_str())
+            .unwrap_or_default()
+            .to_string()
+    }
 }
@@ -0,0 +1,107 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: command_line.rs
+ * description: Command-line argument templates, split like a shell and filled from a job's config
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use serde_json::Value;
+
+/// Splits a command line into arguments on whitespace. Single and double quotes group words and
+/// a backslash escapes the next character, e.g. `--title "Re 100"` gives two arguments. Templates
+/// such as `{{ n_procs }}` are never split.
+pub fn split_args(text: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"') | None, '\\') => {
+                let escaped = chars
+                    .next()
+                    .ok_or("The command line ends with a lone '\\'")?;
+                current.get_or_insert_with(String::new).push(escaped);
+            }
+            (Some(_), c) => current.get_or_insert_with(String::new).push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, '{') if chars.as_str().starts_with('{') => {
+                // a template is kept whole, spaces inside the braces included
+                let rest = chars.as_str();
+                let end = rest.find("}}").map(|end| end + 2).unwrap_or(rest.len());
+                let arg = current.get_or_insert_with(String::new);
+                arg.push(c);
+                arg.push_str(&rest[..end]);
+                chars = rest[end..].chars();
+            }
+            (None, c) if c.is_whitespace() => args.extend(current.take()),
+            (None, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    match quote {
+        Some(q) => Err(format!("The command line has an unclosed {} quote", q)),
+        None => {
+            args.extend(current);
+            Ok(args)
+        }
+    }
+}
+
+/// Replaces each `{{path}}` in `arg` with the config value at the dotted `path`, e.g.
+/// `--threads={{solver.n_procs}}`. Strings are inserted as they are, other values as JSON.
+pub fn fill_template(arg: &str, config: &Value) -> Result<String, String> {
+    let mut filled = String::new();
+    let mut rest = arg;
+    while let Some(start) = rest.find("{{") {
+        let end = rest[start..]
+            .find("}}")
+            .map(|end| start + end)
+            .ok_or(format!("'{}' has an unclosed {{{{", arg))?;
+        let path = rest[start + 2..end].trim();
+        let value = path
+            .split('.')
+            .try_fold(config, |value, key| value.get(key))
+            .filter(|value| !value.is_null())
+            .ok_or(format!("'{}' is not set in the config", path))?;
+
+        filled.push_str(&rest[..start]);
+        match value {
+            Value::String(text) => filled.push_str(text),
+            other => filled.push_str(&other.to_string()),
+        }
+        rest = &rest[end + 2..];
+    }
+    filled.push_str(rest);
+    Ok(filled)
+}
+
+/// The arguments of a command line with their templates filled from `config`, as the JSON list of
+/// strings a job expects.
+pub fn build_args(text: &str, config: &Value) -> Result<Value, String> {
+    split_args(text)?
+        .iter()
+        .map(|arg| fill_template(arg, config).map(Value::String))
+        .collect::<Result<Vec<Value>, String>>()
+        .map(Value::Array)
+}
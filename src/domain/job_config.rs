@@ -0,0 +1,151 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: job_config.rs
+ * description: Shareable job setup and named presets of an application's config
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Version of the exported file layout, bumped when fields change meaning.
+pub const JOB_CONFIG_VERSION: u32 = 1;
+
+/// Everything needed to set up a job again: the target application, the job's name and priority
+/// and the config built from the application's schema.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobConfig {
+    pub version: u32,
+    pub application_id: Uuid,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub priority: i64,
+    #[serde(default)]
+    pub config: Value,
+}
+
+impl JobConfig {
+    pub fn new(application_id: Uuid, name: String, priority: i64, config: Value) -> Self {
+        Self {
+            version: JOB_CONFIG_VERSION,
+            application_id,
+            name,
+            priority,
+            config,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// Reads an exported file, rejecting versions newer than this client understands.
+    pub fn from_json_str(text: &str) -> Result<Self, String> {
+        let job_config: JobConfig = serde_json::from_str(text)
+            .map_err(|e| format!("Not a valid job config file: {}", e))?;
+        if job_config.version > JOB_CONFIG_VERSION {
+            return Err(format!(
+                "The job config file was written by a newer version (format {}), please update",
+                job_config.version
+            ));
+        }
+        Ok(job_config)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Presets
+// -------------------------------------------------------------------------------------------------
+
+/// A named config kept for reuse, e.g. a base case that individual jobs start from.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobPreset {
+    pub name: String,
+    #[serde(default)]
+    pub config: Value,
+}
+
+/// The presets saved for one application, also the layout of an exported presets file.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobPresets {
+    pub version: u32,
+    pub application_id: Uuid,
+    #[serde(default)]
+    pub presets: Vec<JobPreset>,
+}
+
+impl JobPresets {
+    pub fn new(application_id: Uuid) -> Self {
+        Self {
+            version: JOB_CONFIG_VERSION,
+            application_id,
+            presets: Vec::new(),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&JobPreset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+
+    /// Adds the preset, replacing any existing preset of the same name.
+    pub fn insert(&mut self, preset: JobPreset) {
+        match self.presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.presets.retain(|preset| preset.name != name);
+    }
+
+    /// Merges imported presets in, those with matching names are overwritten. Returns how many
+    /// presets were taken over.
+    pub fn merge(&mut self, other: JobPresets) -> Result<usize, String> {
+        if other.application_id != self.application_id {
+            return Err(format!(
+                "The presets are for a different application ({})",
+                other.application_id
+            ));
+        }
+        let count = other.presets.len();
+        for preset in other.presets {
+            self.insert(preset);
+        }
+        Ok(count)
+    }
+
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// Reads stored or exported presets, rejecting versions newer than this client understands.
+    pub fn from_json_str(text: &str) -> Result<Self, String> {
+        let presets: JobPresets =
+            serde_json::from_str(text).map_err(|e| format!("Not a valid presets file: {}", e))?;
+        if presets.version > JOB_CONFIG_VERSION {
+            return Err(format!(
+                "The presets file was written by a newer version (format {}), please update",
+                presets.version
+            ));
+        }
+        Ok(presets)
+    }
+}
@@ -22,10 +22,13 @@
 
 use std::fmt;
 
+use chrono::{DateTime, Utc};
 use serde_json::Value;
 use std::path::PathBuf;
 use uuid::Uuid;
 
+use crate::domain::runner_info::RunnerInfo;
+
 // -------------------------------------------------------------------------------------------------
 // Job Related Enums
 // -------------------------------------------------------------------------------------------------
@@ -72,7 +75,133 @@ impl fmt::Display for JobStatus {
     }
 }
 
+impl JobStatus {
+    /// Succeeded or failed, the runner will not touch the job again.
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Succeeded
+                | JobStatus::Failed
+                | JobStatus::FailedResourceError
+                | JobStatus::FailedTerminated
+                | JobStatus::FailedTimeout
+                | JobStatus::FailedRunnerException
+        )
+    }
+
+    /// Whether the user may move a job from this status to `next`, all other transitions belong
+    /// to the runner.
+    pub fn can_transition_to(&self, next: JobStatus) -> bool {
+        match next {
+            JobStatus::Queued => *self == JobStatus::UploadingInputResources,
+            JobStatus::FailedTerminated => !self.is_finished(),
+            JobStatus::Paused => *self == JobStatus::Running,
+            JobStatus::Running => *self == JobStatus::Paused,
+            _ => false,
+        }
+    }
+}
+
+/// Actions a user can take on an existing job.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JobAction {
+    Cancel,
+    Pause,
+    Resume,
+    Resubmit,
+}
+
+impl fmt::Display for JobAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobAction::Cancel => write!(f, "Cancel"),
+            JobAction::Pause => write!(f, "Pause"),
+            JobAction::Resume => write!(f, "Resume"),
+            JobAction::Resubmit => write!(f, "Resubmit"),
+        }
+    }
+}
+
+impl JobAction {
+    pub const ALL: [JobAction; 4] = [
+        JobAction::Cancel,
+        JobAction::Pause,
+        JobAction::Resume,
+        JobAction::Resubmit,
+    ];
+
+    /// The status the action patches onto the job, resubmitting creates a new job instead.
+    pub fn target_status(&self) -> Option<JobStatus> {
+        match self {
+            JobAction::Cancel => Some(JobStatus::FailedTerminated),
+            JobAction::Pause => Some(JobStatus::Paused),
+            JobAction::Resume => Some(JobStatus::Running),
+            JobAction::Resubmit => None,
+        }
+    }
+}
+
+/// Operations applied to many jobs at once, e.g. to clean up after a failed sweep.
 #[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BulkOperation {
+    Cancel,
+    /// Deletes the job and its files. With `keep_results` its output and result files are
+    /// downloaded first.
+    Delete {
+        keep_results: bool,
+    },
+    /// Resubmits finished jobs and queues jobs still waiting for their inputs.
+    Requeue,
+    Reprioritise(i64),
+}
+
+impl fmt::Display for BulkOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BulkOperation::Cancel => write!(f, "Cancel"),
+            BulkOperation::Delete { .. } => write!(f, "Delete"),
+            BulkOperation::Requeue => write!(f, "Re-queue"),
+            BulkOperation::Reprioritise(priority) => write!(f, "Set priority to {}", priority),
+        }
+    }
+}
+
+impl BulkOperation {
+    /// What the operation will change on a job, or why the job is skipped.
+    pub fn plan(&self, job: &JobInfo) -> Result<String, String> {
+        match *self {
+            BulkOperation::Cancel if job.status.can_transition_to(JobStatus::FailedTerminated) => {
+                Ok(format!("{} -> {}", job.status, JobStatus::FailedTerminated))
+            }
+            BulkOperation::Cancel => Err(format!("Already finished ({})", job.status)),
+            BulkOperation::Delete { .. } if !job.can_delete() => {
+                Err(format!("Still in progress ({})", job.status))
+            }
+            BulkOperation::Delete { keep_results } => Ok(match keep_results {
+                true => "Results are downloaded, then job and files deleted".to_string(),
+                false => "Job and all of its files are deleted".to_string(),
+            }),
+            BulkOperation::Requeue if job.status.is_finished() => {
+                Ok("Resubmitted as a new job".to_string())
+            }
+            BulkOperation::Requeue if job.status.can_transition_to(JobStatus::Queued) => {
+                Ok(format!("{} -> {}", job.status, JobStatus::Queued))
+            }
+            BulkOperation::Requeue => Err(format!("Still in progress ({})", job.status)),
+            BulkOperation::Reprioritise(_) if job.status.is_finished() => {
+                Err(format!("Already finished ({})", job.status))
+            }
+            BulkOperation::Reprioritise(priority) if priority == job.priority => {
+                Err(format!("Priority is already {}", priority))
+            }
+            BulkOperation::Reprioritise(priority) => {
+                Ok(format!("Priority {} -> {}", job.priority, priority))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ResourceType {
     Input,
     Output,
@@ -82,11 +211,48 @@ pub enum ResourceType {
     Result,
 }
 
+impl ResourceType {
+    /// Order in which resources are presented, following the lifecycle of a job.
+    pub const DISPLAY_ORDER: [ResourceType; 6] = [
+        ResourceType::Input,
+        ResourceType::Config,
+        ResourceType::Log,
+        ResourceType::Output,
+        ResourceType::Result,
+        ResourceType::Temp,
+    ];
+
+    /// Files a job produced, downloaded before the job is deleted with its results kept.
+    pub fn is_result(&self) -> bool {
+        matches!(self, ResourceType::Output | ResourceType::Result)
+    }
+}
+
+impl fmt::Display for ResourceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceType::Input => write!(f, "Input"),
+            ResourceType::Output => write!(f, "Output"),
+            ResourceType::Config => write!(f, "Config"),
+            ResourceType::Log => write!(f, "Log"),
+            ResourceType::Temp => write!(f, "Temp"),
+            ResourceType::Result => write!(f, "Result"),
+        }
+    }
+}
+
+/// A status observed by the front end, the backend only reports the current status.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JobStatusChange {
+    pub status: JobStatus,
+    pub observed_at: DateTime<Utc>,
+}
+
 // -------------------------------------------------------------------------------------------------
 // JobInfo
 // -------------------------------------------------------------------------------------------------
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct JobInfo {
     pub id: Uuid,
     pub name: String,
@@ -98,6 +264,7 @@ pub struct JobInfo {
     pub command_line_args: Option<Value>, // needs to be a list of values as the root node
     pub exit_code: Option<i64>,
     pub resources: Vec<Uuid>,
+    pub status_history: Vec<JobStatusChange>,
 }
 
 #[allow(dead_code)]
@@ -183,13 +350,94 @@ impl JobInfo {
         self.id = id;
         Ok(())
     }
+
+    /// Moves the job to `next` if the user is allowed to make that transition.
+    pub fn transition_to(&mut self, next: JobStatus) -> Result<(), String> {
+        if !self.status.can_transition_to(next) {
+            return Err(format!(
+                "Job cannot move from '{}' to '{}'",
+                self.status, next
+            ));
+        }
+        self.status = next;
+        Ok(())
+    }
+
+    /// Jobs are only deleted before they are queued or once finished, a runner may be working on
+    /// any other.
+    pub fn can_delete(&self) -> bool {
+        self.status == JobStatus::UploadingInputResources || self.status.is_finished()
+    }
+
+    /// Whether an action applies to the job in its current status. Pausing and resuming are
+    /// carried out by the assigned runner, the backend does not advertise which runners support
+    /// it, so they are only offered while that runner is reachable.
+    pub fn allows(&self, action: JobAction, runner: Option<&RunnerInfo>) -> bool {
+        match action {
+            JobAction::Cancel => self.status.can_transition_to(JobStatus::FailedTerminated),
+            JobAction::Pause => {
+                self.is_runner_reachable(runner) && self.status.can_transition_to(JobStatus::Paused)
+            }
+            JobAction::Resume => {
+                self.is_runner_reachable(runner)
+                    && self.status.can_transition_to(JobStatus::Running)
+            }
+            JobAction::Resubmit => self.status.is_finished(),
+        }
+    }
+
+    fn is_runner_reachable(&self, runner: Option<&RunnerInfo>) -> bool {
+        runner
+            .filter(|runner| Some(runner.id) == self.runner_id)
+            .is_some_and(|runner| runner.is_reachable())
+    }
+
+    pub fn available_actions(&self, runner: Option<&RunnerInfo>) -> Vec<JobAction> {
+        JobAction::ALL
+            .into_iter()
+            .filter(|action| self.allows(*action, runner))
+            .collect()
+    }
+
+    /// A new, unsubmitted job with the same application, runner and arguments as this finished
+    /// one. Resources are not carried over, they belong to the original job.
+    pub fn resubmission(&self) -> Result<JobInfo, String> {
+        if !self.status.is_finished() {
+            return Err(format!(
+                "Only finished jobs can be resubmitted, '{}' is {}",
+                self.name, self.status
+            ));
+        }
+        JobInfo::new()
+            .name(self.name.clone())
+            .application_id(self.application_id)
+            .priority(self.priority)
+            .maybe_runner_id(self.runner_id)
+            .executable(self.executable.clone())
+            .maybe_command_line_args(&self.command_line_args)
+            .build()
+    }
+
+    /// Carries over the status history of a previously fetched copy of this job and records the
+    /// current status if it differs from the last one observed.
+    pub fn merge_status_history(&mut self, previous: Option<&JobInfo>) {
+        if let Some(previous) = previous {
+            self.status_history = previous.status_history.clone();
+        }
+        if self.status_history.last().map(|change| change.status) != Some(self.status) {
+            self.status_history.push(JobStatusChange {
+                status: self.status,
+                observed_at: Utc::now(),
+            });
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
 // JobResource
 // -------------------------------------------------------------------------------------------------
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[allow(dead_code)]
 pub struct JobResource {
     pub id: Uuid,
@@ -223,6 +471,19 @@ impl JobResource {
         self.id = id;
         Ok(())
     }
+
+    /// Name to save a downloaded copy under, falling back to the last segment of the file URL.
+    pub fn file_name(&self) -> String {
+        match self.original_file_path.file_name() {
+            Some(name) => name.to_string_lossy().to_string(),
+            None => self
+                .file
+                .rsplit('/')
+                .find(|segment| !segment.is_empty())
+                .unwrap_or("resource")
+                .to_string(),
+        }
+    }
 }
 
 // -------------------------------------------------------------------------------------------------
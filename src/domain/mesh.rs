@@ -0,0 +1,287 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: mesh.rs
+ * description: Triangulated surface meshes for the 3D viewer
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use std::collections::HashSet;
+use std::fmt;
+
+// -------------------------------------------------------------------------------------------------
+// Mesh Format
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MeshFormat {
+    Stl,
+    Obj,
+    Vtk,
+    Vtu,
+}
+
+impl MeshFormat {
+    /// Guesses the format from a file extension.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let extension = file_name.rsplit_once('.')?.1.to_ascii_lowercase();
+        match extension.as_str() {
+            "stl" => Some(MeshFormat::Stl),
+            "obj" => Some(MeshFormat::Obj),
+            "vtk" => Some(MeshFormat::Vtk),
+            "vtu" => Some(MeshFormat::Vtu),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MeshFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshFormat::Stl => write!(f, "STL"),
+            MeshFormat::Obj => write!(f, "OBJ"),
+            MeshFormat::Vtk => write!(f, "VTK"),
+            MeshFormat::Vtu => write!(f, "VTU"),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Bounding Box
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+impl BoundingBox {
+    pub fn center(&self) -> [f32; 3] {
+        [0, 1, 2].map(|axis| 0.5 * (self.min[axis] + self.max[axis]))
+    }
+
+    /// Half the length of the diagonal, the radius of a sphere enclosing the box.
+    pub fn radius(&self) -> f32 {
+        let diagonal = [0, 1, 2].map(|axis| self.max[axis] - self.min[axis]);
+        0.5 * length(diagonal)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Mesh Fields
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldLocation {
+    Point,
+    Cell,
+}
+
+impl fmt::Display for FieldLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldLocation::Point => write!(f, "point"),
+            FieldLocation::Cell => write!(f, "cell"),
+        }
+    }
+}
+
+/// A result array on the mesh. Point fields hold a tuple per vertex; cell fields hold a tuple per
+/// triangle, copied from the cell the triangle was cut from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeshField {
+    pub name: String,
+    pub location: FieldLocation,
+    pub components: usize,
+    pub values: Vec<f32>,
+}
+
+impl MeshField {
+    /// One scalar per tuple, vectors and tensors are reduced to their magnitude.
+    pub fn scalars(&self) -> Vec<f32> {
+        match self.components {
+            1 => self.values.clone(),
+            components => self
+                .values
+                .chunks_exact(components)
+                .map(|tuple| tuple.iter().map(|value| value * value).sum::<f32>().sqrt())
+                .collect(),
+        }
+    }
+
+    /// Smallest and largest finite scalar.
+    pub fn range(&self) -> Option<(f32, f32)> {
+        self.scalars()
+            .into_iter()
+            .filter(|value| value.is_finite())
+            .fold(None, |range, value| match range {
+                None => Some((value, value)),
+                Some((min, max)) => Some((min.min(value), max.max(value))),
+            })
+    }
+
+    pub fn label(&self) -> String {
+        match self.components {
+            1 => format!("{} ({})", self.name, self.location),
+            _ => format!("|{}| ({})", self.name, self.location),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Mesh
+// -------------------------------------------------------------------------------------------------
+
+/// An indexed triangle surface. Vertices may be shared between triangles (OBJ) or repeated per
+/// triangle (STL), shared vertices shade smoothly and repeated ones shade flat.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mesh {
+    pub positions: Vec<[f32; 3]>,
+    pub triangles: Vec<[u32; 3]>,
+    pub fields: Vec<MeshField>,
+}
+
+impl Mesh {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        let first = *self.positions.first()?;
+        Some(self.positions.iter().fold(
+            BoundingBox {
+                min: first,
+                max: first,
+            },
+            |bounds, position| BoundingBox {
+                min: [0, 1, 2].map(|axis| bounds.min[axis].min(position[axis])),
+                max: [0, 1, 2].map(|axis| bounds.max[axis].max(position[axis])),
+            },
+        ))
+    }
+
+    /// Per-vertex normals, averaged over the triangles sharing each vertex and weighted by their
+    /// area.
+    pub fn vertex_normals(&self) -> Vec<[f32; 3]> {
+        let mut normals = vec![[0.0f32; 3]; self.positions.len()];
+        for triangle in &self.triangles {
+            let [a, b, c] = triangle.map(|index| self.positions[index as usize]);
+            // the cross product's length is twice the triangle's area.
+            let normal = cross(subtract(b, a), subtract(c, a));
+            for index in triangle {
+                let sum = &mut normals[*index as usize];
+                *sum = [0, 1, 2].map(|axis| sum[axis] + normal[axis]);
+            }
+        }
+        normals.into_iter().map(normalise).collect()
+    }
+
+    /// A field's scalar at each triangle corner, three per triangle in triangle order.
+    pub fn corner_scalars(&self, field: &MeshField) -> Vec<f32> {
+        let scalars = field.scalars();
+        match field.location {
+            FieldLocation::Point => self
+                .triangles
+                .iter()
+                .flatten()
+                .map(|index| scalars[*index as usize])
+                .collect(),
+            FieldLocation::Cell => scalars.iter().flat_map(|value| [*value; 3]).collect(),
+        }
+    }
+
+    /// Each triangle edge once, for drawing the mesh as a wireframe.
+    pub fn edges(&self) -> Vec<[u32; 2]> {
+        let mut seen = HashSet::new();
+        self.triangles
+            .iter()
+            .flat_map(|[a, b, c]| [[*a, *b], [*b, *c], [*c, *a]])
+            .filter(|[a, b]| seen.insert((*a.min(b), *a.max(b))))
+            .collect()
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Vector Helpers
+// -------------------------------------------------------------------------------------------------
+
+fn subtract(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length(a: [f32; 3]) -> f32 {
+    (a[0] * a[0] + a[1] * a[1] + a[2] * a[2]).sqrt()
+}
+
+/// Scales to unit length, degenerate vectors (e.g. unused vertices) become zero.
+fn normalise(a: [f32; 3]) -> [f32; 3] {
+    let len = length(a);
+    match len > f32::EPSILON {
+        true => a.map(|value| value / len),
+        false => [0.0; 3],
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Tests
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_enclose_all_positions() {
+        let mesh = Mesh {
+            positions: vec![[1.0, -2.0, 0.0], [-1.0, 4.0, 2.0], [0.0, 0.0, 3.0]],
+            ..Mesh::new()
+        };
+        let bounds = mesh.bounds().unwrap();
+        assert_eq!(bounds.min, [-1.0, -2.0, 0.0]);
+        assert_eq!(bounds.max, [1.0, 4.0, 3.0]);
+        assert_eq!(bounds.center(), [0.0, 1.0, 1.5]);
+        assert_eq!(Mesh::new().bounds(), None);
+    }
+
+    #[test]
+    fn radius_is_half_the_diagonal() {
+        let bounds = BoundingBox {
+            min: [0.0; 3],
+            max: [2.0, 3.0, 6.0],
+        };
+        assert_eq!(bounds.radius(), 3.5);
+    }
+
+    #[test]
+    fn detects_formats_from_file_names() {
+        assert_eq!(MeshFormat::from_file_name("a.b.OBJ"), Some(MeshFormat::Obj));
+        assert_eq!(MeshFormat::from_file_name("stl"), None);
+    }
+}
@@ -21,6 +21,11 @@
  */
 
 pub mod application_info;
+pub mod command_line;
+pub mod job_config;
 pub mod job_context;
+pub mod mesh;
+pub mod parameter_sweep;
+pub mod residual_history;
 pub mod runner_info;
 pub mod user_context;
@@ -0,0 +1,431 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: parameter_sweep.rs
+ * description: Parameter sweeps, the cartesian product of swept config values run as a job batch
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use serde_json::{Number, Value};
+
+/// Upper bound on the jobs a single sweep may generate.
+pub const MAX_SWEEP_JOBS: usize = 256;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SweepRange {
+    List(Vec<f64>),
+    /// `count` values evenly spaced from `start` to `stop`, both included.
+    Linear {
+        start: f64,
+        stop: f64,
+        count: usize,
+    },
+    /// `count` values evenly spaced in log space, `start` and `stop` must be positive.
+    Log {
+        start: f64,
+        stop: f64,
+        count: usize,
+    },
+}
+
+impl SweepRange {
+    pub fn values(&self) -> Result<Vec<f64>, String> {
+        if let SweepRange::Linear { count, .. } | SweepRange::Log { count, .. } = *self {
+            if count > MAX_SWEEP_JOBS {
+                return Err(format!(
+                    "A swept parameter takes at most {} values",
+                    MAX_SWEEP_JOBS
+                ));
+            }
+        }
+        let spaced = |start: f64, stop: f64, count: usize| -> Vec<f64> {
+            match count {
+                1 => vec![start],
+                _ => (0..count)
+                    .map(|i| start + (stop - start) * i as f64 / (count - 1) as f64)
+                    .collect(),
+            }
+        };
+        let values = match *self {
+            SweepRange::List(ref values) => values.clone(),
+            SweepRange::Linear { start, stop, count } => spaced(start, stop, count),
+            SweepRange::Log { start, stop, count } => {
+                if start <= 0.0 || stop <= 0.0 {
+                    return Err("A log range needs positive start and stop values".to_string());
+                }
+                spaced(start.log10(), stop.log10(), count)
+                    .into_iter()
+                    .map(|exponent| 10f64.powf(exponent))
+                    .collect()
+            }
+        };
+        match values.is_empty() {
+            true => Err("A swept parameter needs at least one value".to_string()),
+            false => Ok(values),
+        }
+    }
+}
+
+/// A config value to vary, `path` holds the keys leading to it from the config root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepParameter {
+    pub path: Vec<String>,
+    pub integer: bool,
+    pub range: SweepRange,
+}
+
+impl SweepParameter {
+    /// The parameter's values as JSON, integer parameters are rounded and repeats dropped.
+    fn json_values(&self) -> Result<Vec<Value>, String> {
+        let mut values = Vec::new();
+        for value in self.range.values()? {
+            let json = match self.integer {
+                true => Value::from(value.round() as i64),
+                false => Number::from_f64(value)
+                    .map(Value::Number)
+                    .ok_or(format!("{} is not a valid value", value))?,
+            };
+            if !values.contains(&json) {
+                values.push(json);
+            }
+        }
+        Ok(values)
+    }
+
+    pub fn name(&self) -> String {
+        self.path.join(".")
+    }
+}
+
+/// One combination of swept values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepCase {
+    pub values: Vec<(Vec<String>, Value)>,
+}
+
+impl SweepCase {
+    /// A copy of `config` with the case's values set, missing objects along a path are created.
+    pub fn apply(&self, config: &Value) -> Value {
+        let mut config = config.clone();
+        for (path, value) in &self.values {
+            let mut target = &mut config;
+            for key in path {
+                if !target.is_object() {
+                    *target = Value::Object(Default::default());
+                }
+                target = target
+                    .as_object_mut()
+                    .map(|map| map.entry(key.clone()).or_insert(Value::Null))
+                    .expect("target was made an object");
+            }
+            *target = value.clone();
+        }
+        config
+    }
+
+    /// Short description of the case, e.g. `Re=100 aoa=2`, using the last key of each path.
+    pub fn label(&self) -> String {
+        self.values
+            .iter()
+            .map(|(path, value)| {
+                format!(
+                    "{}={}",
+                    path.last().map(String::as_str).unwrap_or(""),
+                    value
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParameterSweep {
+    pub parameters: Vec<SweepParameter>,
+}
+
+impl ParameterSweep {
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    /// Every combination of the parameters' values, the last parameter varying fastest.
+    pub fn cases(&self) -> Result<Vec<SweepCase>, String> {
+        let mut axes = Vec::new();
+        for (index, parameter) in self.parameters.iter().enumerate() {
+            if self.parameters[..index]
+                .iter()
+                .any(|other| other.path == parameter.path)
+            {
+                return Err(format!("{} is swept more than once", parameter.name()));
+            }
+            let values = parameter
+                .json_values()
+                .map_err(|e| format!("{}: {}", parameter.name(), e))?;
+            axes.push((parameter.path.clone(), values));
+        }
+
+        let count = axes
+            .iter()
+            .try_fold(1usize, |count, (_, values)| count.checked_mul(values.len()))
+            .filter(|count| *count <= MAX_SWEEP_JOBS)
+            .ok_or(format!(
+                "The sweep generates more than {} jobs, reduce the number of values",
+                MAX_SWEEP_JOBS
+            ))?;
+
+        let mut cases = vec![SweepCase { values: Vec::new() }];
+        for (path, values) in &axes {
+            cases = cases
+                .into_iter()
+                .flat_map(|case| {
+                    values.iter().map(move |value| {
+                        let mut case = case.clone();
+                        case.values.push((path.clone(), value.clone()));
+                        case
+                    })
+                })
+                .collect();
+        }
+        debug_assert_eq!(cases.len(), count);
+        Ok(cases)
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+// Batch Names
+// -------------------------------------------------------------------------------------------------
+
+/// Name of a job in a batch: `<batch> [<n>/<total>] <label>`. Jobs carry no tags on the backend,
+/// so the batch is recovered from the name with `batch_name`.
+pub fn batch_job_name(batch: &str, index: usize, total: usize, label: &str) -> String {
+    format!("{} [{}/{}] {}", batch, index + 1, total, label)
+        .trim_end()
+        .to_string()
+}
+
+/// The batch and 1-based position of a job named by `batch_job_name`. The counter is looked for
+/// from the end, so batch names may themselves contain brackets.
+pub fn batch_name(job_name: &str) -> Option<(&str, usize)> {
+    job_name.rmatch_indices(" [").find_map(|(start, _)| {
+        let rest = &job_name[start + 2..];
+        let (counter, _) = rest.split_once(']')?;
+        let (index, total) = counter.split_once('/')?;
+        let index = index.parse::<usize>().ok()?;
+        let total = total.parse::<usize>().ok()?;
+        (1..=total)
+            .contains(&index)
+            .then(|| (&job_name[..start], index))
+    })
+}
+
+// -------------------------------------------------------------------------------------------------
+// Tests
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parameter(path: &[&str], integer: bool, range: SweepRange) -> SweepParameter {
+        SweepParameter {
+            path: path.iter().map(|key| key.to_string()).collect(),
+            integer,
+            range,
+        }
+    }
+
+    #[test]
+    fn cases_vary_the_last_parameter_fastest() {
+        let sweep = ParameterSweep {
+            parameters: vec![
+                parameter(&["flow", "re"], true, SweepRange::List(vec![10.0, 20.0])),
+                parameter(&["aoa"], false, SweepRange::List(vec![0.0, 2.5, 5.0])),
+            ],
+        };
+        let labels = sweep
+            .cases()
+            .unwrap()
+            .iter()
+            .map(SweepCase::label)
+            .collect::<Vec<String>>();
+        assert_eq!(
+            labels,
+            vec![
+                "re=10 aoa=0.0",
+                "re=10 aoa=2.5",
+                "re=10 aoa=5.0",
+                "re=20 aoa=0.0",
+                "re=20 aoa=2.5",
+                "re=20 aoa=5.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn cases_apply_values_along_their_paths() {
+        let sweep = ParameterSweep {
+            parameters: vec![parameter(
+                &["flow", "re"],
+                true,
+                SweepRange::List(vec![10.0]),
+            )],
+        };
+        let case = &sweep.cases().unwrap()[0];
+        assert_eq!(
+            case.apply(&json!({"flow": {"nu": 1}, "steps": 5})),
+            json!({"flow": {"nu": 1, "re": 10}, "steps": 5})
+        );
+        assert_eq!(case.apply(&json!({"flow": 3})), json!({"flow": {"re": 10}}));
+    }
+
+    #[test]
+    fn cases_are_limited_to_max_sweep_jobs() {
+        let sweep = |count: usize| ParameterSweep {
+            parameters: vec![
+                parameter(
+                    &["a"],
+                    false,
+                    SweepRange::Linear {
+                        start: 0.0,
+                        stop: 1.0,
+                        count: MAX_SWEEP_JOBS / 2,
+                    },
+                ),
+                parameter(
+                    &["b"],
+                    false,
+                    SweepRange::Linear {
+                        start: 0.0,
+                        stop: 1.0,
+                        count,
+                    },
+                ),
+            ],
+        };
+        assert_eq!(sweep(2).cases().unwrap().len(), MAX_SWEEP_JOBS);
+        assert!(sweep(3).cases().is_err());
+        assert!(sweep(usize::MAX).cases().is_err());
+    }
+
+    #[test]
+    fn cases_reject_a_parameter_swept_twice() {
+        let sweep = ParameterSweep {
+            parameters: vec![
+                parameter(&["a"], false, SweepRange::List(vec![1.0])),
+                parameter(&["a"], false, SweepRange::List(vec![2.0])),
+            ],
+        };
+        assert!(sweep.cases().unwrap_err().contains("more than once"));
+    }
+
+    #[test]
+    fn ranges_include_both_endpoints() {
+        let linear = SweepRange::Linear {
+            start: 0.1,
+            stop: 0.7,
+            count: 4,
+        };
+        let values = linear.values().unwrap();
+        assert_eq!(values.len(), 4);
+        assert_eq!((values[0], values[3]), (0.1, 0.7));
+
+        let log = SweepRange::Log {
+            start: 1e-3,
+            stop: 1e3,
+            count: 7,
+        };
+        let values = log.values().unwrap();
+        assert_eq!(values.len(), 7);
+        assert_eq!((values[0], values[6]), (1e-3, 1e3));
+        assert!((values[3] - 1.0).abs() < 1e-12, "{:?}", values);
+
+        let single = SweepRange::Linear {
+            start: 2.0,
+            stop: 5.0,
+            count: 1,
+        };
+        assert_eq!(single.values().unwrap(), vec![2.0]);
+    }
+
+    #[test]
+    fn ranges_reject_invalid_bounds() {
+        let log = SweepRange::Log {
+            start: 0.0,
+            stop: 10.0,
+            count: 2,
+        };
+        assert!(log.values().is_err());
+        let empty = SweepRange::Linear {
+            start: 0.0,
+            stop: 1.0,
+            count: 0,
+        };
+        assert!(empty.values().is_err());
+        assert!(SweepRange::List(Vec::new()).values().is_err());
+    }
+
+    #[test]
+    fn integer_parameters_are_rounded_without_repeats() {
+        let sweep = ParameterSweep {
+            parameters: vec![parameter(
+                &["n"],
+                true,
+                SweepRange::Linear {
+                    start: 1.0,
+                    stop: 3.0,
+                    count: 5,
+                },
+            )],
+        };
+        let values = sweep
+            .cases()
+            .unwrap()
+            .into_iter()
+            .map(|case| case.values[0].1.clone())
+            .collect::<Vec<Value>>();
+        assert_eq!(values, vec![json!(1), json!(2), json!(3)]);
+    }
+
+    #[test]
+    fn batch_names_round_trip() {
+        let name = batch_job_name("Cavity", 4, 6, "re=100 aoa=2");
+        assert_eq!(name, "Cavity [5/6] re=100 aoa=2");
+        assert_eq!(batch_name(&name), Some(("Cavity", 5)));
+        assert_eq!(
+            batch_name(&batch_job_name("Cavity", 0, 1, "")),
+            Some(("Cavity", 1))
+        );
+    }
+
+    #[test]
+    fn batch_names_may_contain_brackets() {
+        for batch in ["Cavity [fine]", "Cavity [1/2]", "Run [a [b]"] {
+            let name = batch_job_name(batch, 2, 3, "aoa=2");
+            assert_eq!(batch_name(&name), Some((batch, 3)), "{}", name);
+        }
+    }
+
+    #[test]
+    fn names_outside_a_batch_have_no_batch() {
+        assert_eq!(batch_name("Cavity"), None);
+        assert_eq!(batch_name("Cavity [fine]"), None);
+        assert_eq!(batch_name("Cavity [0/3]"), None);
+        assert_eq!(batch_name("Cavity [4/3]"), None);
+    }
+}
@@ -0,0 +1,144 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: residual_history.rs
+ * description: Residual and monitor histories read from solver output
+ * ------------------------------------------------------------------------------------------------
+ */
+
+// -------------------------------------------------------------------------------------------------
+// Residual History
+// -------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResidualSeries {
+    pub name: String,
+    pub points: Vec<(f64, f64)>, // (iteration, value)
+}
+
+/// Columns of numbers (comma or whitespace separated) with one column holding the iteration.
+///
+/// Solver logs mix tables with free text, so lines are read one at a time and only rows that are
+/// entirely numeric and as wide as the first row count. The line before the first row names the
+/// columns if it has the same width (a leading `#` or `%` is ignored); the iteration column is
+/// the one named like `iter`, `step` or `time`, otherwise the first.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResidualHistory {
+    pub iteration_column: String,
+    pub series: Vec<ResidualSeries>,
+    iteration_index: usize,
+    width: usize,
+    last_header: Option<Vec<String>>,
+    lines_read: usize,
+}
+
+impl ResidualHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of lines consumed, so a growing file can be fed only its new lines.
+    pub fn lines_read(&self) -> usize {
+        self.lines_read
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.iter().all(|series| series.points.is_empty())
+    }
+
+    pub fn push_line(&mut self, line: &str) {
+        self.lines_read += 1;
+
+        let fields = split_fields(line);
+        if fields.len() < 2 {
+            return;
+        }
+
+        let values = fields
+            .iter()
+            .map(|field| field.parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>();
+        match values {
+            Ok(values) => self.push_row(values),
+            Err(_) if self.width == 0 => {
+                let is_header = fields.iter().all(|field| field.parse::<f64>().is_err());
+                self.last_header =
+                    is_header.then(|| fields.iter().map(|field| field.to_string()).collect());
+            }
+            Err(_) => {}
+        }
+    }
+
+    fn push_row(&mut self, values: Vec<f64>) {
+        if self.width == 0 {
+            self.set_columns(values.len());
+        }
+        if values.len() != self.width {
+            return;
+        }
+
+        let iteration = values[self.iteration_index];
+        let mut series = self.series.iter_mut();
+        for (index, value) in values.into_iter().enumerate() {
+            if index != self.iteration_index {
+                if let Some(series) = series.next() {
+                    series.points.push((iteration, value));
+                }
+            }
+        }
+    }
+
+    fn set_columns(&mut self, width: usize) {
+        let names = match self.last_header.take() {
+            Some(header) if header.len() == width => header,
+            _ => (0..width)
+                .map(|index| match index {
+                    0 => "Iteration".to_string(),
+                    _ => format!("Column {}", index + 1),
+                })
+                .collect(),
+        };
+
+        self.width = width;
+        self.iteration_index = names
+            .iter()
+            .position(|name| {
+                let name = name.to_lowercase();
+                name.contains("iter") || name == "step" || name == "time"
+            })
+            .unwrap_or(0);
+        self.iteration_column = names[self.iteration_index].clone();
+        self.series = names
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| *index != self.iteration_index)
+            .map(|(_, name)| ResidualSeries {
+                name,
+                points: Vec::new(),
+            })
+            .collect();
+    }
+}
+
+/// Splits a line on commas or whitespace, ignoring a leading comment marker.
+fn split_fields(line: &str) -> Vec<&str> {
+    let line = line.trim().trim_start_matches(['#', '%']).trim();
+    match line.contains(',') {
+        true => line.split(',').map(|field| field.trim()).collect(),
+        false => line.split_whitespace().collect(),
+    }
+}
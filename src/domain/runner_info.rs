@@ -52,6 +52,11 @@ impl RunnerInfo {
         self
     }
 
+    /// Whether the runner is connected and able to act on changes to its jobs.
+    pub fn is_reachable(&self) -> bool {
+        matches!(self.state, RunnerState::Idle | RunnerState::Busy)
+    }
+
     pub fn new_complete(
         id: Uuid,
         name: String,
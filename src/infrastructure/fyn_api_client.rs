@@ -21,21 +21,28 @@
  */
 
 use chrono::{DateTime, Utc};
+use futures::future::{LocalBoxFuture, Shared};
+use futures::FutureExt;
 use leptos::{prelude::*, reactive::spawn_local};
 use serde::{Deserialize, Serialize};
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::rc::Rc;
 use uuid::Uuid;
 
 use crate::common::base64_utils::*;
 use crate::domain::application_info::AppInfo;
 use crate::domain::job_context::{
-    JobInfo as JobInfoDomain, JobStatus as JobStatusDomain, ResourceType,
+    JobInfo as JobInfoDomain, JobResource as JobResourceDomain, JobStatus as JobStatusDomain,
+    ResourceType,
 };
 use crate::domain::runner_info::{
     RunnerInfo as RunnerInfoDomain, RunnerState as RunnerStateDomain,
 };
 use crate::domain::user_context::UserContext;
+use crate::infrastructure::fyn_api_error::FynApiError;
 
 use fyn_api::apis::accounts_api::{accounts_users_create, accounts_users_retrieve};
 use fyn_api::apis::application_registry_api::{
@@ -44,6 +51,7 @@ use fyn_api::apis::application_registry_api::{
 use fyn_api::apis::configuration::Configuration;
 use fyn_api::apis::job_manager_api::*;
 use fyn_api::apis::runner_manager_api::runner_manager_users_list;
+use fyn_api::apis::{Error as ApiError, ResponseContent};
 use fyn_api::models::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,13 +65,18 @@ struct TokenRefreshResponse {
     access: String,
 }
 
+/// A token refresh shared by every request waiting on it, resolving to whether it succeeded.
+type TokenRefresh = Shared<LocalBoxFuture<'static, bool>>;
+
 #[derive(Clone)]
 pub struct FynApiClient {
     config: RwSignal<Configuration>,
     access_token: RwSignal<Option<String>>,
     refresh_token: RwSignal<Option<String>>,
     user_id: RwSignal<Option<String>>,
+    authenticated: RwSignal<bool>,
     loading: RwSignal<bool>,
+    refresh_in_flight: StoredValue<Option<TokenRefresh>, LocalStorage>,
 }
 
 
@@ -76,7 +89,9 @@ impl FynApiClient {
             access_token: RwSignal::new(None),
             refresh_token: RwSignal::new(None),
             user_id: RwSignal::new(None),
+            authenticated: RwSignal::new(false),
             loading: RwSignal::new(true),
+            refresh_in_flight: StoredValue::new_local(None),
         };
 
         spawn_local({
@@ -89,11 +104,21 @@ impl FynApiClient {
         context
     }
 
+    /// Whether the client currently holds a session, flips to false on logout or when the access
+    /// token can no longer be refreshed.
+    pub fn is_authenticated(&self) -> bool {
+        self.authenticated.get()
+    }
+
     // ---------------------------------------------------------------------------------------------
     // Authentication & Session Management
     // ---------------------------------------------------------------------------------------------
 
-    pub async fn login(&self, username: String, password: String) -> Result<UserContext, String> {
+    pub async fn login(
+        &self,
+        username: String,
+        password: String,
+    ) -> Result<UserContext, FynApiError> {
         self.loading.set(true);
         leptos::logging::log!("Attempting JWT login for user: {}", username);
 
@@ -108,30 +133,32 @@ impl FynApiClient {
             .await
             .map_err(|e| {
                 leptos::logging::error!("Login request failed: {:?}", e);
-                format!("Login failed: {:?}", e)
+                FynApiError::from(e)
             })?;
 
         if !response.status().is_success() {
+            let status = response.status().as_u16();
             let error_text = response.text().await.unwrap_or_default();
             leptos::logging::error!("Login failed with status: {}", error_text);
             self.loading.set(false);
-            return Err(format!("Login failed: {}", error_text));
+            return Err(FynApiError::from_response(status, &error_text));
         }
 
         let tokens: TokenResponse = response.json().await.map_err(|e| {
             leptos::logging::error!("Token parse error: {:?}", e);
             self.loading.set(false);
-            format!("Failed to parse tokens: {:?}", e)
+            FynApiError::from(e)
         })?;
 
         leptos::logging::log!("JWT tokens received successfully");
 
         // Decode user_id from token payload
-        let user_id = decode_token_user_id(&tokens.access)?;
+        let user_id = decode_token_user_id(&tokens.access).map_err(FynApiError::Decode)?;
 
         self.access_token.set(Some(tokens.access.clone()));
         self.refresh_token.set(Some(tokens.refresh.clone()));
         self.user_id.set(Some(user_id.clone()));
+        self.authenticated.set(true);
         if let Some(window) = web_sys::window() {
             if let Ok(Some(storage)) = window.local_storage() {
                 let _ = storage.set_item("access_token", &tokens.access);
@@ -152,11 +179,10 @@ impl FynApiClient {
         Ok(user)
     }
 
-    pub async fn refresh_access_token(&self) -> Result<(), String> {
-        let refresh_token = self
-            .refresh_token
-            .get()
-            .ok_or("No refresh token available")?;
+    pub async fn refresh_access_token(&self) -> Result<(), FynApiError> {
+        let refresh_token = self.refresh_token.get().ok_or(FynApiError::Unauthorized(
+            "No refresh token available".to_string(),
+        ))?;
 
         leptos::logging::log!("Refreshing access token...");
 
@@ -169,17 +195,15 @@ impl FynApiClient {
                 "refresh": refresh_token
             }))
             .send()
-            .await
-            .map_err(|e| format!("Token refresh failed: {:?}", e))?;
+            .await?;
 
         if !response.status().is_success() {
-            return Err("Token refresh failed - please login again".to_string());
+            let status = response.status().as_u16();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(FynApiError::from_response(status, &error_text));
         }
 
-        let new_token: TokenRefreshResponse = response
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse refreshed token: {:?}", e))?;
+        let new_token: TokenRefreshResponse = response.json().await?;
 
         // Update stored token
         self.access_token.set(Some(new_token.access.clone()));
@@ -207,6 +231,7 @@ impl FynApiClient {
         self.access_token.set(None);
         self.refresh_token.set(None);
         self.user_id.set(None);
+        self.authenticated.set(false);
 
         // Clear from localStorage
         if let Some(window) = web_sys::window() {
@@ -223,11 +248,150 @@ impl FynApiClient {
         });
     }
 
-    async fn get_current_user(&self, user_id: &str) -> Result<UserContext, String> {
-        // Use the generated API client - it automatically adds the Bearer token!
-        let user = accounts_users_retrieve(&self.config.get(), user_id)
+    /// Checks the `exp` claim of the current access token, treating tokens that will lapse within
+    /// the next few seconds as already expired.
+    fn is_access_token_expired(&self) -> bool {
+        match self.access_token.get() {
+            Some(token) => match decode_jwt::<JwtClaims>(&token) {
+                Ok(claims) => claims.exp - TOKEN_EXPIRY_LEEWAY_SECONDS <= Utc::now().timestamp(),
+                Err(_) => true,
+            },
+            None => false,
+        }
+    }
+
+    /// Attempts a token refresh, ending the session only if the refresh token has been rejected.
+    /// A network failure keeps the session so a later request can try again.
+    ///
+    /// Concurrent callers await the same refresh, a burst of requests made with an expired token
+    /// sends a single refresh and logs out at most once.
+    async fn refresh_or_logout(&self) -> bool {
+        let refresh = match self.refresh_in_flight.get_value() {
+            Some(refresh) => refresh,
+            None => {
+                let client = self.clone();
+                let refresh = async move {
+                    let refreshed = match client.refresh_access_token().await {
+                        Ok(()) => true,
+                        Err(e @ (FynApiError::Unauthorized(_) | FynApiError::Validation(_))) => {
+                            leptos::logging::error!(
+                                "Token refresh rejected - please login again: {}",
+                                e
+                            );
+                            client.logout().await;
+                            false
+                        }
+                        Err(e) => {
+                            leptos::logging::error!("Token refresh failed: {}", e);
+                            false
+                        }
+                    };
+                    client.refresh_in_flight.set_value(None);
+                    refreshed
+                }
+                .boxed_local()
+                .shared();
+                self.refresh_in_flight.set_value(Some(refresh.clone()));
+                refresh
+            }
+        };
+        refresh.await
+    }
+
+    /// Runs a generated API request, refreshing the access token beforehand if it has expired and
+    /// retrying once should the backend still respond with 401 Unauthorized. A request is not sent
+    /// with a token known to have expired, it fails as unauthorized if the refresh does.
+    ///
+    /// The request closure receives a fresh copy of the configuration on each attempt so that the
+    /// retry is sent with the refreshed bearer token.
+    async fn with_token_refresh<T, E, F, Fut>(&self, request: F) -> Result<T, ApiError<E>>
+    where
+        F: Fn(Configuration) -> Fut,
+        Fut: Future<Output = Result<T, ApiError<E>>>,
+    {
+        if self.is_access_token_expired() && !self.refresh_or_logout().await {
+            return Err(ApiError::ResponseError(ResponseContent {
+                status: reqwest::StatusCode::UNAUTHORIZED,
+                content: serde_json::json!({ "detail": TOKEN_REFRESH_FAILED }).to_string(),
+                entity: None,
+            }));
+        }
+
+        let config = self.config.get();
+        let sent_token = config.bearer_access_token.clone();
+        let response = request(config).await;
+        if !is_unauthorized(&response) || self.refresh_token.get().is_none() {
+            return response;
+        }
+
+        // another request may have refreshed the token while this one was in flight
+        let refreshed_meanwhile = self.config.get().bearer_access_token != sent_token;
+        leptos::logging::log!("Request unauthorized, retrying after token refresh...");
+        if !refreshed_meanwhile && !self.refresh_or_logout().await {
+            return response;
+        }
+        request(self.config.get()).await
+    }
+
+    /// Browser fetch counterpart of [`Self::with_token_refresh`] for requests that bypass the
+    /// generated client. The request is rebuilt for the retry as a fetched request is consumed.
+    async fn fetch_with_token_refresh(
+        &self,
+        build_request: impl Fn() -> Result<web_sys::Request, FynApiError>,
+    ) -> Result<web_sys::Response, FynApiError> {
+        if self.is_access_token_expired() && !self.refresh_or_logout().await {
+            return Err(FynApiError::Unauthorized(TOKEN_REFRESH_FAILED.to_string()));
+        }
+
+        let sent_token = self.access_token.get();
+        let response = self.fetch_with_token(build_request()?).await?;
+        if response.status() != 401 || self.refresh_token.get().is_none() {
+            return Ok(response);
+        }
+
+        // another request may have refreshed the token while this one was in flight
+        let refreshed_meanwhile = self.access_token.get() != sent_token;
+        leptos::logging::log!("Request unauthorized, retrying after token refresh...");
+        if !refreshed_meanwhile && !self.refresh_or_logout().await {
+            return Ok(response);
+        }
+        self.fetch_with_token(build_request()?).await
+    }
+
+    async fn fetch_with_token(
+        &self,
+        request: web_sys::Request,
+    ) -> Result<web_sys::Response, FynApiError> {
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen_futures::JsFuture;
+
+        let access_token = self.access_token.get().ok_or(FynApiError::Unauthorized(
+            "No access token available - please login first".to_string(),
+        ))?;
+
+        request
+            .headers()
+            .set("Authorization", &format!("Bearer {}", access_token))
+            .map_err(|_| FynApiError::Request("Failed to set Authorization header".to_string()))?;
+
+        let window =
+            web_sys::window().ok_or(FynApiError::Request("No window object".to_string()))?;
+        let resp_value = JsFuture::from(window.fetch_with_request(&request))
             .await
-            .map_err(|e| format!("Failed to get user: {:?}", e))?;
+            .map_err(|e| FynApiError::Network(format!("Fetch failed: {:?}", e)))?;
+
+        resp_value
+            .dyn_into()
+            .map_err(|_| FynApiError::Decode("Failed to cast to Response".to_string()))
+    }
+
+    async fn get_current_user(&self, user_id: &str) -> Result<UserContext, FynApiError> {
+        // Use the generated API client - it automatically adds the Bearer token!
+        let user = self
+            .with_token_refresh(
+                |config| async move { accounts_users_retrieve(&config, user_id).await },
+            )
+            .await?;
 
         Ok(UserContext::new()
             .username(&user.username)
@@ -251,13 +415,14 @@ impl FynApiClient {
                     self.access_token.set(Some(access.clone()));
                     self.refresh_token.set(Some(refresh));
                     self.user_id.set(Some(user_id.clone()));
+                    self.authenticated.set(true);
 
                     // Update config
                     self.config.update(|c| {
                         c.bearer_access_token = Some(access);
                     });
 
-                    // Try to get user info
+                    // Try to get user info, an expired token is refreshed along the way
                     match self.get_current_user(&user_id).await {
                         Ok(user) => {
                             leptos::logging::log!("Session restored for: {:?}", user.username);
@@ -265,15 +430,9 @@ impl FynApiClient {
                         }
                         Err(e) => {
                             leptos::logging::log!("Session restore failed: {}", e);
-                            // Token might be expired, try refresh
-                            if self.refresh_access_token().await.is_ok() {
-                                // Retry getting user
-                                return self.get_current_user(&user_id).await.ok();
-                            } else {
-                                // Clear invalid tokens
-                                self.logout().await;
-                                return None;
-                            }
+                            // Clear invalid tokens
+                            self.logout().await;
+                            return None;
                         }
                     }
                 }
@@ -291,7 +450,7 @@ impl FynApiClient {
         &self,
         new_user: UserContext,
         password: String,
-    ) -> Result<String, String> {
+    ) -> Result<String, FynApiError> {
         self.loading.set(true);
 
         let mut new_user_request = UserRequest::new(
@@ -306,7 +465,10 @@ impl FynApiClient {
 
         let _response = accounts_users_create(&self.config.get(), new_user_request)
             .await
-            .map_err(|e| format!("API error: {:?}", e))?;
+            .map_err(|e| {
+                self.loading.set(false);
+                FynApiError::from(e)
+            })?;
 
         self.loading.set(false);
         Ok("User created successfully".to_string())
@@ -316,39 +478,44 @@ impl FynApiClient {
     // Applications
     // ---------------------------------------------------------------------------------------------
 
-    pub async fn get_applications(&self) -> Option<HashMap<Uuid, AppInfo>> {
-        match application_registry_list(&self.config.get()).await {
-            Ok(list_of_apps) => Some(
-                list_of_apps
-                    .iter()
-                    .map(|app| {
-                        (
+    pub async fn get_applications(&self) -> Result<HashMap<Uuid, AppInfo>, FynApiError> {
+        match self
+            .with_token_refresh(|config| async move { application_registry_list(&config).await })
+            .await
+        {
+            Ok(list_of_apps) => Ok(list_of_apps
+                .iter()
+                .map(|app| {
+                    (
+                        app.id,
+                        AppInfo::new_basic(
                             app.id,
-                            AppInfo::new_basic(
-                                app.id,
-                                app.name.clone(),
-                                app.file_path.clone(),
-                                app.schema_path.clone(),
-                            ),
-                        )
-                    })
-                    .collect(),
-            ),
+                            app.name.clone(),
+                            app.file_path.clone(),
+                            app.schema_path.clone(),
+                        ),
+                    )
+                })
+                .collect()),
             Err(e) => {
                 leptos::logging::error!("Application registry API error: {:?}", e);
-                None
+                Err(e.into())
             }
         }
     }
 
-    pub async fn get_app_schema(&self, app_id: Uuid) -> Option<serde_json::Value> {
-        match application_registry_program_schema_retrieve(&self.config.get(), &app_id.to_string())
+    pub async fn get_app_schema(&self, app_id: Uuid) -> Result<serde_json::Value, FynApiError> {
+        let app_id = &app_id.to_string();
+        match self
+            .with_token_refresh(|config| async move {
+                application_registry_program_schema_retrieve(&config, app_id).await
+            })
             .await
         {
-            Ok(json_string) => Some(json_string),
+            Ok(json_string) => Ok(json_string),
             Err(e) => {
                 leptos::logging::error!("Application registry schema fetch error: {}", e);
-                None::<serde_json::Value>
+                Err(e.into())
             }
         }
     }
@@ -357,36 +524,184 @@ impl FynApiClient {
     // Job
     // ---------------------------------------------------------------------------------------------
 
-    pub async fn submit_new_job(&self, new_job: &JobInfoDomain) -> Result<JobInfoDomain, String> {
-        match job_manager_users_create(&self.config.get(), new_job.to_api_request()).await {
+    pub async fn submit_new_job(
+        &self,
+        new_job: &JobInfoDomain,
+    ) -> Result<JobInfoDomain, FynApiError> {
+        match self
+            .with_token_refresh(|config| async move {
+                job_manager_users_create(&config, new_job.to_api_request()).await
+            })
+            .await
+        {
             Ok(job_info) => job_info.to_domain(),
-            Err(e) => Err(format!("job_manager_users_create failed: {:?}", e)),
+            Err(e) => {
+                leptos::logging::error!("job_manager_users_create failed: {:?}", e);
+                Err(e.into())
+            }
         }
     }
 
-    pub async fn patch_job(&self, job: &JobInfoDomain) -> Result<JobInfoDomain, String> {
-        let response = job_manager_users_partial_update(
-            &self.config.get(),
-            &job.id.to_string(),
-            Some(job.to_api_patch()),
-        )
-        .await
-        .map_err(|e| format!("Error setting updating job status {:?}", e))?;
+    pub async fn patch_job(&self, job: &JobInfoDomain) -> Result<JobInfoDomain, FynApiError> {
+        let job_id = &job.id.to_string();
+        let response = self
+            .with_token_refresh(|config| async move {
+                job_manager_users_partial_update(&config, job_id, Some(job.to_api_patch())).await
+            })
+            .await
+            .map_err(|e| {
+                leptos::logging::error!("Error setting updating job status {:?}", e);
+                FynApiError::from(e)
+            })?;
         response.to_domain()
     }
 
-    pub async fn get_jobs(&self) -> Result<HashMap<Uuid, JobInfoDomain>, String> {
+    pub async fn delete_job(&self, job_id: Uuid) -> Result<(), FynApiError> {
+        let job_id = &job_id.to_string();
+        self.with_token_refresh(|config| async move {
+            job_manager_users_destroy(&config, job_id).await
+        })
+        .await
+        .map_err(|e| {
+            leptos::logging::error!("job_manager_users_destroy failed: {:?}", e);
+            FynApiError::from(e)
+        })
+    }
+
+    pub async fn get_jobs(&self) -> Result<HashMap<Uuid, JobInfoDomain>, FynApiError> {
         self.loading.set(true);
         leptos::logging::log!("Fetching job info...");
-        let response = job_manager_users_list(&self.config.get())
+        let response = self
+            .with_token_refresh(|config| async move { job_manager_users_list(&config).await })
             .await
-            .map_err(|e| format!("API error: {:?}", e))?;
+            .map_err(|e| {
+                self.loading.set(false);
+                FynApiError::from(e)
+            })?;
         self.loading.set(false);
 
         response
             .iter()
             .map(|job| job.to_domain().map(|domain| (job.id, domain)))
-            .collect::<Result<HashMap<Uuid, JobInfoDomain>, String>>()
+            .collect::<Result<HashMap<Uuid, JobInfoDomain>, FynApiError>>()
+    }
+
+    pub async fn get_job(&self, job_id: Uuid) -> Result<JobInfoDomain, FynApiError> {
+        let job_id = &job_id.to_string();
+        let response = self
+            .with_token_refresh(|config| async move {
+                job_manager_users_retrieve(&config, job_id).await
+            })
+            .await?;
+        response.to_domain()
+    }
+
+    /// Fetches the job's resources by their ids, together rather than one after another.
+    pub async fn get_job_resources(
+        &self,
+        job: &JobInfoDomain,
+    ) -> Result<Vec<JobResourceDomain>, FynApiError> {
+        leptos::logging::log!("Fetching resources for job {}...", job.id);
+        let requests = job.resources.iter().map(|resource_id| async move {
+            let resource_id = &resource_id.to_string();
+            let response = self
+                .with_token_refresh(|config| async move {
+                    job_manager_resources_users_retrieve(&config, resource_id).await
+                })
+                .await?;
+            response.to_domain()
+        });
+        futures::future::try_join_all(requests).await
+    }
+
+    /// Download the file behind a job resource
+    ///
+    /// NOTE: Like uploads, this bypasses the generated OpenAPI client as resource files are served
+    /// as raw bytes rather than JSON. The fetch carries the JWT Bearer token.
+    pub async fn download_job_resource(
+        &self,
+        resource: &JobResourceDomain,
+    ) -> Result<web_sys::Blob, FynApiError> {
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen_futures::JsFuture;
+
+        let url = self.resource_url(resource);
+        let response = self
+            .fetch_with_token_refresh(|| {
+                web_sys::Request::new_with_str(&url)
+                    .map_err(|_| FynApiError::Request("Failed to create request".to_string()))
+            })
+            .await?;
+
+        if !response.ok() {
+            leptos::logging::error!("Download failed with status: {}", response.status());
+            return Err(response_error(&response).await);
+        }
+
+        let promise = response
+            .blob()
+            .map_err(|_| FynApiError::Decode("Response has no body".to_string()))?;
+        JsFuture::from(promise)
+            .await
+            .map_err(|e| FynApiError::Network(format!("Download failed: {:?}", e)))?
+            .dyn_into()
+            .map_err(|_| FynApiError::Decode("Failed to cast to Blob".to_string()))
+    }
+
+    /// Fetch the bytes of a resource from `offset` onwards, for following a file as it grows.
+    ///
+    /// NOTE: Uses an HTTP Range request. A server that ignores the range answers with the whole
+    /// file, which is then trimmed, and an offset at the end of the file yields no bytes.
+    pub async fn fetch_job_resource_from(
+        &self,
+        resource: &JobResourceDomain,
+        offset: u64,
+    ) -> Result<Vec<u8>, FynApiError> {
+        use wasm_bindgen_futures::JsFuture;
+
+        let url = self.resource_url(resource);
+        let response = self
+            .fetch_with_token_refresh(|| {
+                let request = web_sys::Request::new_with_str(&url)
+                    .map_err(|_| FynApiError::Request("Failed to create request".to_string()))?;
+                request
+                    .headers()
+                    .set("Range", &format!("bytes={}-", offset))
+                    .map_err(|_| FynApiError::Request("Failed to set Range header".to_string()))?;
+                Ok(request)
+            })
+            .await?;
+
+        match response.status() {
+            416 => return Ok(Vec::new()), // range not satisfiable, nothing new
+            _ if !response.ok() => {
+                leptos::logging::error!("Range fetch failed with status: {}", response.status());
+                return Err(response_error(&response).await);
+            }
+            _ => {}
+        }
+
+        let promise = response
+            .array_buffer()
+            .map_err(|_| FynApiError::Decode("Response has no body".to_string()))?;
+        let buffer = JsFuture::from(promise)
+            .await
+            .map_err(|e| FynApiError::Network(format!("Fetch failed: {:?}", e)))?;
+        let bytes = js_sys::Uint8Array::new(&buffer).to_vec();
+
+        match response.status() {
+            206 => Ok(bytes),
+            _ => Ok(bytes.into_iter().skip(offset as usize).collect()),
+        }
+    }
+
+    /// Resource files are reported as absolute URLs, relative paths are resolved against the API.
+    fn resource_url(&self, resource: &JobResourceDomain) -> String {
+        if resource.file.starts_with("http://") || resource.file.starts_with("https://") {
+            resource.file.clone()
+        } else {
+            format!("{}{}", self.config.get().base_path, resource.file)
+        }
     }
 
     /// Upload a web File as a job resource (for browser-based uploads)
@@ -403,69 +718,168 @@ impl FynApiClient {
         file: web_sys::File,
         resource_type: &str,
         description: Option<&str>,
-    ) -> Result<(), String> {
-        use wasm_bindgen::JsCast;
-        use wasm_bindgen_futures::JsFuture;
-
-        let access_token = self
-            .access_token
-            .get()
-            .ok_or("No access token available - please login first")?;
-
+    ) -> Result<(), FynApiError> {
         let base_url = &self.config.get().base_path;
+        let form_error = |msg: &str| FynApiError::Request(msg.to_string());
+        let form_data = resource_form_data(job_id, &file, resource_type, description)?;
 
-        // Create multipart form
-        let form_data =
-            web_sys::FormData::new().map_err(|_| "Failed to create FormData".to_string())?;
+        // Build request using web-sys, the JWT Bearer token is attached on fetch
+        let url = format!("{}/job_manager/resources/users/", base_url);
+        let response = self
+            .fetch_with_token_refresh(|| {
+                let opts = web_sys::RequestInit::new();
+                opts.set_method("POST");
+                opts.set_body(form_data.as_ref());
+                web_sys::Request::new_with_str_and_init(&url, &opts)
+                    .map_err(|_| form_error("Failed to create request"))
+            })
+            .await?;
 
-        form_data
-            .append_with_str("job", &job_id.to_string())
-            .map_err(|_| "Failed to append job ID".to_string())?;
+        if response.ok() {
+            leptos::logging::log!("Job resource uploaded successfully");
+            Ok(())
+        } else {
+            leptos::logging::error!("Upload failed with status: {}", response.status());
+            Err(response_error(&response).await)
+        }
+    }
 
-        form_data
-            .append_with_str("resource_type", resource_type)
-            .map_err(|_| "Failed to append resource_type".to_string())?;
+    /// Upload a web File as a job resource, reporting the fraction of the file sent so far to
+    /// `on_progress`.
+    ///
+    /// NOTE: fetch has no upload progress events, so the same multipart form as in
+    /// [`Self::upload_job_resource_file`] is sent through XMLHttpRequest instead.
+    pub async fn upload_job_resource_file_with_progress(
+        &self,
+        job_id: Uuid,
+        file: web_sys::File,
+        resource_type: &str,
+        description: Option<&str>,
+        on_progress: impl Fn(f64) + 'static,
+    ) -> Result<(), FynApiError> {
+        let form_data = resource_form_data(job_id, &file, resource_type, description)?;
+        let url = format!(
+            "{}/job_manager/resources/users/",
+            self.config.get().base_path
+        );
+        let on_progress = Rc::new(on_progress);
 
-        form_data
-            .append_with_blob("file", &file)
-            .map_err(|_| "Failed to append file".to_string())?;
+        if self.is_access_token_expired() && !self.refresh_or_logout().await {
+            return Err(FynApiError::Unauthorized(TOKEN_REFRESH_FAILED.to_string()));
+        }
 
-        if let Some(desc) = description {
-            form_data
-                .append_with_str("description", desc)
-                .map_err(|_| "Failed to append description".to_string())?;
+        let sent_token = self.access_token.get();
+        let mut request = self
+            .send_with_progress(&url, &form_data, on_progress.clone())
+            .await?;
+        if request.status().unwrap_or(0) == 401 && self.refresh_token.get().is_some() {
+            // another request may have refreshed the token while the upload was in flight
+            let refreshed_meanwhile = self.access_token.get() != sent_token;
+            leptos::logging::log!("Upload unauthorized, retrying after token refresh...");
+            if refreshed_meanwhile || self.refresh_or_logout().await {
+                request = self
+                    .send_with_progress(&url, &form_data, on_progress)
+                    .await?;
+            }
         }
 
-        // Build request with JWT Bearer token using web-sys
-        let opts = web_sys::RequestInit::new();
-        opts.set_method("POST");
-        opts.set_body(form_data.as_ref());
+        let status = request.status().unwrap_or(0);
+        if (200..300).contains(&status) {
+            leptos::logging::log!("Job resource uploaded successfully");
+            Ok(())
+        } else {
+            leptos::logging::error!("Upload failed with status: {}", status);
+            let body = request.response_text().ok().flatten().unwrap_or_default();
+            Err(FynApiError::from_response(status, &body))
+        }
+    }
 
-        let url = format!("{}/job_manager/resources/users/", base_url);
-        let request = web_sys::Request::new_with_str_and_init(&url, &opts)
-            .map_err(|_| "Failed to create request".to_string())?;
+    /// Sends a form with the JWT Bearer token, resolving once the request has completed.
+    async fn send_with_progress(
+        &self,
+        url: &str,
+        form_data: &web_sys::FormData,
+        on_progress: Rc<dyn Fn(f64)>,
+    ) -> Result<web_sys::XmlHttpRequest, FynApiError> {
+        use wasm_bindgen::closure::Closure;
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen_futures::JsFuture;
 
+        let request_error = |msg: &str| FynApiError::Request(msg.to_string());
+        let network_error = |msg: &str| FynApiError::Network(msg.to_string());
+        let access_token = self.access_token.get().ok_or(FynApiError::Unauthorized(
+            "No access token available - please login first".to_string(),
+        ))?;
+
+        let request = web_sys::XmlHttpRequest::new()
+            .map_err(|_| request_error("Failed to create request"))?;
         request
-            .headers()
-            .set("Authorization", &format!("Bearer {}", access_token))
-            .map_err(|_| "Failed to set Authorization header".to_string())?;
+            .open("POST", url)
+            .map_err(|_| request_error("Failed to open request"))?;
+        request
+            .set_request_header("Authorization", &format!("Bearer {}", access_token))
+            .map_err(|_| request_error("Failed to set Authorization header"))?;
 
-        // Execute fetch
-        let window = web_sys::window().ok_or("No window object")?;
-        let resp_value = JsFuture::from(window.fetch_with_request(&request))
+        let progress =
+            Closure::<dyn Fn(web_sys::ProgressEvent)>::new(move |event: web_sys::ProgressEvent| {
+                if event.length_computable() && event.total() > 0.0 {
+                    on_progress(event.loaded() / event.total());
+                }
+            });
+        request
+            .upload()
+            .map_err(|_| request_error("Upload progress is not available"))?
+            .set_onprogress(Some(progress.as_ref().unchecked_ref()));
+
+        // settles once the server has answered or the connection failed
+        let completed = js_sys::Promise::new(&mut |resolve, reject| {
+            request.set_onload(Some(&resolve));
+            request.set_onerror(Some(&reject));
+            request.set_onabort(Some(&reject));
+        });
+        request
+            .send_with_opt_form_data(Some(form_data))
+            .map_err(|_| network_error("Failed to send request"))?;
+        JsFuture::from(completed)
             .await
-            .map_err(|e| format!("Fetch failed: {:?}", e))?;
+            .map_err(|_| network_error("Upload failed, the connection was interrupted"))?;
+        Ok(request)
+    }
 
-        let response: web_sys::Response = resp_value
-            .dyn_into()
-            .map_err(|_| "Failed to cast to Response".to_string())?;
+    /// Attach a copy of an existing resource to another job, e.g. the config of a resubmitted job.
+    pub async fn copy_job_resource(
+        &self,
+        resource: &JobResourceDomain,
+        job_id: Uuid,
+    ) -> Result<(), FynApiError> {
+        let blob = self.download_job_resource(resource).await?;
+        let file = web_sys::File::new_with_blob_sequence(
+            &js_sys::Array::of1(&blob),
+            &resource.file_name(),
+        )
+        .map_err(|e| FynApiError::Request(format!("Failed to create File: {:?}", e)))?;
+
+        let description = Some(resource.description.as_str()).filter(|desc| !desc.is_empty());
+        self.upload_job_resource_file(
+            job_id,
+            file,
+            &domain_api_resource_type(resource.resource_type).to_string(),
+            description,
+        )
+        .await
+    }
 
-        if response.ok() {
-            leptos::logging::log!("Job resource uploaded successfully");
-            Ok(())
-        } else {
-            Err(format!("Upload failed with status: {}", response.status()))
-        }
+    /// Delete a resource and the file stored for it.
+    pub async fn delete_job_resource(&self, resource_id: Uuid) -> Result<(), FynApiError> {
+        let resource_id = &resource_id.to_string();
+        self.with_token_refresh(|config| async move {
+            job_manager_resources_users_destroy(&config, resource_id).await
+        })
+        .await
+        .map_err(|e| {
+            leptos::logging::error!("job_manager_resources_users_destroy failed: {:?}", e);
+            FynApiError::from(e)
+        })
     }
 
     /// Create a web_sys::File from JSON data
@@ -497,18 +911,20 @@ impl FynApiClient {
     // Runners
     // ---------------------------------------------------------------------------------------------
 
-    pub async fn get_runner_info(&self) -> Result<HashMap<Uuid, RunnerInfoDomain>, String> {
+    pub async fn get_runner_info(&self) -> Result<HashMap<Uuid, RunnerInfoDomain>, FynApiError> {
         self.loading.set(true);
 
         leptos::logging::log!("Fetching runner info...");
 
         // The bearer_access_token in config automatically adds:
         // Authorization: Bearer <token>
-        let response = runner_manager_users_list(&self.config.get())
+        let response = self
+            .with_token_refresh(|config| async move { runner_manager_users_list(&config).await })
             .await
             .map_err(|e| {
                 leptos::logging::error!("Runner info API error: {:?}", e);
-                format!("API error: {:?}", e)
+                self.loading.set(false);
+                FynApiError::from(e)
             })?;
 
         leptos::logging::log!("Runner info retrieved successfully");
@@ -541,6 +957,12 @@ impl FynApiClient {
 //  JWT token helpers
 // -------------------------------------------------------------------------------------------------
 
+/// Tokens expiring within this window are refreshed ahead of the request.
+const TOKEN_EXPIRY_LEEWAY_SECONDS: i64 = 30;
+
+/// Detail of the error a request fails with when its expired token could not be refreshed.
+const TOKEN_REFRESH_FAILED: &str = "Access token expired and could not be refreshed";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct JwtClaims {
     pub user_id: String,
@@ -566,6 +988,59 @@ fn decode_token_user_id(token: &str) -> Result<String, String> {
     Ok(claims.user_id)
 }
 
+/// Multipart form for the resource upload endpoint: the job, the resource type, the file and an
+/// optional description.
+fn resource_form_data(
+    job_id: Uuid,
+    file: &web_sys::File,
+    resource_type: &str,
+    description: Option<&str>,
+) -> Result<web_sys::FormData, FynApiError> {
+    let form_error = |msg: &str| FynApiError::Request(msg.to_string());
+
+    let form_data =
+        web_sys::FormData::new().map_err(|_| form_error("Failed to create FormData"))?;
+
+    form_data
+        .append_with_str("job", &job_id.to_string())
+        .map_err(|_| form_error("Failed to append job ID"))?;
+
+    form_data
+        .append_with_str("resource_type", resource_type)
+        .map_err(|_| form_error("Failed to append resource_type"))?;
+
+    form_data
+        .append_with_blob("file", file)
+        .map_err(|_| form_error("Failed to append file"))?;
+
+    if let Some(desc) = description {
+        form_data
+            .append_with_str("description", desc)
+            .map_err(|_| form_error("Failed to append description"))?;
+    }
+    Ok(form_data)
+}
+
+/// Builds a typed error from a failed browser fetch response.
+async fn response_error(response: &web_sys::Response) -> FynApiError {
+    let body = match response.text() {
+        Ok(promise) => wasm_bindgen_futures::JsFuture::from(promise)
+            .await
+            .ok()
+            .and_then(|text| text.as_string())
+            .unwrap_or_default(),
+        Err(_) => String::new(),
+    };
+    FynApiError::from_response(response.status(), &body)
+}
+
+fn is_unauthorized<T, E>(response: &Result<T, ApiError<E>>) -> bool {
+    matches!(
+        response,
+        Err(ApiError::ResponseError(content)) if content.status == reqwest::StatusCode::UNAUTHORIZED
+    )
+}
+
 // -------------------------------------------------------------------------------------------------
 // Front-Back End Enum Mapping
 // -------------------------------------------------------------------------------------------------
@@ -612,7 +1087,6 @@ fn api_domain_job_status(api_status: StatusEnum) -> JobStatusDomain {
     }
 }
 
-#[allow(dead_code)]
 fn domain_api_resource_type(domain_status: ResourceType) -> ResourceTypeEnum {
     match domain_status {
         ResourceType::Input => ResourceTypeEnum::In,
@@ -698,12 +1172,12 @@ impl APIDomainTraits for JobInfoDomain {
 
 trait DomainAPITraits {
     type Domain;
-    fn to_domain(&self) -> Result<Self::Domain, String>;
+    fn to_domain(&self) -> Result<Self::Domain, FynApiError>;
 }
 
 impl DomainAPITraits for JobInfo {
     type Domain = JobInfoDomain;
-    fn to_domain(&self) -> Result<Self::Domain, String> {
+    fn to_domain(&self) -> Result<Self::Domain, FynApiError> {
         JobInfoDomain::new()
             .id(self.id)
             .name(self.name.clone().unwrap_or("unnamed".to_string()))
@@ -716,5 +1190,20 @@ impl DomainAPITraits for JobInfo {
             .maybe_exit_code(self.exit_code.flatten().map(|v| v as i64))
             .resources(&self.resources)
             .build()
+            .map_err(FynApiError::Decode)
+    }
+}
+
+impl DomainAPITraits for JobResource {
+    type Domain = JobResourceDomain;
+    fn to_domain(&self) -> Result<Self::Domain, FynApiError> {
+        let mut resource = JobResourceDomain::new_resource(
+            api_domain_resource_type(self.resource_type),
+            self.file.clone(),
+            self.description.clone().unwrap_or_default(),
+            PathBuf::new(),
+        );
+        resource.set_id(self.id).map_err(FynApiError::Decode)?;
+        Ok(resource)
     }
 }
@@ -0,0 +1,316 @@
+/* ------------------------------------------------------------------------------------------------
+ * Fyn-Front: Modern CFD/CAE Web Interface
+ * Copyright (C) 2025 Fyn-Front Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>.
+ * ------------------------------------------------------------------------------------------------
+ * filename: fyn_api_error.rs
+ * description: Typed errors returned by the Fyn backend API client
+ * ------------------------------------------------------------------------------------------------
+ */
+
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::fmt;
+
+use fyn_api::apis::Error as ApiError;
+
+/// Key used by Django REST framework for errors not tied to a single field.
+pub const NON_FIELD_ERRORS: &str = "non_field_errors";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FynApiError {
+    /// The request never reached the backend, or its response was cut off.
+    Network(String),
+    /// The request could not be built in the browser, e.g. a header or form field was refused.
+    Request(String),
+    /// Missing or expired credentials (401).
+    Unauthorized(String),
+    /// Authenticated, but not permitted to perform the action (403).
+    Forbidden(String),
+    /// The requested object does not exist (404).
+    NotFound(String),
+    /// The backend rejected the submitted data (400), messages are keyed by field name.
+    Validation(IndexMap<String, Vec<String>>),
+    /// The backend failed (5xx) or replied with a status the client does not handle.
+    Server { status: u16, message: String },
+    /// The response (or a token) could not be decoded into the expected model.
+    Decode(String),
+}
+
+impl FynApiError {
+    /// Classifies an HTTP error response by status code, parsing the body where it carries detail.
+    pub fn from_response(status: u16, body: &str) -> Self {
+        match status {
+            400 => FynApiError::Validation(parse_field_errors(body)),
+            401 => FynApiError::Unauthorized(parse_detail(body)),
+            403 => FynApiError::Forbidden(parse_detail(body)),
+            404 => FynApiError::NotFound(parse_detail(body)),
+            _ => FynApiError::Server {
+                status,
+                message: parse_detail(body),
+            },
+        }
+    }
+
+    /// Validation messages for a single field, if the backend reported any.
+    pub fn field_errors(&self, field: &str) -> Option<&Vec<String>> {
+        match self {
+            FynApiError::Validation(errors) => errors.get(field),
+            _ => None,
+        }
+    }
+
+    pub fn is_auth_error(&self) -> bool {
+        matches!(
+            self,
+            FynApiError::Unauthorized(_) | FynApiError::Forbidden(_)
+        )
+    }
+}
+
+impl fmt::Display for FynApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FynApiError::Network(msg) => write!(f, "Could not reach the server: {}", msg),
+            FynApiError::Request(msg) => write!(f, "Could not prepare the request: {}", msg),
+            FynApiError::Unauthorized(_) => {
+                write!(f, "Your session has expired, please sign in again")
+            }
+            FynApiError::Forbidden(_) => {
+                write!(f, "You do not have permission to perform this action")
+            }
+            FynApiError::NotFound(_) => write!(f, "The requested item could not be found"),
+            FynApiError::Validation(errors) => {
+                let messages = errors
+                    .iter()
+                    .map(|(field, msgs)| match field.as_str() {
+                        NON_FIELD_ERRORS | "detail" => msgs.join(" "),
+                        _ => format!("{}: {}", field, msgs.join(" ")),
+                    })
+                    .collect::<Vec<String>>();
+                write!(f, "{}", messages.join("; "))
+            }
+            FynApiError::Server { status, message } => {
+                write!(f, "Server error ({}): {}", status, message)
+            }
+            FynApiError::Decode(msg) => write!(f, "Unexpected response from server: {}", msg),
+        }
+    }
+}
+
+impl<T> From<ApiError<T>> for FynApiError {
+    fn from(error: ApiError<T>) -> Self {
+        match error {
+            ApiError::Reqwest(e) if e.is_decode() => FynApiError::Decode(e.to_string()),
+            ApiError::Reqwest(e) => FynApiError::Network(e.to_string()),
+            ApiError::Serde(e) => FynApiError::Decode(e.to_string()),
+            ApiError::Io(e) => FynApiError::Network(e.to_string()),
+            ApiError::ResponseError(content) => {
+                FynApiError::from_response(content.status.as_u16(), &content.content)
+            }
+        }
+    }
+}
+
+impl From<reqwest::Error> for FynApiError {
+    fn from(error: reqwest::Error) -> Self {
+        match error.is_decode() {
+            true => FynApiError::Decode(error.to_string()),
+            false => FynApiError::Network(error.to_string()),
+        }
+    }
+}
+
+// -------------------------------------------------------------------------------------------------
+//  Django Response Parsing
+// -------------------------------------------------------------------------------------------------
+
+/// Flattens a Django REST framework error body into per-field messages. Nested serializer errors
+/// are keyed with dotted paths (e.g. `config.n_procs`, `resources.0`).
+fn parse_field_errors(body: &str) -> IndexMap<String, Vec<String>> {
+    let mut errors = IndexMap::new();
+    match serde_json::from_str::<Value>(body) {
+        Ok(value) => collect_field_errors(NON_FIELD_ERRORS, &value, &mut errors),
+        Err(_) => {
+            errors.insert(NON_FIELD_ERRORS.to_string(), vec![body.to_string()]);
+        }
+    }
+    errors
+}
+
+fn collect_field_errors(path: &str, value: &Value, errors: &mut IndexMap<String, Vec<String>>) {
+    let child_path = |key: &str| match path {
+        NON_FIELD_ERRORS => key.to_string(),
+        _ => format!("{}.{}", path, key),
+    };
+
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                collect_field_errors(&child_path(key), child, errors);
+            }
+        }
+        Value::Array(items)
+            if items
+                .iter()
+                .all(|item| !item.is_object() && !item.is_array()) =>
+        {
+            for item in items {
+                collect_field_errors(path, item, errors);
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                collect_field_errors(&child_path(&index.to_string()), child, errors);
+            }
+        }
+        Value::String(msg) => errors
+            .entry(path.to_string())
+            .or_default()
+            .push(msg.clone()),
+        Value::Null => {}
+        other => errors
+            .entry(path.to_string())
+            .or_default()
+            .push(other.to_string()),
+    }
+}
+
+/// Extracts the `detail` message Django attaches to non-validation errors.
+fn parse_detail(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|value| value["detail"].as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| body.to_string())
+}
+
+// -------------------------------------------------------------------------------------------------
+//  Tests
+// -------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field_errors(body: &str) -> Vec<(String, Vec<String>)> {
+        parse_field_errors(body).into_iter().collect()
+    }
+
+    fn messages(messages: &[&str]) -> Vec<String> {
+        messages.iter().map(|message| message.to_string()).collect()
+    }
+
+    #[test]
+    fn classifies_responses_by_status() {
+        let detail = r#"{"detail": "Not found."}"#;
+        assert_eq!(
+            FynApiError::from_response(401, detail),
+            FynApiError::Unauthorized("Not found.".to_string())
+        );
+        assert_eq!(
+            FynApiError::from_response(403, detail),
+            FynApiError::Forbidden("Not found.".to_string())
+        );
+        assert_eq!(
+            FynApiError::from_response(404, detail),
+            FynApiError::NotFound("Not found.".to_string())
+        );
+        assert_eq!(
+            FynApiError::from_response(502, "Bad Gateway"),
+            FynApiError::Server {
+                status: 502,
+                message: "Bad Gateway".to_string()
+            }
+        );
+        let validation = FynApiError::from_response(400, r#"{"name": ["Required."]}"#);
+        assert_eq!(
+            validation.field_errors("name"),
+            Some(&messages(&["Required."]))
+        );
+    }
+
+    #[test]
+    fn parses_field_error_maps() {
+        let body = r#"{"name": ["This field is required.", "Too short."], "priority": "Invalid."}"#;
+        assert_eq!(
+            field_errors(body),
+            vec![
+                (
+                    "name".to_string(),
+                    messages(&["This field is required.", "Too short."])
+                ),
+                ("priority".to_string(), messages(&["Invalid."])),
+            ]
+        );
+    }
+
+    #[test]
+    fn keys_nested_field_errors_by_dotted_path() {
+        let body = r#"{"config": {"n_procs": ["Must be positive."]}, "resources": [{}, {"file": ["Missing."]}]}"#;
+        assert_eq!(
+            field_errors(body),
+            vec![
+                (
+                    "config.n_procs".to_string(),
+                    messages(&["Must be positive."])
+                ),
+                ("resources.1.file".to_string(), messages(&["Missing."])),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_non_field_errors_apart() {
+        let body = r#"{"non_field_errors": ["Passwords do not match."]}"#;
+        let error = FynApiError::from_response(400, body);
+        assert_eq!(
+            error.field_errors(NON_FIELD_ERRORS),
+            Some(&messages(&["Passwords do not match."]))
+        );
+        assert_eq!(error.to_string(), "Passwords do not match.");
+
+        // a bare list is not tied to any field either
+        assert_eq!(
+            field_errors(r#"["Job is already running."]"#),
+            vec![(
+                NON_FIELD_ERRORS.to_string(),
+                messages(&["Job is already running."])
+            )]
+        );
+    }
+
+    #[test]
+    fn parses_detail_messages() {
+        assert_eq!(
+            parse_detail(r#"{"detail": "Authentication credentials were not provided."}"#),
+            "Authentication credentials were not provided."
+        );
+        assert_eq!(
+            field_errors(r#"{"detail": "Invalid input."}"#),
+            vec![("detail".to_string(), messages(&["Invalid input."]))]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_raw_body_when_it_is_not_json() {
+        let body = "<html>Internal Server Error</html>";
+        assert_eq!(parse_detail(body), body);
+        assert_eq!(parse_detail(r#"{"error": "x"}"#), r#"{"error": "x"}"#);
+        assert_eq!(
+            field_errors(body),
+            vec![(NON_FIELD_ERRORS.to_string(), messages(&[body]))]
+        );
+    }
+}
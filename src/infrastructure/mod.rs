@@ -22,3 +22,4 @@
 
 
 pub mod fyn_api_client;
+pub mod fyn_api_error;
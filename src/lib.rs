@@ -36,6 +36,7 @@ use crate::infrastructure::fyn_api_client::FynApiClient;
 
 // Top-Level pages
 use crate::application::home::Home;
+use crate::application::job_detail::JobDetail;
 use crate::application::not_found::NotFound;
 use crate::application::register::Register;
 use crate::application::showcase::Showcase;
@@ -54,11 +55,21 @@ pub fn App() -> impl IntoView {
 
     // recover session.
     let user_context: RwSignal<Option<UserContext>> = RwSignal::new(None);
-    spawn_local(async move {
-        user_context.set(fyn_api_client_context.restore_session().await);
+    spawn_local({
+        let fyn_api_client_context = fyn_api_client_context.clone();
+        async move {
+            user_context.set(fyn_api_client_context.restore_session().await);
+        }
     });
     provide_context(user_context);
 
+    // drop the user once the client loses its session, e.g. the refresh token has expired.
+    Effect::new(move |_| {
+        if !fyn_api_client_context.is_authenticated() && user_context.get_untracked().is_some() {
+            user_context.set(None);
+        }
+    });
+
     view! {
         <Html attr:lang="en" attr:dir="ltr" attr:data-theme="light" />
 
@@ -74,6 +85,7 @@ pub fn App() -> impl IntoView {
                 <Routes fallback=NotFound >
                     <Route path=path!("/") view=Home />
                     <Route path=path!("/simulate") view=Simulate />
+                    <Route path=path!("/jobs/:id") view=JobDetail />
                     <Route path=path!("/register") view=Register />
                     <Route path=path!("/sign_in") view=SignIn />
                     <Route path=path!("/showcase") view=Showcase />  // Add this